@@ -1,3 +1,10 @@
 pub mod ecc_chip;
+pub mod ecdsa_chip;
+pub mod fp12_integer_chip;
+pub mod fp2_integer_chip;
+pub mod fp6_integer_chip;
+pub mod g2_ecc_chip;
+pub mod general_ecc_chip;
 pub mod integer_chip;
 pub mod native_ecc_chip;
+pub mod pairing_chip;
@@ -67,7 +67,16 @@ impl<'a, C: CurveAffine, N: FieldExt> EccChip<'a, C, N> {
     }
 }
 
-const CONFIG_WINDOW_SIZE: usize = 4usize;
+pub const CONFIG_WINDOW_SIZE: usize = 4usize;
+// Points per batch of `bucket_msm`, and the point count above which `msm` uses it,
+// counted after the GLV split in `glv_msm`. With a batch of 4 points a bit of the
+// scalars costs 15 bisec per batch, as the 4 bits of a window cost per point in
+// `shamir`, so `bucket_msm` only saves the candidates: 11 adds per batch instead of
+// 14 per point, for 3 more doublings. From one full batch it takes no more adds,
+// bisec and curvatures than `shamir`, below the choice depends on the rows of an add
+// against a bisec, see `bench_five_column_msm_rows`.
+pub const CONFIG_BUCKET_WINDOW_SIZE: usize = 4usize;
+pub const CONFIG_BUCKET_MSM_THRESHOLD: usize = 3usize;
 
 pub trait EccChipOps<C: CurveAffine, N: FieldExt> {
     type AssignedScalar;
@@ -142,13 +151,25 @@ pub trait EccChipOps<C: CurveAffine, N: FieldExt> {
         points: &mut Vec<AssignedPoint<C, N>>,
         scalars: &Vec<Self::AssignedScalar>,
     ) -> Result<AssignedPoint<C, N>, Error> {
-        assert!(CONFIG_WINDOW_SIZE >= 1usize);
         assert!(points.len() == scalars.len());
         let windows_in_be = scalars
             .iter()
             .map(|s| self.decompose_scalar::<CONFIG_WINDOW_SIZE>(ctx, s))
             .collect::<Result<Vec<_>, _>>()?;
 
+        self.shamir_windows(ctx, points, &windows_in_be)
+    }
+    // Same as `shamir` with the scalars already decomposed, every scalar must have
+    // the same number of windows.
+    fn shamir_windows(
+        &self,
+        ctx: &mut Context<N>,
+        points: &mut Vec<AssignedPoint<C, N>>,
+        windows_in_be: &[Vec<[AssignedCondition<N>; CONFIG_WINDOW_SIZE]>],
+    ) -> Result<AssignedPoint<C, N>, Error> {
+        assert!(CONFIG_WINDOW_SIZE >= 1usize);
+        assert!(points.len() == windows_in_be.len());
+
         let identity = self.assign_identity(ctx)?;
         let point_candidates: Vec<Vec<AssignedPoint<_, _>>> = points
             .iter_mut()
@@ -163,11 +184,31 @@ pub trait EccChipOps<C: CurveAffine, N: FieldExt> {
             })
             .collect::<Result<Vec<_>, Error>>()?;
 
+        let selectors = (0..windows_in_be[0].len())
+            .map(|wi| {
+                windows_in_be
+                    .iter()
+                    .map(|windows| windows[wi].to_vec())
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+
+        self.sum_of_candidates(ctx, &point_candidates, &selectors, CONFIG_WINDOW_SIZE)
+    }
+    // Computes sum_i(2 ^ (doublings * (rounds - 1 - i)) * sum_j(candidates[j][selectors[i][j]]))
+    // where the selectors are little endian bits.
+    fn sum_of_candidates(
+        &self,
+        ctx: &mut Context<N>,
+        candidates: &[Vec<AssignedPoint<C, N>>],
+        selectors: &[Vec<Vec<AssignedCondition<N>>>],
+        doublings: usize,
+    ) -> Result<AssignedPoint<C, N>, Error> {
         let pick_candidate = |ctx: &mut Context<N>,
-                              pi: usize,
-                              bits_in_le: &[AssignedCondition<N>; CONFIG_WINDOW_SIZE]|
+                              gi: usize,
+                              bits_in_le: &[AssignedCondition<N>]|
          -> Result<AssignedPoint<C, N>, Error> {
-            let mut curr_candidates: Vec<AssignedPoint<_, _>> = point_candidates[pi].clone();
+            let mut curr_candidates: Vec<AssignedPoint<_, _>> = candidates[gi].clone();
             for bit in bits_in_le {
                 let mut next_candidates = vec![];
                 let len = curr_candidates.len() / 2;
@@ -190,7 +231,7 @@ pub trait EccChipOps<C: CurveAffine, N: FieldExt> {
 
         let mut round_size = None;
 
-        for wi in 0..windows_in_be[0].len() {
+        for (wi, round_selectors) in selectors.iter().enumerate() {
             let mut get_inner =
                 |round_size: Option<usize>| -> Result<(usize, AssignedPoint<C, N>), Error> {
                     match (round_size, ctx.in_shape_mode()) {
@@ -206,8 +247,8 @@ pub trait EccChipOps<C: CurveAffine, N: FieldExt> {
                         _ => {
                             let c = *ctx.offset;
                             let mut inner_acc = None;
-                            for pi in 0..points.len() {
-                                let mut ci = pick_candidate(ctx, pi, &windows_in_be[pi][wi])?;
+                            for (gi, bits_in_le) in round_selectors.iter().enumerate() {
+                                let mut ci = pick_candidate(ctx, gi, bits_in_le)?;
                                 match inner_acc {
                                     None => inner_acc = Some(ci),
                                     Some(_inner_acc) => {
@@ -231,7 +272,7 @@ pub trait EccChipOps<C: CurveAffine, N: FieldExt> {
             match acc {
                 None => acc = Some(inner_acc),
                 Some(mut _acc) => {
-                    for _ in 0..CONFIG_WINDOW_SIZE {
+                    for _ in 0..doublings {
                         _acc = self.double(ctx, &mut _acc)?;
                     }
                     _acc = self.add(ctx, &mut inner_acc, &_acc)?;
@@ -242,6 +283,114 @@ pub trait EccChipOps<C: CurveAffine, N: FieldExt> {
 
         Ok(acc.unwrap())
     }
+    // Bucket method msm, the points are split into batches of at most WINDOW_SIZE
+    // points. The buckets of a batch are the sums of its subsets, so each bit of
+    // the scalars costs one bucket selection and one add per batch, instead of
+    // one selection per point as in `shamir`.
+    fn bucket_msm<const WINDOW_SIZE: usize>(
+        &self,
+        ctx: &mut Context<N>,
+        points: &mut Vec<AssignedPoint<C, N>>,
+        scalars: &Vec<Self::AssignedScalar>,
+    ) -> Result<AssignedPoint<C, N>, Error> {
+        assert!(points.len() == scalars.len());
+        let windows_in_be = scalars
+            .iter()
+            .map(|s| self.decompose_scalar::<CONFIG_WINDOW_SIZE>(ctx, s))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.bucket_msm_windows::<WINDOW_SIZE>(ctx, points, &windows_in_be)
+    }
+    fn bucket_msm_windows<const WINDOW_SIZE: usize>(
+        &self,
+        ctx: &mut Context<N>,
+        points: &mut Vec<AssignedPoint<C, N>>,
+        windows_in_be: &[Vec<[AssignedCondition<N>; CONFIG_WINDOW_SIZE]>],
+    ) -> Result<AssignedPoint<C, N>, Error> {
+        assert!(WINDOW_SIZE >= 1usize);
+        assert!(points.len() == windows_in_be.len());
+
+        let bits_in_be = windows_in_be
+            .iter()
+            .map(|windows| {
+                windows
+                    .iter()
+                    .flat_map(|window| window.iter().rev().cloned())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Balance the batches so that the last one is not almost empty
+        let batches = (points.len() + WINDOW_SIZE - 1) / WINDOW_SIZE;
+        let mut batch_ranges = vec![];
+        let mut start = 0;
+        for bi in 0..batches {
+            let rest_batches = batches - bi;
+            let end = start + (points.len() - start + rest_batches - 1) / rest_batches;
+            batch_ranges.push(start..end);
+            start = end;
+        }
+
+        // buckets[mask] is the sum of the points of the batch selected by mask
+        let identity = self.assign_identity(ctx)?;
+        let buckets = batch_ranges
+            .iter()
+            .map(|range| {
+                let mut buckets = vec![identity.clone()];
+                for pi in range.clone() {
+                    for mask in 0..buckets.len() {
+                        let mut bucket = if mask == 0 {
+                            points[pi].clone()
+                        } else {
+                            self.add(ctx, &mut buckets[mask], &points[pi])?
+                        };
+                        self.curvature(ctx, &mut bucket)?;
+                        buckets.push(bucket);
+                    }
+                }
+                Ok(buckets)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let selectors = (0..bits_in_be[0].len())
+            .map(|bi| {
+                batch_ranges
+                    .iter()
+                    .map(|range| range.clone().map(|pi| bits_in_be[pi][bi]).collect())
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+
+        self.sum_of_candidates(ctx, &buckets, &selectors, 1)
+    }
+    // Multi scalar multiplication with `bucket_msm` for many points and `shamir`
+    // otherwise.
+    fn msm(
+        &self,
+        ctx: &mut Context<N>,
+        points: &mut Vec<AssignedPoint<C, N>>,
+        scalars: &Vec<Self::AssignedScalar>,
+    ) -> Result<AssignedPoint<C, N>, Error> {
+        assert!(points.len() == scalars.len());
+        let windows_in_be = scalars
+            .iter()
+            .map(|s| self.decompose_scalar::<CONFIG_WINDOW_SIZE>(ctx, s))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.msm_windows(ctx, points, &windows_in_be)
+    }
+    fn msm_windows(
+        &self,
+        ctx: &mut Context<N>,
+        points: &mut Vec<AssignedPoint<C, N>>,
+        windows_in_be: &[Vec<[AssignedCondition<N>; CONFIG_WINDOW_SIZE]>],
+    ) -> Result<AssignedPoint<C, N>, Error> {
+        if points.len() > CONFIG_BUCKET_MSM_THRESHOLD {
+            self.bucket_msm_windows::<CONFIG_BUCKET_WINDOW_SIZE>(ctx, points, windows_in_be)
+        } else {
+            self.shamir_windows(ctx, points, windows_in_be)
+        }
+    }
     fn constant_mul(
         &self,
         ctx: &mut Context<N>,
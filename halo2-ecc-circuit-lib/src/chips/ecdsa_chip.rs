@@ -0,0 +1,94 @@
+use super::{
+    ecc_chip::{AssignedPoint, EccChipOps},
+    general_ecc_chip::GeneralEccChip,
+    integer_chip::{AssignedInteger, IntegerChipOps},
+};
+use crate::gates::base_gate::Context;
+use group::ff::Field;
+use group::Group;
+use halo2_proofs::{arithmetic::CurveAffine, plonk::Error};
+use halo2curves::FieldExt;
+
+#[derive(Clone, Debug)]
+pub struct AssignedEcdsaSignature<C: CurveAffine, N: FieldExt> {
+    pub r: AssignedInteger<C::ScalarExt, N>,
+    pub s: AssignedInteger<C::ScalarExt, N>,
+}
+
+impl<C: CurveAffine, N: FieldExt> AssignedEcdsaSignature<C, N> {
+    pub fn new(r: AssignedInteger<C::ScalarExt, N>, s: AssignedInteger<C::ScalarExt, N>) -> Self {
+        Self { r, s }
+    }
+}
+
+pub struct EcdsaChip<'a, C: CurveAffine, N: FieldExt> {
+    pub ecc_chip: GeneralEccChip<'a, C, N>,
+}
+
+impl<'a, C: CurveAffine, N: FieldExt> EcdsaChip<'a, C, N> {
+    pub fn new(
+        integer_chip: &'a dyn IntegerChipOps<C::Base, N>,
+        scalar_integer_chip: &'a dyn IntegerChipOps<C::ScalarExt, N>,
+    ) -> Self {
+        Self {
+            ecc_chip: GeneralEccChip::new(integer_chip, scalar_integer_chip),
+        }
+    }
+
+    pub fn assign_signature(
+        &self,
+        ctx: &mut Context<N>,
+        r: C::ScalarExt,
+        s: C::ScalarExt,
+    ) -> Result<AssignedEcdsaSignature<C, N>, Error> {
+        let scalar_integer_chip = self.ecc_chip.scalar_integer_chip;
+        let r = scalar_integer_chip.assign_w(ctx, &r)?;
+        let s = scalar_integer_chip.assign_w(ctx, &s)?;
+        Ok(AssignedEcdsaSignature::new(r, s))
+    }
+
+    // Constrains sig to be a signature of msg_hash by pk, i.e. r = x mod n where
+    // (x, y) = (msg_hash / s) * G + (r / s) * pk.
+    pub fn verify(
+        &self,
+        ctx: &mut Context<N>,
+        pk: &mut AssignedPoint<C, N>,
+        msg_hash: &mut AssignedInteger<C::ScalarExt, N>,
+        sig: &mut AssignedEcdsaSignature<C, N>,
+    ) -> Result<(), Error> {
+        let base_gate = self.ecc_chip.base_gate();
+        let scalar_integer_chip = self.ecc_chip.scalar_integer_chip;
+
+        base_gate.assert_false(ctx, &pk.z)?;
+        let r_zero = scalar_integer_chip.is_zero(ctx, &mut sig.r)?;
+        base_gate.assert_false(ctx, &r_zero)?;
+
+        let mut one = scalar_integer_chip.assign_constant(ctx, C::ScalarExt::one())?;
+        let (s_zero, mut s_inv) = scalar_integer_chip.div(ctx, &mut one, &mut sig.s)?;
+        base_gate.assert_false(ctx, &s_zero)?;
+        let u1 = scalar_integer_chip.mul(ctx, msg_hash, &mut s_inv)?;
+        let u2 = scalar_integer_chip.mul(ctx, &mut sig.r, &mut s_inv)?;
+
+        let u1_g = self
+            .ecc_chip
+            .constant_mul(ctx, C::CurveExt::generator(), &u1)?;
+        let mut u2_pk = self.ecc_chip.mul(ctx, pk, &u2)?;
+        let mut p = self.ecc_chip.add(ctx, &mut u2_pk, &u1_g)?;
+        base_gate.assert_false(ctx, &p.z)?;
+
+        self.assert_r_of_x(ctx, &mut p.x, &sig.r)
+    }
+
+    // Constrains r = x mod n. The limbs of x read as an integer of the scalar field,
+    // which is x mod n only when they hold the canonical x, else x + p would do.
+    pub fn assert_r_of_x(
+        &self,
+        ctx: &mut Context<N>,
+        x: &mut AssignedInteger<C::Base, N>,
+        r: &AssignedInteger<C::ScalarExt, N>,
+    ) -> Result<(), Error> {
+        self.ecc_chip.integer_chip().assert_canonical(ctx, x)?;
+        let x = AssignedInteger::<C::ScalarExt, N>::new(x.limbs_le.clone(), x.overflows);
+        self.ecc_chip.scalar_integer_chip.assert_equal(ctx, &x, r)
+    }
+}
@@ -0,0 +1,212 @@
+use super::{
+    fp2_integer_chip::AssignedFq2,
+    fp6_integer_chip::{AssignedFq6, Fp6IntegerChip},
+    integer_chip::{AssignedInteger, IntegerChipOps},
+};
+use crate::{gates::base_gate::Context, utils::field_to_bn};
+use group::ff::Field;
+use halo2_proofs::plonk::Error;
+use halo2curves::{
+    bn256::{Fq, Fq12, Fq2},
+    FieldExt,
+};
+
+// Element c0 + c1 * w of Fq12 = Fq6[w] / (w^2 - v).
+#[derive(Clone, Debug)]
+pub struct AssignedFq12<N: FieldExt> {
+    pub c0: AssignedFq6<N>,
+    pub c1: AssignedFq6<N>,
+}
+
+impl<N: FieldExt> AssignedFq12<N> {
+    pub fn new(c0: AssignedFq6<N>, c1: AssignedFq6<N>) -> Self {
+        Self { c0, c1 }
+    }
+}
+
+// (9 + u)^(k * (p^power - 1) / 6) for k in 0..6, c1.ci (resp. c0.ci) is the
+// coefficient of w^(2 * i + 1) (resp. w^(2 * i)) and w^6 = 9 + u.
+pub fn frobenius_coeffs(power: usize) -> [Fq2; 6] {
+    let p = field_to_bn(&-Fq::one()) + 1u64;
+    let exp = (p.pow(power as u32) - 1u64) / 6u64;
+    let xi = Fq2 {
+        c0: Fq::from(9),
+        c1: Fq::one(),
+    };
+    let gamma = xi.pow_vartime(exp.to_u64_digits());
+
+    let mut coeffs = [Fq2::one(); 6];
+    let mut acc = Fq2::one();
+    for coeff in coeffs.iter_mut() {
+        *coeff = acc;
+        acc *= gamma;
+    }
+    coeffs
+}
+
+pub struct Fp12IntegerChip<'a, N: FieldExt> {
+    pub fp6_chip: Fp6IntegerChip<'a, N>,
+}
+
+impl<'a, N: FieldExt> Fp12IntegerChip<'a, N> {
+    pub fn new(integer_chip: &'a dyn IntegerChipOps<Fq, N>) -> Self {
+        Self {
+            fp6_chip: Fp6IntegerChip::new(integer_chip),
+        }
+    }
+
+    pub fn assign(&self, ctx: &mut Context<N>, v: Fq12) -> Result<AssignedFq12<N>, Error> {
+        let c0 = self.fp6_chip.assign(ctx, v.c0)?;
+        let c1 = self.fp6_chip.assign(ctx, v.c1)?;
+        Ok(AssignedFq12::new(c0, c1))
+    }
+
+    pub fn assign_constant(&self, ctx: &mut Context<N>, v: Fq12) -> Result<AssignedFq12<N>, Error> {
+        let c0 = self.fp6_chip.assign_constant(ctx, v.c0)?;
+        let c1 = self.fp6_chip.assign_constant(ctx, v.c1)?;
+        Ok(AssignedFq12::new(c0, c1))
+    }
+
+    pub fn get_fq12(&self, a: &AssignedFq12<N>) -> Result<Fq12, Error> {
+        Ok(Fq12 {
+            c0: self.fp6_chip.get_fq6(&a.c0)?,
+            c1: self.fp6_chip.get_fq6(&a.c1)?,
+        })
+    }
+
+    pub fn mul(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq12<N>,
+        b: &mut AssignedFq12<N>,
+    ) -> Result<AssignedFq12<N>, Error> {
+        let fp6_chip = &self.fp6_chip;
+
+        // c0 = a0 * b0 + v * a1 * b1, c1 = (a0 + a1) * (b0 + b1) - a0 * b0 - a1 * b1
+        let t0 = fp6_chip.mul(ctx, &mut a.c0, &mut b.c0)?;
+        let mut t1 = fp6_chip.mul(ctx, &mut a.c1, &mut b.c1)?;
+        let t1_v = fp6_chip.mul_by_nonresidue(ctx, &mut t1)?;
+        let c0 = fp6_chip.add(ctx, &t0, &t1_v)?;
+
+        let mut a01 = fp6_chip.add(ctx, &a.c0, &a.c1)?;
+        let mut b01 = fp6_chip.add(ctx, &b.c0, &b.c1)?;
+        let s = fp6_chip.mul(ctx, &mut a01, &mut b01)?;
+        let s = fp6_chip.sub(ctx, &s, &t0)?;
+        let c1 = fp6_chip.sub(ctx, &s, &t1)?;
+
+        Ok(AssignedFq12::new(c0, c1))
+    }
+
+    pub fn square(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq12<N>,
+    ) -> Result<AssignedFq12<N>, Error> {
+        let fp6_chip = &self.fp6_chip;
+
+        // c0 = (a0 + a1) * (a0 + v * a1) - t - v * t, c1 = 2 * t, where t = a0 * a1
+        let mut t = fp6_chip.mul(ctx, &mut a.c0, &mut a.c1)?;
+        let t_v = fp6_chip.mul_by_nonresidue(ctx, &mut t)?;
+
+        let mut a01 = fp6_chip.add(ctx, &a.c0, &a.c1)?;
+        let a1_v = fp6_chip.mul_by_nonresidue(ctx, &mut a.c1)?;
+        let mut a01_v = fp6_chip.add(ctx, &a.c0, &a1_v)?;
+        let s = fp6_chip.mul(ctx, &mut a01, &mut a01_v)?;
+        let s = fp6_chip.sub(ctx, &s, &t)?;
+        let c0 = fp6_chip.sub(ctx, &s, &t_v)?;
+        let c1 = fp6_chip.add(ctx, &t, &t)?;
+
+        Ok(AssignedFq12::new(c0, c1))
+    }
+
+    // Multiplies by a + b * w + c * w^3, the shape of the line functions of the
+    // miller loop, with a in Fq.
+    pub fn mul_by_013(
+        &self,
+        ctx: &mut Context<N>,
+        f: &mut AssignedFq12<N>,
+        a: &mut AssignedInteger<Fq, N>,
+        b: &mut AssignedFq2<N>,
+        c: &mut AssignedFq2<N>,
+    ) -> Result<AssignedFq12<N>, Error> {
+        let fp6_chip = &self.fp6_chip;
+        let integer_chip = fp6_chip.fp2_chip.integer_chip;
+
+        // (a + (b + c * v) * w) * (f0 + f1 * w)
+        let t0 = fp6_chip.mul_by_fq(ctx, &mut f.c0, a)?;
+        let mut t1 = fp6_chip.mul_by_01(ctx, &mut f.c1, b, c)?;
+        let t1_v = fp6_chip.mul_by_nonresidue(ctx, &mut t1)?;
+        let c0 = fp6_chip.add(ctx, &t0, &t1_v)?;
+
+        let mut f01 = fp6_chip.add(ctx, &f.c0, &f.c1)?;
+        let mut ab = AssignedFq2::new(integer_chip.add(ctx, &b.c0, a)?, b.c1.clone());
+        let s = fp6_chip.mul_by_01(ctx, &mut f01, &mut ab, c)?;
+        let s = fp6_chip.sub(ctx, &s, &t0)?;
+        let c1 = fp6_chip.sub(ctx, &s, &t1)?;
+
+        Ok(AssignedFq12::new(c0, c1))
+    }
+
+    // The inverse in the cyclotomic subgroup, which the final exponentiation
+    // works in after its easy part.
+    pub fn conjugate(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedFq12<N>,
+    ) -> Result<AssignedFq12<N>, Error> {
+        let c1 = self.fp6_chip.neg(ctx, &a.c1)?;
+        Ok(AssignedFq12::new(a.c0.clone(), c1))
+    }
+
+    // Raises to p^power.
+    pub fn frobenius_map(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedFq12<N>,
+        power: usize,
+    ) -> Result<AssignedFq12<N>, Error> {
+        let fp2_chip = &self.fp6_chip.fp2_chip;
+        let coeffs = frobenius_coeffs(power);
+
+        let mut map = |c: &AssignedFq2<N>, k: usize| -> Result<AssignedFq2<N>, Error> {
+            let mut c = if power % 2 == 1 {
+                fp2_chip.conjugate(ctx, c)?
+            } else {
+                c.clone()
+            };
+            if k == 0 {
+                Ok(c)
+            } else {
+                fp2_chip.mul_constant(ctx, &mut c, coeffs[k])
+            }
+        };
+
+        let c0 = AssignedFq6::new(map(&a.c0.c0, 0)?, map(&a.c0.c1, 2)?, map(&a.c0.c2, 4)?);
+        let c1 = AssignedFq6::new(map(&a.c1.c0, 1)?, map(&a.c1.c1, 3)?, map(&a.c1.c2, 5)?);
+        Ok(AssignedFq12::new(c0, c1))
+    }
+
+    // a must not be zero.
+    pub fn invert(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq12<N>,
+    ) -> Result<AssignedFq12<N>, Error> {
+        let a_inv = self.get_fq12(a)?.invert().unwrap_or(Fq12::zero());
+        let mut a_inv = self.assign(ctx, a_inv)?;
+        let one = self.assign_constant(ctx, Fq12::one())?;
+        let prod = self.mul(ctx, a, &mut a_inv)?;
+        self.assert_equal(ctx, &prod, &one)?;
+        Ok(a_inv)
+    }
+
+    pub fn assert_equal(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedFq12<N>,
+        b: &AssignedFq12<N>,
+    ) -> Result<(), Error> {
+        self.fp6_chip.assert_equal(ctx, &a.c0, &b.c0)?;
+        self.fp6_chip.assert_equal(ctx, &a.c1, &b.c1)
+    }
+}
@@ -0,0 +1,269 @@
+use super::integer_chip::{AssignedInteger, IntegerChipOps};
+use crate::gates::base_gate::{AssignedCondition, BaseGateOps, Context};
+use group::ff::Field;
+use halo2_proofs::plonk::Error;
+use halo2curves::{
+    bn256::{Fq, Fq2},
+    FieldExt,
+};
+
+// Element c0 + c1 * u of Fq2 = Fq[u] / (u^2 + 1).
+#[derive(Clone, Debug)]
+pub struct AssignedFq2<N: FieldExt> {
+    pub c0: AssignedInteger<Fq, N>,
+    pub c1: AssignedInteger<Fq, N>,
+}
+
+impl<N: FieldExt> AssignedFq2<N> {
+    pub fn new(c0: AssignedInteger<Fq, N>, c1: AssignedInteger<Fq, N>) -> Self {
+        Self { c0, c1 }
+    }
+}
+
+pub struct Fp2IntegerChip<'a, N: FieldExt> {
+    pub integer_chip: &'a dyn IntegerChipOps<Fq, N>,
+}
+
+impl<'a, N: FieldExt> Fp2IntegerChip<'a, N> {
+    pub fn new(integer_chip: &'a dyn IntegerChipOps<Fq, N>) -> Self {
+        Self { integer_chip }
+    }
+
+    pub fn base_gate(&self) -> &dyn BaseGateOps<N> {
+        self.integer_chip.base_gate()
+    }
+
+    pub fn assign(&self, ctx: &mut Context<N>, v: Fq2) -> Result<AssignedFq2<N>, Error> {
+        let c0 = self.integer_chip.assign_w(ctx, &v.c0)?;
+        let c1 = self.integer_chip.assign_w(ctx, &v.c1)?;
+        Ok(AssignedFq2::new(c0, c1))
+    }
+
+    pub fn assign_constant(&self, ctx: &mut Context<N>, v: Fq2) -> Result<AssignedFq2<N>, Error> {
+        let c0 = self.integer_chip.assign_constant(ctx, v.c0)?;
+        let c1 = self.integer_chip.assign_constant(ctx, v.c1)?;
+        Ok(AssignedFq2::new(c0, c1))
+    }
+
+    pub fn get_fq2(&self, a: &AssignedFq2<N>) -> Result<Fq2, Error> {
+        Ok(Fq2 {
+            c0: self.integer_chip.get_w(&a.c0)?,
+            c1: self.integer_chip.get_w(&a.c1)?,
+        })
+    }
+
+    pub fn reduce(&self, ctx: &mut Context<N>, a: &mut AssignedFq2<N>) -> Result<(), Error> {
+        self.integer_chip.reduce(ctx, &mut a.c0)?;
+        self.integer_chip.reduce(ctx, &mut a.c1)
+    }
+
+    pub fn add(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedFq2<N>,
+        b: &AssignedFq2<N>,
+    ) -> Result<AssignedFq2<N>, Error> {
+        let c0 = self.integer_chip.add(ctx, &a.c0, &b.c0)?;
+        let c1 = self.integer_chip.add(ctx, &a.c1, &b.c1)?;
+        Ok(AssignedFq2::new(c0, c1))
+    }
+
+    pub fn sub(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedFq2<N>,
+        b: &AssignedFq2<N>,
+    ) -> Result<AssignedFq2<N>, Error> {
+        let c0 = self.integer_chip.sub(ctx, &a.c0, &b.c0)?;
+        let c1 = self.integer_chip.sub(ctx, &a.c1, &b.c1)?;
+        Ok(AssignedFq2::new(c0, c1))
+    }
+
+    pub fn neg(&self, ctx: &mut Context<N>, a: &AssignedFq2<N>) -> Result<AssignedFq2<N>, Error> {
+        let c0 = self.integer_chip.neg(ctx, &a.c0)?;
+        let c1 = self.integer_chip.neg(ctx, &a.c1)?;
+        Ok(AssignedFq2::new(c0, c1))
+    }
+
+    pub fn conjugate(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedFq2<N>,
+    ) -> Result<AssignedFq2<N>, Error> {
+        let c1 = self.integer_chip.neg(ctx, &a.c1)?;
+        Ok(AssignedFq2::new(a.c0.clone(), c1))
+    }
+
+    pub fn mul(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq2<N>,
+        b: &mut AssignedFq2<N>,
+    ) -> Result<AssignedFq2<N>, Error> {
+        let integer_chip = self.integer_chip;
+
+        // karatsuba: c1 = (a0 + a1) * (b0 + b1) - a0 * b0 - a1 * b1
+        let t0 = integer_chip.mul(ctx, &mut a.c0, &mut b.c0)?;
+        let t1 = integer_chip.mul(ctx, &mut a.c1, &mut b.c1)?;
+        let mut a_sum = integer_chip.add(ctx, &a.c0, &a.c1)?;
+        let mut b_sum = integer_chip.add(ctx, &b.c0, &b.c1)?;
+        let t2 = integer_chip.mul(ctx, &mut a_sum, &mut b_sum)?;
+
+        let c0 = integer_chip.sub(ctx, &t0, &t1)?;
+        let c1 = integer_chip.sub(ctx, &t2, &t0)?;
+        let c1 = integer_chip.sub(ctx, &c1, &t1)?;
+        Ok(AssignedFq2::new(c0, c1))
+    }
+
+    pub fn square(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq2<N>,
+    ) -> Result<AssignedFq2<N>, Error> {
+        let integer_chip = self.integer_chip;
+
+        // c0 = (a0 + a1) * (a0 - a1), c1 = 2 * a0 * a1
+        let mut sum = integer_chip.add(ctx, &a.c0, &a.c1)?;
+        let mut diff = integer_chip.sub(ctx, &a.c0, &a.c1)?;
+        let c0 = integer_chip.mul(ctx, &mut sum, &mut diff)?;
+        let mut t = integer_chip.mul(ctx, &mut a.c0, &mut a.c1)?;
+        let c1 = integer_chip.mul_small_constant(ctx, &mut t, 2)?;
+        Ok(AssignedFq2::new(c0, c1))
+    }
+
+    pub fn mul_by_fq(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq2<N>,
+        b: &mut AssignedInteger<Fq, N>,
+    ) -> Result<AssignedFq2<N>, Error> {
+        let c0 = self.integer_chip.mul(ctx, &mut a.c0, b)?;
+        let c1 = self.integer_chip.mul(ctx, &mut a.c1, b)?;
+        Ok(AssignedFq2::new(c0, c1))
+    }
+
+    pub fn mul_small_constant(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq2<N>,
+        b: usize,
+    ) -> Result<AssignedFq2<N>, Error> {
+        let c0 = self.integer_chip.mul_small_constant(ctx, &mut a.c0, b)?;
+        let c1 = self.integer_chip.mul_small_constant(ctx, &mut a.c1, b)?;
+        Ok(AssignedFq2::new(c0, c1))
+    }
+
+    pub fn mul_constant(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq2<N>,
+        b: Fq2,
+    ) -> Result<AssignedFq2<N>, Error> {
+        if b.c1 == Fq::zero() {
+            let mut b = self.integer_chip.assign_constant(ctx, b.c0)?;
+            self.mul_by_fq(ctx, a, &mut b)
+        } else {
+            let mut b = self.assign_constant(ctx, b)?;
+            self.mul(ctx, a, &mut b)
+        }
+    }
+
+    // Multiplies by the non-residue 9 + u of the Fq6 and Fq12 towers of bn256.
+    pub fn mul_by_nonresidue(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq2<N>,
+    ) -> Result<AssignedFq2<N>, Error> {
+        let integer_chip = self.integer_chip;
+        let t0 = integer_chip.mul_small_constant(ctx, &mut a.c0, 9)?;
+        let t1 = integer_chip.mul_small_constant(ctx, &mut a.c1, 9)?;
+        let c0 = integer_chip.sub(ctx, &t0, &a.c1)?;
+        let c1 = integer_chip.add(ctx, &a.c0, &t1)?;
+        Ok(AssignedFq2::new(c0, c1))
+    }
+
+    // Returns a / b, b must not be zero.
+    pub fn div_unsafe(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedFq2<N>,
+        b: &mut AssignedFq2<N>,
+    ) -> Result<AssignedFq2<N>, Error> {
+        let a_fq2 = self.get_fq2(a)?;
+        let b_fq2 = self.get_fq2(b)?;
+        let c = a_fq2 * b_fq2.invert().unwrap_or(Fq2::zero());
+
+        let mut c = self.assign(ctx, c)?;
+        let bc = self.mul(ctx, b, &mut c)?;
+        self.assert_equal(ctx, &bc, a)?;
+        Ok(c)
+    }
+
+    pub fn invert(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq2<N>,
+    ) -> Result<AssignedFq2<N>, Error> {
+        let one = self.assign_constant(ctx, Fq2::one())?;
+        self.div_unsafe(ctx, &one, a)
+    }
+
+    // Returns whether b is zero and a / b, which is zero when b is.
+    pub fn div(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedFq2<N>,
+        b: &mut AssignedFq2<N>,
+    ) -> Result<(AssignedCondition<N>, AssignedFq2<N>), Error> {
+        let zero = self.assign_constant(ctx, Fq2::zero())?;
+        let one = self.assign_constant(ctx, Fq2::one())?;
+
+        let b_zero = self.is_zero(ctx, b)?;
+        let mut b = self.bisec(ctx, &b_zero, &one, b)?;
+        let c = self.div_unsafe(ctx, a, &mut b)?;
+        let c = self.bisec(ctx, &b_zero, &zero, &c)?;
+        Ok((b_zero, c))
+    }
+
+    pub fn is_zero(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq2<N>,
+    ) -> Result<AssignedCondition<N>, Error> {
+        let c0_zero = self.integer_chip.is_zero(ctx, &mut a.c0)?;
+        let c1_zero = self.integer_chip.is_zero(ctx, &mut a.c1)?;
+        self.base_gate().and(ctx, &c0_zero, &c1_zero)
+    }
+
+    pub fn is_equal(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedFq2<N>,
+        b: &AssignedFq2<N>,
+    ) -> Result<AssignedCondition<N>, Error> {
+        let mut diff = self.sub(ctx, a, b)?;
+        self.is_zero(ctx, &mut diff)
+    }
+
+    pub fn assert_equal(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedFq2<N>,
+        b: &AssignedFq2<N>,
+    ) -> Result<(), Error> {
+        self.integer_chip.assert_equal(ctx, &a.c0, &b.c0)?;
+        self.integer_chip.assert_equal(ctx, &a.c1, &b.c1)
+    }
+
+    pub fn bisec(
+        &self,
+        ctx: &mut Context<N>,
+        cond: &AssignedCondition<N>,
+        a: &AssignedFq2<N>,
+        b: &AssignedFq2<N>,
+    ) -> Result<AssignedFq2<N>, Error> {
+        let c0 = self.integer_chip.bisec(ctx, cond, &a.c0, &b.c0)?;
+        let c1 = self.integer_chip.bisec(ctx, cond, &a.c1, &b.c1)?;
+        Ok(AssignedFq2::new(c0, c1))
+    }
+}
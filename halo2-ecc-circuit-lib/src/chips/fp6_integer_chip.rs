@@ -0,0 +1,204 @@
+use super::{
+    fp2_integer_chip::{AssignedFq2, Fp2IntegerChip},
+    integer_chip::{AssignedInteger, IntegerChipOps},
+};
+use crate::gates::base_gate::Context;
+use halo2_proofs::plonk::Error;
+use halo2curves::{
+    bn256::{Fq, Fq6},
+    FieldExt,
+};
+
+// Element c0 + c1 * v + c2 * v^2 of Fq6 = Fq2[v] / (v^3 - (9 + u)).
+#[derive(Clone, Debug)]
+pub struct AssignedFq6<N: FieldExt> {
+    pub c0: AssignedFq2<N>,
+    pub c1: AssignedFq2<N>,
+    pub c2: AssignedFq2<N>,
+}
+
+impl<N: FieldExt> AssignedFq6<N> {
+    pub fn new(c0: AssignedFq2<N>, c1: AssignedFq2<N>, c2: AssignedFq2<N>) -> Self {
+        Self { c0, c1, c2 }
+    }
+}
+
+pub struct Fp6IntegerChip<'a, N: FieldExt> {
+    pub fp2_chip: Fp2IntegerChip<'a, N>,
+}
+
+impl<'a, N: FieldExt> Fp6IntegerChip<'a, N> {
+    pub fn new(integer_chip: &'a dyn IntegerChipOps<Fq, N>) -> Self {
+        Self {
+            fp2_chip: Fp2IntegerChip::new(integer_chip),
+        }
+    }
+
+    pub fn assign(&self, ctx: &mut Context<N>, v: Fq6) -> Result<AssignedFq6<N>, Error> {
+        let c0 = self.fp2_chip.assign(ctx, v.c0)?;
+        let c1 = self.fp2_chip.assign(ctx, v.c1)?;
+        let c2 = self.fp2_chip.assign(ctx, v.c2)?;
+        Ok(AssignedFq6::new(c0, c1, c2))
+    }
+
+    pub fn assign_constant(&self, ctx: &mut Context<N>, v: Fq6) -> Result<AssignedFq6<N>, Error> {
+        let c0 = self.fp2_chip.assign_constant(ctx, v.c0)?;
+        let c1 = self.fp2_chip.assign_constant(ctx, v.c1)?;
+        let c2 = self.fp2_chip.assign_constant(ctx, v.c2)?;
+        Ok(AssignedFq6::new(c0, c1, c2))
+    }
+
+    pub fn get_fq6(&self, a: &AssignedFq6<N>) -> Result<Fq6, Error> {
+        Ok(Fq6 {
+            c0: self.fp2_chip.get_fq2(&a.c0)?,
+            c1: self.fp2_chip.get_fq2(&a.c1)?,
+            c2: self.fp2_chip.get_fq2(&a.c2)?,
+        })
+    }
+
+    pub fn add(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedFq6<N>,
+        b: &AssignedFq6<N>,
+    ) -> Result<AssignedFq6<N>, Error> {
+        let c0 = self.fp2_chip.add(ctx, &a.c0, &b.c0)?;
+        let c1 = self.fp2_chip.add(ctx, &a.c1, &b.c1)?;
+        let c2 = self.fp2_chip.add(ctx, &a.c2, &b.c2)?;
+        Ok(AssignedFq6::new(c0, c1, c2))
+    }
+
+    pub fn sub(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedFq6<N>,
+        b: &AssignedFq6<N>,
+    ) -> Result<AssignedFq6<N>, Error> {
+        let c0 = self.fp2_chip.sub(ctx, &a.c0, &b.c0)?;
+        let c1 = self.fp2_chip.sub(ctx, &a.c1, &b.c1)?;
+        let c2 = self.fp2_chip.sub(ctx, &a.c2, &b.c2)?;
+        Ok(AssignedFq6::new(c0, c1, c2))
+    }
+
+    pub fn neg(&self, ctx: &mut Context<N>, a: &AssignedFq6<N>) -> Result<AssignedFq6<N>, Error> {
+        let c0 = self.fp2_chip.neg(ctx, &a.c0)?;
+        let c1 = self.fp2_chip.neg(ctx, &a.c1)?;
+        let c2 = self.fp2_chip.neg(ctx, &a.c2)?;
+        Ok(AssignedFq6::new(c0, c1, c2))
+    }
+
+    pub fn mul(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq6<N>,
+        b: &mut AssignedFq6<N>,
+    ) -> Result<AssignedFq6<N>, Error> {
+        let fp2_chip = &self.fp2_chip;
+
+        // karatsuba over the three coefficients, v^3 = 9 + u
+        let t0 = fp2_chip.mul(ctx, &mut a.c0, &mut b.c0)?;
+        let t1 = fp2_chip.mul(ctx, &mut a.c1, &mut b.c1)?;
+        let t2 = fp2_chip.mul(ctx, &mut a.c2, &mut b.c2)?;
+
+        // c0 = t0 + (9 + u) * ((a1 + a2) * (b1 + b2) - t1 - t2)
+        let mut a12 = fp2_chip.add(ctx, &a.c1, &a.c2)?;
+        let mut b12 = fp2_chip.add(ctx, &b.c1, &b.c2)?;
+        let s = fp2_chip.mul(ctx, &mut a12, &mut b12)?;
+        let s = fp2_chip.sub(ctx, &s, &t1)?;
+        let mut s = fp2_chip.sub(ctx, &s, &t2)?;
+        let s = fp2_chip.mul_by_nonresidue(ctx, &mut s)?;
+        let c0 = fp2_chip.add(ctx, &t0, &s)?;
+
+        // c1 = (a0 + a1) * (b0 + b1) - t0 - t1 + (9 + u) * t2
+        let mut a01 = fp2_chip.add(ctx, &a.c0, &a.c1)?;
+        let mut b01 = fp2_chip.add(ctx, &b.c0, &b.c1)?;
+        let s = fp2_chip.mul(ctx, &mut a01, &mut b01)?;
+        let s = fp2_chip.sub(ctx, &s, &t0)?;
+        let s = fp2_chip.sub(ctx, &s, &t1)?;
+        let t2_xi = fp2_chip.mul_by_nonresidue(ctx, &mut t2.clone())?;
+        let c1 = fp2_chip.add(ctx, &s, &t2_xi)?;
+
+        // c2 = (a0 + a2) * (b0 + b2) - t0 - t2 + t1
+        let mut a02 = fp2_chip.add(ctx, &a.c0, &a.c2)?;
+        let mut b02 = fp2_chip.add(ctx, &b.c0, &b.c2)?;
+        let s = fp2_chip.mul(ctx, &mut a02, &mut b02)?;
+        let s = fp2_chip.sub(ctx, &s, &t0)?;
+        let s = fp2_chip.sub(ctx, &s, &t2)?;
+        let c2 = fp2_chip.add(ctx, &s, &t1)?;
+
+        Ok(AssignedFq6::new(c0, c1, c2))
+    }
+
+    pub fn square(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq6<N>,
+    ) -> Result<AssignedFq6<N>, Error> {
+        self.mul(ctx, &mut a.clone(), a)
+    }
+
+    // Multiplies by b0 + b1 * v, the sparse factor of the line functions.
+    pub fn mul_by_01(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq6<N>,
+        b0: &mut AssignedFq2<N>,
+        b1: &mut AssignedFq2<N>,
+    ) -> Result<AssignedFq6<N>, Error> {
+        let fp2_chip = &self.fp2_chip;
+
+        let t0 = fp2_chip.mul(ctx, &mut a.c0, b0)?;
+        let t1 = fp2_chip.mul(ctx, &mut a.c1, b1)?;
+
+        // c0 = t0 + (9 + u) * a2 * b1
+        let mut s = fp2_chip.mul(ctx, &mut a.c2, b1)?;
+        let s = fp2_chip.mul_by_nonresidue(ctx, &mut s)?;
+        let c0 = fp2_chip.add(ctx, &t0, &s)?;
+
+        // c1 = (a0 + a1) * (b0 + b1) - t0 - t1
+        let mut a01 = fp2_chip.add(ctx, &a.c0, &a.c1)?;
+        let mut b01 = fp2_chip.add(ctx, b0, b1)?;
+        let s = fp2_chip.mul(ctx, &mut a01, &mut b01)?;
+        let s = fp2_chip.sub(ctx, &s, &t0)?;
+        let c1 = fp2_chip.sub(ctx, &s, &t1)?;
+
+        // c2 = a2 * b0 + t1
+        let s = fp2_chip.mul(ctx, &mut a.c2, b0)?;
+        let c2 = fp2_chip.add(ctx, &s, &t1)?;
+
+        Ok(AssignedFq6::new(c0, c1, c2))
+    }
+
+    pub fn mul_by_fq(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq6<N>,
+        b: &mut AssignedInteger<Fq, N>,
+    ) -> Result<AssignedFq6<N>, Error> {
+        let c0 = self.fp2_chip.mul_by_fq(ctx, &mut a.c0, b)?;
+        let c1 = self.fp2_chip.mul_by_fq(ctx, &mut a.c1, b)?;
+        let c2 = self.fp2_chip.mul_by_fq(ctx, &mut a.c2, b)?;
+        Ok(AssignedFq6::new(c0, c1, c2))
+    }
+
+    // Multiplies by v, the non-residue of Fq12 = Fq6[w] / (w^2 - v).
+    pub fn mul_by_nonresidue(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedFq6<N>,
+    ) -> Result<AssignedFq6<N>, Error> {
+        let c0 = self.fp2_chip.mul_by_nonresidue(ctx, &mut a.c2)?;
+        Ok(AssignedFq6::new(c0, a.c0.clone(), a.c1.clone()))
+    }
+
+    pub fn assert_equal(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedFq6<N>,
+        b: &AssignedFq6<N>,
+    ) -> Result<(), Error> {
+        self.fp2_chip.assert_equal(ctx, &a.c0, &b.c0)?;
+        self.fp2_chip.assert_equal(ctx, &a.c1, &b.c1)?;
+        self.fp2_chip.assert_equal(ctx, &a.c2, &b.c2)
+    }
+}
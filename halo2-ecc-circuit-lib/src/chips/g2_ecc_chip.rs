@@ -0,0 +1,274 @@
+use super::{
+    ecc_chip::EccChipOps,
+    fp2_integer_chip::{AssignedFq2, Fp2IntegerChip},
+    integer_chip::IntegerChipOps,
+    native_ecc_chip::NativeEccChip,
+    pairing_chip::AssignedG2Affine,
+};
+use crate::gates::base_gate::{AssignedCondition, AssignedValue, BaseGateOps, Context};
+use group::ff::Field;
+use group::{prime::PrimeCurveAffine, Curve, Group};
+use halo2_proofs::plonk::Error;
+use halo2curves::{
+    bn256::{Fq, Fq2, Fr, G1Affine, G2Affine, G2},
+    CurveAffine, FieldExt,
+};
+
+// Point of bn256 G2, z is set for the identity, whose x and y are meaningless.
+#[derive(Clone, Debug)]
+pub struct AssignedG2Point<N: FieldExt> {
+    pub x: AssignedFq2<N>,
+    pub y: AssignedFq2<N>,
+    pub z: AssignedCondition<N>,
+}
+
+impl<N: FieldExt> AssignedG2Point<N> {
+    pub fn new(x: AssignedFq2<N>, y: AssignedFq2<N>, z: AssignedCondition<N>) -> Self {
+        Self { x, y, z }
+    }
+}
+
+// Same as `EccChip`, for the coordinates of G2 in Fq2.
+pub struct G2EccChip<'a, N: FieldExt> {
+    pub fp2_chip: Fp2IntegerChip<'a, N>,
+}
+
+impl<'a, N: FieldExt> G2EccChip<'a, N> {
+    pub fn new(integer_chip: &'a dyn IntegerChipOps<Fq, N>) -> Self {
+        Self {
+            fp2_chip: Fp2IntegerChip::new(integer_chip),
+        }
+    }
+
+    pub fn base_gate(&self) -> &dyn BaseGateOps<N> {
+        self.fp2_chip.base_gate()
+    }
+
+    fn coordinates(c: G2) -> (Fq2, Fq2, N) {
+        let coordinates = c.to_affine().coordinates();
+        let x = coordinates.map(|v| *v.x()).unwrap_or(Fq2::zero());
+        let y = coordinates.map(|v| *v.y()).unwrap_or(Fq2::zero());
+        let z = N::conditional_select(&N::zero(), &N::one(), c.to_affine().is_identity());
+        (x, y, z)
+    }
+
+    pub fn assign_constant_point(
+        &self,
+        ctx: &mut Context<N>,
+        c: G2,
+    ) -> Result<AssignedG2Point<N>, Error> {
+        let (x, y, z) = Self::coordinates(c);
+
+        let x = self.fp2_chip.assign_constant(ctx, x)?;
+        let y = self.fp2_chip.assign_constant(ctx, y)?;
+        let z = self.base_gate().assign_constant(ctx, z)?;
+
+        Ok(AssignedG2Point::new(x, y, z.into()))
+    }
+
+    pub fn assign_point(&self, ctx: &mut Context<N>, c: G2) -> Result<AssignedG2Point<N>, Error> {
+        let (x, y, z) = Self::coordinates(c);
+
+        let base_gate = self.base_gate();
+        let fp2_chip = &self.fp2_chip;
+        let mut x = fp2_chip.assign(ctx, x)?;
+        let mut y = fp2_chip.assign(ctx, y)?;
+        let z = base_gate.assign(ctx, z)?;
+
+        // Constrain y^2 = x^3 + b
+        let b = fp2_chip.assign_constant(ctx, G2Affine::b())?;
+        let y2 = fp2_chip.square(ctx, &mut y)?;
+        let mut x2 = fp2_chip.square(ctx, &mut x)?;
+        let x3 = fp2_chip.mul(ctx, &mut x2, &mut x)?;
+        let right = fp2_chip.add(ctx, &x3, &b)?;
+        let eq = fp2_chip.is_equal(ctx, &y2, &right)?;
+        let eq_or_identity = base_gate.or(ctx, &eq, &z.into())?;
+        base_gate.assert_true(ctx, &eq_or_identity)?;
+
+        Ok(AssignedG2Point::new(x, y, z.into()))
+    }
+
+    pub fn assign_identity(&self, ctx: &mut Context<N>) -> Result<AssignedG2Point<N>, Error> {
+        let zero = self.fp2_chip.assign_constant(ctx, Fq2::zero())?;
+        let one = self.base_gate().assign_constant(ctx, N::one())?;
+
+        Ok(AssignedG2Point::new(zero.clone(), zero, one.into()))
+    }
+
+    pub fn bisec_point(
+        &self,
+        ctx: &mut Context<N>,
+        cond: &AssignedCondition<N>,
+        a: &AssignedG2Point<N>,
+        b: &AssignedG2Point<N>,
+    ) -> Result<AssignedG2Point<N>, Error> {
+        let x = self.fp2_chip.bisec(ctx, cond, &a.x, &b.x)?;
+        let y = self.fp2_chip.bisec(ctx, cond, &a.y, &b.y)?;
+        let z = self.base_gate().bisec_cond(ctx, cond, &a.z, &b.z)?;
+
+        Ok(AssignedG2Point::new(x, y, z))
+    }
+
+    // The tangent slope at a, with whether it is vertical.
+    fn curvature(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedG2Point<N>,
+    ) -> Result<(AssignedCondition<N>, AssignedFq2<N>), Error> {
+        let fp2_chip = &self.fp2_chip;
+
+        // 3 * x ^ 2 / 2 * y
+        let mut x_square = fp2_chip.square(ctx, &mut a.x)?;
+        let numerator = fp2_chip.mul_small_constant(ctx, &mut x_square, 3usize)?;
+        let mut denominator = fp2_chip.mul_small_constant(ctx, &mut a.y, 2usize)?;
+        fp2_chip.div(ctx, &numerator, &mut denominator)
+    }
+
+    fn lambda_to_point(
+        &self,
+        ctx: &mut Context<N>,
+        lambda_z: AssignedCondition<N>,
+        lambda: &mut AssignedFq2<N>,
+        a: &AssignedG2Point<N>,
+        b: &AssignedG2Point<N>,
+    ) -> Result<AssignedG2Point<N>, Error> {
+        let fp2_chip = &self.fp2_chip;
+
+        // cx = lambda ^ 2 - a.x - b.x
+        let cx = {
+            let l_square = fp2_chip.square(ctx, lambda)?;
+            let t = fp2_chip.sub(ctx, &l_square, &a.x)?;
+            fp2_chip.sub(ctx, &t, &b.x)?
+        };
+
+        let cy = {
+            let mut t = fp2_chip.sub(ctx, &a.x, &cx)?;
+            let t = fp2_chip.mul(ctx, &mut t, lambda)?;
+            fp2_chip.sub(ctx, &t, &a.y)?
+        };
+
+        Ok(AssignedG2Point::new(cx, cy, lambda_z))
+    }
+
+    pub fn add(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedG2Point<N>,
+        b: &AssignedG2Point<N>,
+    ) -> Result<AssignedG2Point<N>, Error> {
+        let base_gate = self.base_gate();
+        let fp2_chip = &self.fp2_chip;
+
+        let mut diff_x = fp2_chip.sub(ctx, &a.x, &b.x)?;
+        let mut diff_y = fp2_chip.sub(ctx, &a.y, &b.y)?;
+        let (x_eq, tangent) = fp2_chip.div(ctx, &diff_y, &mut diff_x)?;
+
+        let y_eq = fp2_chip.is_zero(ctx, &mut diff_y)?;
+        let eq = base_gate.and(ctx, &x_eq, &y_eq)?;
+
+        let (curvature_z, curvature) = self.curvature(ctx, a)?;
+        let lambda_z = base_gate.bisec_cond(ctx, &eq, &curvature_z, &x_eq)?;
+        let mut lambda = fp2_chip.bisec(ctx, &eq, &curvature, &tangent)?;
+
+        let p = self.lambda_to_point(ctx, lambda_z, &mut lambda, a, b)?;
+        let p = self.bisec_point(ctx, &a.z, b, &p)?;
+        let p = self.bisec_point(ctx, &b.z, a, &p)?;
+
+        Ok(p)
+    }
+
+    pub fn double(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedG2Point<N>,
+    ) -> Result<AssignedG2Point<N>, Error> {
+        let base_gate = self.base_gate();
+        let (curvature_z, mut curvature) = self.curvature(ctx, a)?;
+        let mut p = self.lambda_to_point(ctx, curvature_z, &mut curvature, a, a)?;
+        p.z = base_gate.bisec_cond(ctx, &a.z, &a.z, &p.z)?;
+        Ok(p)
+    }
+
+    pub fn neg(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedG2Point<N>,
+    ) -> Result<AssignedG2Point<N>, Error> {
+        let y = self.fp2_chip.neg(ctx, &a.y)?;
+        Ok(AssignedG2Point::new(a.x.clone(), y, a.z))
+    }
+
+    pub fn sub(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedG2Point<N>,
+        b: &AssignedG2Point<N>,
+    ) -> Result<AssignedG2Point<N>, Error> {
+        let neg_b = self.neg(ctx, b)?;
+        self.add(ctx, a, &neg_b)
+    }
+
+    pub fn assert_equal(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedG2Point<N>,
+        b: &AssignedG2Point<N>,
+    ) -> Result<(), Error> {
+        let base_gate = self.base_gate();
+        let fp2_chip = &self.fp2_chip;
+        let eq_x = fp2_chip.is_equal(ctx, &a.x, &b.x)?;
+        let eq_y = fp2_chip.is_equal(ctx, &a.y, &b.y)?;
+        let eq_z = base_gate.xnor(ctx, &a.z, &b.z)?;
+        let eq_xy = base_gate.and(ctx, &eq_x, &eq_y)?;
+        let eq_xyz = base_gate.and(ctx, &eq_xy, &eq_z)?;
+
+        let is_both_identity = base_gate.and(ctx, &a.z, &b.z)?;
+        let eq = base_gate.or(ctx, &eq_xyz, &is_both_identity)?;
+
+        base_gate.assert_true(ctx, &eq)
+    }
+
+    // The pairing takes affine points, a must not be the identity.
+    pub fn to_affine(
+        &self,
+        ctx: &mut Context<N>,
+        a: &AssignedG2Point<N>,
+    ) -> Result<AssignedG2Affine<N>, Error> {
+        self.base_gate().assert_false(ctx, &a.z)?;
+        Ok(AssignedG2Affine::new(a.x.clone(), a.y.clone()))
+    }
+}
+
+impl<'a> G2EccChip<'a, Fr> {
+    pub fn constant_mul(
+        &self,
+        ctx: &mut Context<Fr>,
+        a: G2,
+        s: &AssignedValue<Fr>,
+    ) -> Result<AssignedG2Point<Fr>, Error> {
+        // G1 and G2 share the scalar field, so is the decomposition of the scalar.
+        let scalar_chip = NativeEccChip::<G1Affine>::new(self.fp2_chip.integer_chip);
+        let bits_be = scalar_chip.decompose_scalar::<2usize>(ctx, s)?;
+        let identity = self.assign_constant_point(ctx, G2::identity())?;
+        let mut acc = None;
+        let mut base = a;
+        for bit_le in bits_be.iter().rev() {
+            let candidate01 = self.assign_constant_point(ctx, base + base)?;
+            let candidate10 = self.assign_constant_point(ctx, base)?;
+            let candidate11 = self.assign_constant_point(ctx, base + base + base)?;
+
+            let candidate0 = self.bisec_point(ctx, &bit_le[0], &candidate10, &identity)?;
+            let candidate1 = self.bisec_point(ctx, &bit_le[0], &candidate11, &candidate01)?;
+
+            let mut slot = self.bisec_point(ctx, &bit_le[1], &candidate1, &candidate0)?;
+
+            match acc {
+                None => acc = Some(slot),
+                Some(acc_) => acc = Some(self.add(ctx, &mut slot, &acc_)?),
+            }
+            base = base + base + base + base;
+        }
+
+        acc.ok_or(Error::Synthesis)
+    }
+}
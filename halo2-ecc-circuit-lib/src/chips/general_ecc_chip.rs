@@ -0,0 +1,107 @@
+use super::{
+    ecc_chip::{EccChip, EccChipOps},
+    integer_chip::{AssignedInteger, IntegerChipOps},
+};
+use crate::{
+    gates::base_gate::{AssignedCondition, AssignedValue, Context},
+    pair,
+    utils::{bn_to_field, field_to_bn},
+};
+use halo2_proofs::{arithmetic::CurveAffine, plonk::Error};
+use halo2curves::FieldExt;
+
+// Ecc chip for curves whose scalar field is not the native field, e.g. secp256k1
+// over bn256 Fr. Scalars are integers of their own integer chip.
+pub struct GeneralEccChip<'a, C: CurveAffine, N: FieldExt> {
+    pub ecc_chip: EccChip<'a, C, N>,
+    pub scalar_integer_chip: &'a dyn IntegerChipOps<C::ScalarExt, N>,
+}
+
+impl<'a, C: CurveAffine, N: FieldExt> GeneralEccChip<'a, C, N> {
+    pub fn new(
+        integer_chip: &'a dyn IntegerChipOps<C::Base, N>,
+        scalar_integer_chip: &'a dyn IntegerChipOps<C::ScalarExt, N>,
+    ) -> Self {
+        GeneralEccChip {
+            ecc_chip: EccChip::new(integer_chip),
+            scalar_integer_chip,
+        }
+    }
+}
+
+impl<'a, C: CurveAffine, N: FieldExt> EccChipOps<C, N> for GeneralEccChip<'a, C, N> {
+    type AssignedScalar = AssignedInteger<C::ScalarExt, N>;
+
+    fn integer_chip(&self) -> &dyn IntegerChipOps<C::Base, N> {
+        self.ecc_chip.integer_chip
+    }
+
+    // Decomposes each limb of the reduced scalar, so the bits are those of an
+    // integer congruent to s, possibly not the canonical one.
+    fn decompose_scalar<const WINDOW_SIZE: usize>(
+        &self,
+        ctx: &mut Context<N>,
+        s: &AssignedInteger<C::ScalarExt, N>,
+    ) -> Result<Vec<[AssignedCondition<N>; WINDOW_SIZE]>, Error> {
+        let zero = N::zero();
+        let one = N::one();
+        let base_gate = self.base_gate();
+        let limb_width = self.scalar_integer_chip.limb_width();
+        assert!(limb_width % WINDOW_SIZE == 0);
+        let windows = limb_width / WINDOW_SIZE;
+
+        let mut s = s.clone();
+        self.scalar_integer_chip.reduce(ctx, &mut s)?;
+
+        let mut ret = vec![];
+        for limb in s.limbs_le.iter() {
+            // Each line constrains bits + (rest >> WINDOW_SIZE) * 2 ^ WINDOW_SIZE = rest,
+            // the rest of the next line, and the last line leaves nothing.
+            let mut rest_bn = field_to_bn(&limb.value);
+            for i in 0..windows {
+                let bits = (0..WINDOW_SIZE)
+                    .map(|j| {
+                        let bit = if rest_bn.bit(j as u64) { one } else { zero };
+                        pair!(bit, N::from(1u64 << j))
+                    })
+                    .collect();
+                let rest = if i == 0 {
+                    pair!(limb, -one)
+                } else {
+                    pair!(bn_to_field::<N>(&rest_bn), -one)
+                };
+                let next_coeff = if i == windows - 1 {
+                    zero
+                } else {
+                    N::from(1u64 << WINDOW_SIZE)
+                };
+                let cells = base_gate.one_line_with_last_base(
+                    ctx,
+                    bits,
+                    rest,
+                    zero,
+                    (vec![], next_coeff),
+                )?;
+                ret.push(
+                    cells[0..WINDOW_SIZE]
+                        .iter()
+                        .map(|v| -> AssignedCondition<N> { v.into() })
+                        .collect::<Vec<_>>()
+                        .try_into()
+                        .unwrap(),
+                );
+                rest_bn >>= WINDOW_SIZE;
+            }
+        }
+
+        ret.reverse();
+
+        for window in &ret {
+            for bit in window {
+                base_gate.assert_bit(ctx, &AssignedValue::from(bit))?;
+            }
+        }
+
+        Ok(ret)
+    }
+}
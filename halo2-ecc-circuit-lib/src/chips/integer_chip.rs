@@ -131,6 +131,7 @@ impl<W: FieldExt, N: FieldExt, const LIMBS: usize, const LIMB_WIDTH: usize>
 pub trait IntegerChipOps<W: FieldExt, N: FieldExt> {
     fn base_gate(&self) -> &dyn BaseGateOps<N>;
     fn range_gate(&self) -> &dyn RangeGateOps<W, N>;
+    fn limb_width(&self) -> usize;
     fn assign_nonleading_limb(&self, ctx: &mut Context<N>, n: N)
         -> Result<AssignedValue<N>, Error>;
     fn assign_w_ceil_leading_limb(
@@ -157,6 +158,13 @@ pub trait IntegerChipOps<W: FieldExt, N: FieldExt> {
         a: &mut AssignedInteger<W, N>,
     ) -> Result<(), Error>;
     fn reduce(&self, ctx: &mut Context<N>, a: &mut AssignedInteger<W, N>) -> Result<(), Error>;
+    // Reduces a and constrains it to be less than the modulus of W, so that its limbs
+    // are the canonical representation of its value.
+    fn assert_canonical(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedInteger<W, N>,
+    ) -> Result<(), Error>;
     fn native<'a>(
         &self,
         ctx: &mut Context<N>,
@@ -1,5 +1,5 @@
 use super::{
-    ecc_chip::{EccChip, EccChipOps},
+    ecc_chip::{AssignedPoint, EccChip, EccChipOps, CONFIG_WINDOW_SIZE},
     integer_chip::IntegerChipOps,
 };
 use crate::{
@@ -8,8 +8,15 @@ use crate::{
     utils::{bn_to_field, field_to_bn},
 };
 use group::ff::{Field, PrimeField};
+use group::Curve;
 use halo2_proofs::{arithmetic::CurveAffine, plonk::Error};
-use num_bigint::BigUint;
+use halo2curves::FieldExt;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+
+// Both halves of a GLV decomposed scalar are below 2 ^ 126 for bn256, rounded up
+// to whole windows.
+pub const GLV_SCALAR_BITS: usize = 128usize;
 
 pub struct NativeEccChip<'a, C: CurveAffine>(pub EccChip<'a, C, C::ScalarExt>);
 
@@ -32,6 +39,268 @@ impl<'a, C: CurveAffine> NativeEccChip<'a, C> {
             s >> WINDOW_SIZE,
         )
     }
+
+    // (beta, lambda) such that lambda * (x, y) = (beta * x, y), for curves with
+    // a = 0 whose fields have cube roots of unity, e.g. bn256 G1.
+    fn endomorphism() -> Option<(C::Base, C::ScalarExt)> {
+        let beta = C::Base::ZETA;
+        if C::a() != C::Base::zero()
+            || beta == C::Base::one()
+            || beta * beta * beta != C::Base::one()
+        {
+            return None;
+        }
+
+        let g = C::generator().coordinates().unwrap();
+        let lambda = C::ScalarExt::ZETA;
+        [lambda, lambda * lambda]
+            .into_iter()
+            .find(|lambda| {
+                let p = (C::generator() * *lambda)
+                    .to_affine()
+                    .coordinates()
+                    .unwrap();
+                *p.x() == *g.x() * beta && *p.y() == *g.y()
+            })
+            .map(|lambda| (beta, lambda))
+    }
+
+    // Short basis (a1, b1, a2, b2) of the lattice {(x, y) | x + y * lambda = 0 mod n},
+    // see Guide to Elliptic Curve Cryptography, algorithm 3.74.
+    fn glv_basis(n: &BigInt, lambda: &BigInt) -> [BigInt; 4] {
+        let zero = BigInt::from(0u64);
+        let mut r = vec![n.clone(), lambda.clone()];
+        let mut t = vec![zero.clone(), BigInt::from(1u64)];
+        while r[r.len() - 1] != zero {
+            let i = r.len() - 1;
+            let q = r[i - 1].div_floor(&r[i]);
+            r.push(&r[i - 1] - &q * &r[i]);
+            t.push(&t[i - 1] - &q * &t[i]);
+        }
+
+        // The last remainder no less than sqrt(n)
+        let l = r.iter().rposition(|ri| ri * ri >= *n).unwrap();
+        let (a1, b1) = (r[l + 1].clone(), -&t[l + 1]);
+        let (a2, b2) =
+            if &r[l] * &r[l] + &t[l] * &t[l] <= &r[l + 2] * &r[l + 2] + &t[l + 2] * &t[l + 2] {
+                (r[l].clone(), -&t[l])
+            } else {
+                (r[l + 2].clone(), -&t[l + 2])
+            };
+
+        [a1, b1, a2, b2]
+    }
+
+    // k = k1 + k2 * lambda mod n with k1 and k2 about sqrt(n) in absolute value.
+    fn glv_decompose(k: &BigInt, n: &BigInt, lambda: &BigInt) -> (BigInt, BigInt) {
+        let [a1, b1, a2, b2] = Self::glv_basis(n, lambda);
+        let round = |x: BigInt| (x * 2 + n).div_floor(&(n * 2));
+        let c1 = round(&b2 * k);
+        let c2 = round(-(&b1 * k));
+
+        let k1 = k - &c1 * &a1 - &c2 * &a2;
+        let k2 = -(&c1 * &b1) - &c2 * &b2;
+        (k1, k2)
+    }
+
+    // Same as `decompose_scalar` for a witness v < 2 ^ (WINDOW_SIZE * windows),
+    // also returns the cell of v the windows are constrained to.
+    fn decompose_unsigned<const WINDOW_SIZE: usize>(
+        &self,
+        ctx: &mut Context<C::ScalarExt>,
+        v: &BigUint,
+        windows: usize,
+    ) -> Result<
+        (
+            AssignedValue<C::ScalarExt>,
+            Vec<[AssignedCondition<C::ScalarExt>; WINDOW_SIZE]>,
+        ),
+        Error,
+    > {
+        let zero = C::ScalarExt::zero();
+        let one = C::ScalarExt::one();
+        let base_gate = self.base_gate();
+
+        let mut assigned_v = None;
+        let mut ret = vec![];
+        let mut rest_bn = v.clone();
+        for i in 0..windows {
+            let rest: C::ScalarExt = bn_to_field(&rest_bn);
+            let (bits, _rest_bn) = self.decompose_bits::<WINDOW_SIZE>(ctx, rest_bn);
+            let bits = bits
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| pair!(v, C::ScalarExt::from(1u64 << i)))
+                .collect();
+            let next_coeff = if i == windows - 1 {
+                zero
+            } else {
+                C::ScalarExt::from(1u64 << WINDOW_SIZE)
+            };
+            let cells = base_gate.one_line_with_last_base(
+                ctx,
+                bits,
+                pair!(rest, -one),
+                zero,
+                (vec![], next_coeff),
+            )?;
+            if i == 0 {
+                assigned_v = Some(cells[base_gate.var_columns() - 1]);
+            }
+            ret.push(
+                cells[0..WINDOW_SIZE]
+                    .iter()
+                    .map(|v| -> AssignedCondition<C::ScalarExt> { v.into() })
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .unwrap(),
+            );
+            rest_bn = _rest_bn;
+        }
+
+        ret.reverse();
+
+        for window in &ret {
+            for bit in window {
+                base_gate.assert_bit(ctx, &AssignedValue::from(bit))?;
+            }
+        }
+
+        Ok((assigned_v.ok_or(Error::Synthesis)?, ret))
+    }
+
+    // Splits s into (-1) ^ sign1 * k1 + (-1) ^ sign2 * k2 * lambda, returns the
+    // signs and the windows of k1 and k2, which are range checked to GLV_SCALAR_BITS.
+    fn glv_split(
+        &self,
+        ctx: &mut Context<C::ScalarExt>,
+        s: &AssignedValue<C::ScalarExt>,
+        lambda: C::ScalarExt,
+    ) -> Result<
+        (
+            [AssignedCondition<C::ScalarExt>; 2],
+            [Vec<[AssignedCondition<C::ScalarExt>; CONFIG_WINDOW_SIZE]>; 2],
+        ),
+        Error,
+    > {
+        let zero = C::ScalarExt::zero();
+        let one = C::ScalarExt::one();
+        let two = one + one;
+        let base_gate = self.base_gate();
+        let windows = GLV_SCALAR_BITS / CONFIG_WINDOW_SIZE;
+
+        let n = BigInt::from(field_to_bn(&-one)) + 1u64;
+        let k = BigInt::from(field_to_bn(&s.value));
+        let (k1, k2) = Self::glv_decompose(&k, &n, &BigInt::from(field_to_bn(&lambda)));
+        let sign1 = if k1.sign() == Sign::Minus { one } else { zero };
+        let sign2 = if k2.sign() == Sign::Minus { one } else { zero };
+
+        let (k1, windows1) =
+            self.decompose_unsigned::<CONFIG_WINDOW_SIZE>(ctx, k1.magnitude(), windows)?;
+        let (k2, windows2) =
+            self.decompose_unsigned::<CONFIG_WINDOW_SIZE>(ctx, k2.magnitude(), windows)?;
+
+        // k1 * (1 - 2 * sign1) + k2 * lambda * (1 - 2 * sign2) = s
+        let cells = base_gate.one_line(
+            ctx,
+            vec![
+                pair!(&k1, one),
+                pair!(sign1, zero),
+                pair!(&k2, lambda),
+                pair!(sign2, zero),
+                pair!(s, -one),
+            ],
+            zero,
+            (vec![-two, -two * lambda], zero),
+        )?;
+        base_gate.assert_bit(ctx, &cells[1])?;
+        base_gate.assert_bit(ctx, &cells[3])?;
+
+        Ok(([cells[1].into(), cells[3].into()], [windows1, windows2]))
+    }
+
+    // Splits each point into P and phi(P) = lambda * P, with scalars of half the
+    // size, returns the new points and their windows. None on curves without the
+    // endomorphism.
+    fn glv_points(
+        &self,
+        ctx: &mut Context<C::ScalarExt>,
+        points: &mut Vec<AssignedPoint<C, C::ScalarExt>>,
+        scalars: &Vec<AssignedValue<C::ScalarExt>>,
+    ) -> Result<
+        Option<(
+            Vec<AssignedPoint<C, C::ScalarExt>>,
+            Vec<Vec<[AssignedCondition<C::ScalarExt>; CONFIG_WINDOW_SIZE]>>,
+        )>,
+        Error,
+    > {
+        let (beta, lambda) = match Self::endomorphism() {
+            Some(endomorphism) => endomorphism,
+            None => return Ok(None),
+        };
+        assert!(points.len() == scalars.len());
+
+        let integer_chip = self.integer_chip();
+        let beta = integer_chip.assign_constant(ctx, beta)?;
+
+        let mut glv_points = vec![];
+        let mut windows_in_be = vec![];
+        for (p, s) in points.iter_mut().zip(scalars.iter()) {
+            let ([sign1, sign2], [windows1, windows2]) = self.glv_split(ctx, s, lambda)?;
+
+            let x = integer_chip.mul(ctx, &mut p.x, &mut beta.clone())?;
+            let endo_p = AssignedPoint::new(x, p.y.clone(), p.z);
+            let neg_p = self.neg(ctx, p)?;
+            let neg_endo_p = self.neg(ctx, &endo_p)?;
+
+            glv_points.push(self.bisec_point(ctx, &sign1, &neg_p, p)?);
+            glv_points.push(self.bisec_point(ctx, &sign2, &neg_endo_p, &endo_p)?);
+            windows_in_be.push(windows1);
+            windows_in_be.push(windows2);
+        }
+
+        Ok(Some((glv_points, windows_in_be)))
+    }
+
+    // Same as `shamir` on the GLV split points, so the doublings are halved.
+    // Falls back to `shamir` on curves without the endomorphism.
+    pub fn glv_shamir(
+        &self,
+        ctx: &mut Context<C::ScalarExt>,
+        points: &mut Vec<AssignedPoint<C, C::ScalarExt>>,
+        scalars: &Vec<AssignedValue<C::ScalarExt>>,
+    ) -> Result<AssignedPoint<C, C::ScalarExt>, Error> {
+        match self.glv_points(ctx, points, scalars)? {
+            Some((mut glv_points, windows_in_be)) => {
+                self.shamir_windows(ctx, &mut glv_points, &windows_in_be)
+            }
+            None => self.shamir(ctx, points, scalars),
+        }
+    }
+
+    // Same as `msm` on the GLV split points.
+    pub fn glv_msm(
+        &self,
+        ctx: &mut Context<C::ScalarExt>,
+        points: &mut Vec<AssignedPoint<C, C::ScalarExt>>,
+        scalars: &Vec<AssignedValue<C::ScalarExt>>,
+    ) -> Result<AssignedPoint<C, C::ScalarExt>, Error> {
+        match self.glv_points(ctx, points, scalars)? {
+            Some((mut glv_points, windows_in_be)) => {
+                self.msm_windows(ctx, &mut glv_points, &windows_in_be)
+            }
+            None => self.msm(ctx, points, scalars),
+        }
+    }
+
+    pub fn glv_mul(
+        &self,
+        ctx: &mut Context<C::ScalarExt>,
+        a: &mut AssignedPoint<C, C::ScalarExt>,
+        s: &AssignedValue<C::ScalarExt>,
+    ) -> Result<AssignedPoint<C, C::ScalarExt>, Error> {
+        self.glv_shamir(ctx, &mut vec![a.clone()], &vec![*s])
+    }
 }
 
 impl<'a, C: CurveAffine> EccChipOps<C, C::ScalarExt> for NativeEccChip<'a, C> {
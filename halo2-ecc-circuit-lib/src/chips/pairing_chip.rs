@@ -0,0 +1,336 @@
+use super::{
+    ecc_chip::AssignedPoint,
+    fp12_integer_chip::{frobenius_coeffs, AssignedFq12, Fp12IntegerChip},
+    fp2_integer_chip::{AssignedFq2, Fp2IntegerChip},
+    integer_chip::{AssignedInteger, IntegerChipOps},
+};
+use crate::gates::base_gate::Context;
+use group::ff::Field;
+use halo2_proofs::plonk::Error;
+use halo2curves::{
+    bn256::{Fq, Fq12, G1Affine, G2Affine},
+    CurveAffine, FieldExt,
+};
+
+// The bn256 curve parameter x, the ate loop runs over 6x + 2.
+const BN_X: u64 = 4965661367192848881;
+
+// Affine point of G2, on the twist y^2 = x^3 + 3 / (9 + u). The identity has no
+// affine coordinates and cannot be assigned.
+#[derive(Clone, Debug)]
+pub struct AssignedG2Affine<N: FieldExt> {
+    pub x: AssignedFq2<N>,
+    pub y: AssignedFq2<N>,
+}
+
+impl<N: FieldExt> AssignedG2Affine<N> {
+    pub fn new(x: AssignedFq2<N>, y: AssignedFq2<N>) -> Self {
+        Self { x, y }
+    }
+}
+
+// Non-adjacent form of e, little endian.
+fn naf(mut e: u128) -> Vec<i8> {
+    let mut digits = vec![];
+    while e > 0 {
+        let digit = if e & 1 == 1 { 2 - (e % 4) as i8 } else { 0 };
+        if digit == 1 {
+            e -= 1;
+        } else if digit == -1 {
+            e += 1;
+        }
+        digits.push(digit);
+        e >>= 1;
+    }
+    digits
+}
+
+// Optimal ate pairing of bn256, over the twist of G2 by w^6 = 9 + u.
+pub struct PairingChip<'a, N: FieldExt> {
+    pub fp12_chip: Fp12IntegerChip<'a, N>,
+    ate_loop_naf: Vec<i8>,
+}
+
+impl<'a, N: FieldExt> PairingChip<'a, N> {
+    pub fn new(integer_chip: &'a dyn IntegerChipOps<Fq, N>) -> Self {
+        Self {
+            fp12_chip: Fp12IntegerChip::new(integer_chip),
+            ate_loop_naf: naf(6 * BN_X as u128 + 2),
+        }
+    }
+
+    pub fn fp2_chip(&self) -> &Fp2IntegerChip<'a, N> {
+        &self.fp12_chip.fp6_chip.fp2_chip
+    }
+
+    // Only checks that `p` is on the curve, not that it is in the G2 subgroup, the
+    // caller must take `p` from a trusted source, e.g. the params.
+    pub fn assign_g2(
+        &self,
+        ctx: &mut Context<N>,
+        p: G2Affine,
+    ) -> Result<AssignedG2Affine<N>, Error> {
+        let fp2_chip = self.fp2_chip();
+        let mut x = fp2_chip.assign(ctx, p.x)?;
+        let mut y = fp2_chip.assign(ctx, p.y)?;
+
+        // Constrain y^2 = x^3 + b
+        let b = fp2_chip.assign_constant(ctx, G2Affine::b())?;
+        let y2 = fp2_chip.square(ctx, &mut y)?;
+        let mut x2 = fp2_chip.square(ctx, &mut x)?;
+        let x3 = fp2_chip.mul(ctx, &mut x2, &mut x)?;
+        let right = fp2_chip.add(ctx, &x3, &b)?;
+        fp2_chip.assert_equal(ctx, &y2, &right)?;
+
+        Ok(AssignedG2Affine::new(x, y))
+    }
+
+    pub fn assign_constant_g2(
+        &self,
+        ctx: &mut Context<N>,
+        p: G2Affine,
+    ) -> Result<AssignedG2Affine<N>, Error> {
+        let fp2_chip = self.fp2_chip();
+        let x = fp2_chip.assign_constant(ctx, p.x)?;
+        let y = fp2_chip.assign_constant(ctx, p.y)?;
+        Ok(AssignedG2Affine::new(x, y))
+    }
+
+    // Returns the tangent slope at t and 2 * t.
+    fn double_step(
+        &self,
+        ctx: &mut Context<N>,
+        t: &mut AssignedG2Affine<N>,
+    ) -> Result<(AssignedFq2<N>, AssignedG2Affine<N>), Error> {
+        let fp2_chip = self.fp2_chip();
+
+        // lambda = 3 * x ^ 2 / 2 * y
+        let mut x2 = fp2_chip.square(ctx, &mut t.x)?;
+        let numerator = fp2_chip.mul_small_constant(ctx, &mut x2, 3)?;
+        let mut denominator = fp2_chip.mul_small_constant(ctx, &mut t.y, 2)?;
+        let mut lambda = fp2_chip.div_unsafe(ctx, &numerator, &mut denominator)?;
+
+        let p = self.lambda_to_point(ctx, &mut lambda, t, &t.x)?;
+        Ok((lambda, p))
+    }
+
+    // Returns the slope of the chord through t and q, and t + q. t and q must be
+    // distinct points with distinct x.
+    fn add_step(
+        &self,
+        ctx: &mut Context<N>,
+        t: &mut AssignedG2Affine<N>,
+        q: &AssignedG2Affine<N>,
+    ) -> Result<(AssignedFq2<N>, AssignedG2Affine<N>), Error> {
+        let fp2_chip = self.fp2_chip();
+
+        let diff_y = fp2_chip.sub(ctx, &q.y, &t.y)?;
+        let mut diff_x = fp2_chip.sub(ctx, &q.x, &t.x)?;
+        let mut lambda = fp2_chip.div_unsafe(ctx, &diff_y, &mut diff_x)?;
+
+        let p = self.lambda_to_point(ctx, &mut lambda, t, &q.x)?;
+        Ok((lambda, p))
+    }
+
+    fn lambda_to_point(
+        &self,
+        ctx: &mut Context<N>,
+        lambda: &mut AssignedFq2<N>,
+        a: &AssignedG2Affine<N>,
+        b_x: &AssignedFq2<N>,
+    ) -> Result<AssignedG2Affine<N>, Error> {
+        let fp2_chip = self.fp2_chip();
+
+        // cx = lambda ^ 2 - a.x - b.x
+        let cx = {
+            let l_square = fp2_chip.square(ctx, lambda)?;
+            let t = fp2_chip.sub(ctx, &l_square, &a.x)?;
+            fp2_chip.sub(ctx, &t, b_x)?
+        };
+
+        // cy = lambda * (a.x - cx) - a.y
+        let cy = {
+            let mut t = fp2_chip.sub(ctx, &a.x, &cx)?;
+            let t = fp2_chip.mul(ctx, &mut t, lambda)?;
+            fp2_chip.sub(ctx, &t, &a.y)?
+        };
+
+        Ok(AssignedG2Affine::new(cx, cy))
+    }
+
+    // Multiplies f by the line of slope lambda through t, evaluated at p, that is
+    // p.y - lambda * p.x * w + (lambda * t.x - t.y) * w^3, p is given as (-p.x, p.y).
+    fn mul_by_line(
+        &self,
+        ctx: &mut Context<N>,
+        f: &mut AssignedFq12<N>,
+        lambda: &mut AssignedFq2<N>,
+        t: &mut AssignedG2Affine<N>,
+        p: &mut (AssignedInteger<Fq, N>, AssignedInteger<Fq, N>),
+    ) -> Result<AssignedFq12<N>, Error> {
+        let fp2_chip = self.fp2_chip();
+
+        let mut b = fp2_chip.mul_by_fq(ctx, lambda, &mut p.0)?;
+        let lambda_x = fp2_chip.mul(ctx, lambda, &mut t.x)?;
+        let mut c = fp2_chip.sub(ctx, &lambda_x, &t.y)?;
+
+        self.fp12_chip.mul_by_013(ctx, f, &mut p.1, &mut b, &mut c)
+    }
+
+    // Frobenius endomorphism of the twist, (x, y) -> (x^p, y^p) on the curve over
+    // Fq12 mapped back onto the twist.
+    fn frobenius_g2(
+        &self,
+        ctx: &mut Context<N>,
+        q: &AssignedG2Affine<N>,
+        power: usize,
+    ) -> Result<AssignedG2Affine<N>, Error> {
+        let fp2_chip = self.fp2_chip();
+        let coeffs = frobenius_coeffs(power);
+
+        let (mut x, mut y) = if power % 2 == 1 {
+            (
+                fp2_chip.conjugate(ctx, &q.x)?,
+                fp2_chip.conjugate(ctx, &q.y)?,
+            )
+        } else {
+            (q.x.clone(), q.y.clone())
+        };
+        let x = fp2_chip.mul_constant(ctx, &mut x, coeffs[2])?;
+        let y = fp2_chip.mul_constant(ctx, &mut y, coeffs[3])?;
+        Ok(AssignedG2Affine::new(x, y))
+    }
+
+    // Product of the miller loops of the pairs, before the final exponentiation.
+    // The points must not be the identity.
+    pub fn multi_miller_loop(
+        &self,
+        ctx: &mut Context<N>,
+        terms: &[(&AssignedPoint<G1Affine, N>, &AssignedG2Affine<N>)],
+    ) -> Result<AssignedFq12<N>, Error> {
+        let base_gate = self.fp2_chip().base_gate();
+        let fp2_chip = self.fp2_chip();
+        let integer_chip = fp2_chip.integer_chip;
+
+        let mut ps = vec![];
+        for (p, _) in terms.iter() {
+            base_gate.assert_false(ctx, &p.z)?;
+            let neg_x = integer_chip.neg(ctx, &p.x)?;
+            ps.push((neg_x, p.y.clone()));
+        }
+        let qs = terms.iter().map(|(_, q)| (*q).clone()).collect::<Vec<_>>();
+        let neg_qs = qs
+            .iter()
+            .map(|q| Ok(AssignedG2Affine::new(q.x.clone(), fp2_chip.neg(ctx, &q.y)?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut ts = qs.clone();
+
+        let mut f = self.fp12_chip.assign_constant(ctx, Fq12::one())?;
+        for digit in self.ate_loop_naf.iter().rev().skip(1) {
+            f = self.fp12_chip.square(ctx, &mut f)?;
+
+            for i in 0..terms.len() {
+                let (mut lambda, t) = self.double_step(ctx, &mut ts[i])?;
+                f = self.mul_by_line(ctx, &mut f, &mut lambda, &mut ts[i], &mut ps[i])?;
+                ts[i] = t;
+
+                if *digit != 0 {
+                    let q = if *digit == 1 { &qs[i] } else { &neg_qs[i] };
+                    let (mut lambda, t) = self.add_step(ctx, &mut ts[i], q)?;
+                    f = self.mul_by_line(ctx, &mut f, &mut lambda, &mut ts[i], &mut ps[i])?;
+                    ts[i] = t;
+                }
+            }
+        }
+
+        // Two more lines, through pi(q) and -pi^2(q)
+        for i in 0..terms.len() {
+            let q1 = self.frobenius_g2(ctx, &qs[i], 1)?;
+            let q2 = self.frobenius_g2(ctx, &qs[i], 2)?;
+            let q2 = AssignedG2Affine::new(q2.x.clone(), fp2_chip.neg(ctx, &q2.y)?);
+
+            let (mut lambda, t) = self.add_step(ctx, &mut ts[i], &q1)?;
+            f = self.mul_by_line(ctx, &mut f, &mut lambda, &mut ts[i], &mut ps[i])?;
+            ts[i] = t;
+
+            let (mut lambda, t) = self.add_step(ctx, &mut ts[i], &q2)?;
+            f = self.mul_by_line(ctx, &mut f, &mut lambda, &mut ts[i], &mut ps[i])?;
+            ts[i] = t;
+        }
+
+        Ok(f)
+    }
+
+    // f^(-x), f must be in the cyclotomic subgroup.
+    fn exp_by_neg_x(
+        &self,
+        ctx: &mut Context<N>,
+        f: &mut AssignedFq12<N>,
+    ) -> Result<AssignedFq12<N>, Error> {
+        let fp12_chip = &self.fp12_chip;
+        let bits = 64 - BN_X.leading_zeros();
+
+        let mut acc = f.clone();
+        for i in (0..bits - 1).rev() {
+            acc = fp12_chip.square(ctx, &mut acc)?;
+            if (BN_X >> i) & 1 == 1 {
+                acc = fp12_chip.mul(ctx, &mut acc, f)?;
+            }
+        }
+        fp12_chip.conjugate(ctx, &acc)
+    }
+
+    // Raises f to (p^12 - 1) / r, up to a fixed power coprime to r, which keeps
+    // the result of a pairing check. The hard part follows "Faster hashing to G2"
+    // by Fuentes-Castaneda, Knapp and Rodriguez-Henriquez.
+    pub fn final_exponentiation(
+        &self,
+        ctx: &mut Context<N>,
+        f: &mut AssignedFq12<N>,
+    ) -> Result<AssignedFq12<N>, Error> {
+        let fp12_chip = &self.fp12_chip;
+
+        // easy part: f^((p^6 - 1) * (p^2 + 1))
+        let mut f_conj = fp12_chip.conjugate(ctx, f)?;
+        let mut f_inv = fp12_chip.invert(ctx, f)?;
+        let mut f1 = fp12_chip.mul(ctx, &mut f_conj, &mut f_inv)?;
+        let mut f1_p2 = fp12_chip.frobenius_map(ctx, &f1, 2)?;
+        let mut r = fp12_chip.mul(ctx, &mut f1_p2, &mut f1)?;
+
+        // hard part
+        let mut y0 = self.exp_by_neg_x(ctx, &mut r)?;
+        let mut y1 = fp12_chip.square(ctx, &mut y0)?;
+        let mut y2 = fp12_chip.square(ctx, &mut y1)?;
+        let mut y3 = fp12_chip.mul(ctx, &mut y2, &mut y1)?;
+        let mut y4 = self.exp_by_neg_x(ctx, &mut y3)?;
+        let mut y5 = fp12_chip.square(ctx, &mut y4)?;
+        let y6 = self.exp_by_neg_x(ctx, &mut y5)?;
+        let mut y3 = fp12_chip.conjugate(ctx, &y3)?;
+        let mut y6 = fp12_chip.conjugate(ctx, &y6)?;
+        let mut y7 = fp12_chip.mul(ctx, &mut y6, &mut y4)?;
+        let mut y8 = fp12_chip.mul(ctx, &mut y7, &mut y3)?;
+        let y9 = fp12_chip.mul(ctx, &mut y8, &mut y1)?;
+        let mut y10 = fp12_chip.mul(ctx, &mut y8, &mut y4)?;
+        let mut y11 = fp12_chip.mul(ctx, &mut y10, &mut r)?;
+        let mut y12 = fp12_chip.frobenius_map(ctx, &y9, 1)?;
+        let mut y13 = fp12_chip.mul(ctx, &mut y12, &mut y11)?;
+        let mut y8 = fp12_chip.frobenius_map(ctx, &y8, 2)?;
+        let mut y14 = fp12_chip.mul(ctx, &mut y8, &mut y13)?;
+        let mut r = fp12_chip.conjugate(ctx, &r)?;
+        let y15 = fp12_chip.mul(ctx, &mut r, &mut y9.clone())?;
+        let mut y15 = fp12_chip.frobenius_map(ctx, &y15, 3)?;
+        fp12_chip.mul(ctx, &mut y15, &mut y14)
+    }
+
+    // Constrains the product of the pairings of the pairs to be one.
+    pub fn pairing_check(
+        &self,
+        ctx: &mut Context<N>,
+        terms: &[(&AssignedPoint<G1Affine, N>, &AssignedG2Affine<N>)],
+    ) -> Result<(), Error> {
+        let mut f = self.multi_miller_loop(ctx, terms)?;
+        let e = self.final_exponentiation(ctx, &mut f)?;
+        let one = self.fp12_chip.assign_constant(ctx, Fq12::one())?;
+        self.fp12_chip.assert_equal(ctx, &e, &one)
+    }
+}
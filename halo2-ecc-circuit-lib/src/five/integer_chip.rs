@@ -592,6 +592,65 @@ impl<'a, W: FieldExt, N: FieldExt> IntegerChipOps<W, N> for FiveColumnIntegerChi
         }
     }
 
+    fn assert_canonical(
+        &self,
+        ctx: &mut Context<N>,
+        a: &mut AssignedInteger<W, N>,
+    ) -> Result<(), Error> {
+        self.reduce(ctx, a)?;
+
+        let zero = N::zero();
+        let one = N::one();
+
+        // Subtract a from w_modulus - 1 limb by limb: each limb of the difference is
+        // range checked, and no borrow goes out of the leading limb, so the difference
+        // is not negative.
+        let max_limbs_le = self.helper.bn_to_limb_le(&(&self.helper.w_modulus - 1u64));
+        let mut borrow_in: Option<AssignedValue<N>> = None;
+        for (i, max_limb) in max_limbs_le.iter().enumerate() {
+            let limb = &a.limbs_le[i];
+            let borrow_in_value = borrow_in.map_or(zero, |borrow| borrow.value);
+            let sub = field_to_bn(&limb.value) + field_to_bn(&borrow_in_value);
+            let borrow_out_value = if i < LIMBS - 1 && &sub > max_limb {
+                one
+            } else {
+                zero
+            };
+            let diff = bn_to_field::<N>(max_limb)
+                + borrow_out_value * self.helper.limb_modulus_on_n
+                - limb.value
+                - borrow_in_value;
+
+            let diff = if i < LIMBS - 1 {
+                self.assign_nonleading_limb(ctx, diff)?
+            } else {
+                self.assign_w_ceil_leading_limb(ctx, diff)?
+            };
+
+            let borrow_out = if i < LIMBS - 1 {
+                let borrow = self.base_gate().assign(ctx, borrow_out_value)?;
+                self.base_gate().assert_bit(ctx, &borrow)?;
+                Some(borrow)
+            } else {
+                None
+            };
+
+            let mut schema = vec![pair!(limb, one), pair!(&diff, one)];
+            if let Some(borrow) = &borrow_in {
+                schema.push(pair!(borrow, one));
+            }
+            if let Some(borrow) = &borrow_out {
+                schema.push(pair!(borrow, -self.helper.limb_modulus_on_n));
+            }
+            self.base_gate()
+                .one_line_add(ctx, schema, -bn_to_field::<N>(max_limb))?;
+
+            borrow_in = borrow_out;
+        }
+
+        Ok(())
+    }
+
     fn native<'c>(
         &self,
         ctx: &mut Context<N>,
@@ -842,6 +901,10 @@ impl<'a, W: FieldExt, N: FieldExt> IntegerChipOps<W, N> for FiveColumnIntegerChi
         self.range_gate
     }
 
+    fn limb_width(&self) -> usize {
+        LIMB_COMMON_WIDTH
+    }
+
     fn bisec(
         &self,
         ctx: &mut Context<N>,
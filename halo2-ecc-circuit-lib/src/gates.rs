@@ -1,2 +1,3 @@
 pub mod base_gate;
+pub mod bits_gate;
 pub mod range_gate;
@@ -0,0 +1,145 @@
+use super::base_gate::{AssignedCondition, AssignedValue, BaseGateOps, Context};
+use crate::utils::field_to_bn;
+use halo2_proofs::{arithmetic::FieldExt, plonk::Error};
+use num_bigint::BigUint;
+
+pub struct BitsGate<'a, N: FieldExt> {
+    pub base_gate: &'a dyn BaseGateOps<N>,
+}
+
+impl<'a, N: FieldExt> BitsGate<'a, N> {
+    pub fn new(base_gate: &'a dyn BaseGateOps<N>) -> Self {
+        BitsGate { base_gate }
+    }
+}
+
+// All bit vectors are little-endian.
+pub trait BitsGateOps<N: FieldExt> {
+    fn base_gate(&self) -> &dyn BaseGateOps<N>;
+
+    fn assign_bits(
+        &self,
+        ctx: &mut Context<'_, N>,
+        v: &BigUint,
+        bits: usize,
+    ) -> Result<Vec<AssignedCondition<N>>, Error> {
+        let base_gate = self.base_gate();
+        let mut digits = v.to_radix_le(2);
+        digits.resize(bits, 0u8);
+
+        digits
+            .iter()
+            .map(|digit| {
+                let bit = base_gate.assign(ctx, N::from(*digit as u64))?;
+                base_gate.assert_bit(ctx, &bit)?;
+                Ok(bit.into())
+            })
+            .collect()
+    }
+
+    fn assign_constant_bits(
+        &self,
+        ctx: &mut Context<'_, N>,
+        v: &BigUint,
+        bits: usize,
+    ) -> Result<Vec<AssignedCondition<N>>, Error> {
+        let base_gate = self.base_gate();
+        let mut digits = v.to_radix_le(2);
+        digits.resize(bits, 0u8);
+
+        digits
+            .iter()
+            .map(|digit| {
+                Ok(base_gate
+                    .assign_constant(ctx, N::from(*digit as u64))?
+                    .into())
+            })
+            .collect()
+    }
+
+    fn bits_to_num(
+        &self,
+        ctx: &mut Context<'_, N>,
+        bits: &[AssignedCondition<N>],
+    ) -> Result<AssignedValue<N>, Error> {
+        let two = N::from(2u64);
+        let bits: Vec<AssignedValue<N>> = bits.iter().map(|bit| bit.into()).collect();
+
+        let mut coeff = N::one();
+        let mut elems = vec![];
+        for bit in bits.iter() {
+            elems.push((bit, coeff));
+            coeff *= two;
+        }
+
+        self.base_gate().sum_with_constant(ctx, elems, N::zero())
+    }
+
+    // The decomposition is only unique if 2 ^ bits doesn't wrap around the native modulus,
+    // use `num_to_bits_canonical` for arbitrary field elements.
+    fn num_to_bits(
+        &self,
+        ctx: &mut Context<'_, N>,
+        a: &AssignedValue<N>,
+        bits: usize,
+    ) -> Result<Vec<AssignedCondition<N>>, Error> {
+        assert!(bits < N::NUM_BITS as usize);
+
+        let res = self.assign_bits(ctx, &field_to_bn(&a.value), bits)?;
+        let sum = self.bits_to_num(ctx, &res)?;
+        self.base_gate().assert_equal(ctx, a, &sum)?;
+
+        Ok(res)
+    }
+
+    fn num_to_bits_canonical(
+        &self,
+        ctx: &mut Context<'_, N>,
+        a: &AssignedValue<N>,
+    ) -> Result<Vec<AssignedCondition<N>>, Error> {
+        let n_modulus = field_to_bn(&-N::one()) + 1u64;
+
+        let res = self.assign_bits(ctx, &field_to_bn(&a.value), N::NUM_BITS as usize)?;
+        self.assert_bits_less_than(ctx, &res, &n_modulus)?;
+        let sum = self.bits_to_num(ctx, &res)?;
+        self.base_gate().assert_equal(ctx, a, &sum)?;
+
+        Ok(res)
+    }
+
+    fn assert_bits_less_than(
+        &self,
+        ctx: &mut Context<'_, N>,
+        bits: &[AssignedCondition<N>],
+        c: &BigUint,
+    ) -> Result<(), Error> {
+        let base_gate = self.base_gate();
+        let mut c_digits = c.to_radix_le(2);
+        c_digits.resize(bits.len().max(c_digits.len()), 0u8);
+
+        // The bits are already less than `c` if it has a one above them.
+        if c_digits[bits.len()..].iter().any(|digit| *digit != 0) {
+            return Ok(());
+        }
+
+        // Scan from the most significant bit, `eq` marks whether all bits so far are equal to `c`'s.
+        let mut eq: AssignedCondition<N> = base_gate.assign_constant(ctx, N::one())?.into();
+        for (bit, c_digit) in bits.iter().zip(c_digits[..bits.len()].iter()).rev() {
+            if *c_digit == 1 {
+                eq = base_gate.and(ctx, &eq, bit)?;
+            } else {
+                // A one over a zero of `c` is only allowed once the prefix is already less.
+                let overflow = base_gate.and(ctx, &eq, bit)?;
+                base_gate.assert_false(ctx, &overflow)?;
+            }
+        }
+
+        base_gate.assert_false(ctx, &eq)
+    }
+}
+
+impl<'a, N: FieldExt> BitsGateOps<N> for BitsGate<'a, N> {
+    fn base_gate(&self) -> &dyn BaseGateOps<N> {
+        self.base_gate
+    }
+}
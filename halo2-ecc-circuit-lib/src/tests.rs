@@ -1,3 +1,8 @@
 pub mod five_base;
+pub mod five_bits;
+pub mod five_ecdsa;
+pub mod five_g2_ecc;
 pub mod five_integer;
+pub mod five_msm;
 pub mod five_native_ecc;
+pub mod five_pairing;
@@ -0,0 +1,185 @@
+use crate::five::base_gate::{FiveColumnBaseGate, FiveColumnBaseGateConfig};
+use crate::gates::base_gate::{BaseGateOps, Context};
+use crate::gates::bits_gate::{BitsGate, BitsGateOps};
+use crate::utils::field_to_bn;
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner},
+    dev::MockProver,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+use halo2curves::bn256::Fr;
+use num_bigint::BigUint;
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+use std::marker::PhantomData;
+
+enum TestCase {
+    NumToBits,
+    NumToBitsCanonical,
+    LessThan,
+}
+
+impl Default for TestCase {
+    fn default() -> TestCase {
+        TestCase::NumToBits
+    }
+}
+
+#[derive(Clone)]
+struct TestFiveColumnBitsGateConfig {
+    base_gate_config: FiveColumnBaseGateConfig,
+}
+
+#[derive(Default)]
+struct TestFiveColumnBitsGateCircuit<N: FieldExt> {
+    test_case: TestCase,
+    _phantom: PhantomData<N>,
+}
+
+impl<N: FieldExt> TestFiveColumnBitsGateCircuit<N> {
+    fn random() -> N {
+        let seed = chrono::offset::Utc::now()
+            .timestamp_nanos()
+            .try_into()
+            .unwrap();
+        let rng = XorShiftRng::seed_from_u64(seed);
+        N::random(rng)
+    }
+
+    fn setup_test_num_to_bits(
+        &self,
+        bits_gate: &BitsGate<'_, N>,
+        ctx: &mut Context<'_, N>,
+    ) -> Result<(), Error> {
+        const BITS: usize = 64usize;
+        let v = N::from(0xdead_beef_0bad_f00du64);
+        let expected = field_to_bn(&v).to_radix_le(2);
+
+        let assigned_v = bits_gate.base_gate().assign(ctx, v)?;
+        let bits = bits_gate.num_to_bits(ctx, &assigned_v, BITS)?;
+
+        assert_eq!(bits.len(), BITS);
+        for (i, bit) in bits.iter().enumerate() {
+            let digit = expected.get(i).copied().unwrap_or(0u8);
+            bits_gate
+                .base_gate()
+                .assert_constant(ctx, &bit.into(), N::from(digit as u64))?;
+        }
+
+        let recomposed = bits_gate.bits_to_num(ctx, &bits)?;
+        bits_gate
+            .base_gate()
+            .assert_equal(ctx, &assigned_v, &recomposed)?;
+        Ok(())
+    }
+
+    fn setup_test_num_to_bits_canonical(
+        &self,
+        bits_gate: &BitsGate<'_, N>,
+        ctx: &mut Context<'_, N>,
+    ) -> Result<(), Error> {
+        for v in [Self::random(), -N::one(), N::zero()] {
+            let assigned_v = bits_gate.base_gate().assign(ctx, v)?;
+            let bits = bits_gate.num_to_bits_canonical(ctx, &assigned_v)?;
+            assert_eq!(bits.len(), N::NUM_BITS as usize);
+        }
+        Ok(())
+    }
+
+    fn setup_test_less_than(
+        &self,
+        bits_gate: &BitsGate<'_, N>,
+        ctx: &mut Context<'_, N>,
+    ) -> Result<(), Error> {
+        let c = BigUint::from(0b1011_0110u64);
+        for v in [0u64, 0b0111_1111u64, 0b1011_0101u64] {
+            let bits = bits_gate.assign_bits(ctx, &BigUint::from(v), 8)?;
+            bits_gate.assert_bits_less_than(ctx, &bits, &c)?;
+        }
+        Ok(())
+    }
+}
+
+impl<N: FieldExt> Circuit<N> for TestFiveColumnBitsGateCircuit<N> {
+    type Config = TestFiveColumnBitsGateConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<N>) -> Self::Config {
+        let base_gate_config = FiveColumnBaseGate::<N>::configure(meta);
+        TestFiveColumnBitsGateConfig { base_gate_config }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<N>,
+    ) -> Result<(), Error> {
+        let base_gate = FiveColumnBaseGate::new(config.base_gate_config);
+        let bits_gate = BitsGate::new(&base_gate);
+
+        layouter.assign_region(
+            || "base",
+            |region| {
+                let base_offset = 0usize;
+                let mut aux = Context::new(region, base_offset);
+                let r = &mut aux;
+                match self.test_case {
+                    TestCase::NumToBits => self.setup_test_num_to_bits(&bits_gate, r),
+                    TestCase::NumToBitsCanonical => {
+                        self.setup_test_num_to_bits_canonical(&bits_gate, r)
+                    }
+                    TestCase::LessThan => self.setup_test_less_than(&bits_gate, r),
+                }
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_five_column_bits_gate_num_to_bits() {
+    const K: u32 = 10;
+    let circuit = TestFiveColumnBitsGateCircuit::<Fr> {
+        test_case: TestCase::NumToBits,
+        _phantom: PhantomData,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn test_five_column_bits_gate_num_to_bits_canonical() {
+    const K: u32 = 12;
+    let circuit = TestFiveColumnBitsGateCircuit::<Fr> {
+        test_case: TestCase::NumToBitsCanonical,
+        _phantom: PhantomData,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn test_five_column_bits_gate_less_than() {
+    const K: u32 = 8;
+    let circuit = TestFiveColumnBitsGateCircuit::<Fr> {
+        test_case: TestCase::LessThan,
+        _phantom: PhantomData,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
@@ -0,0 +1,288 @@
+use crate::chips::ecc_chip::EccChipOps;
+use crate::chips::ecdsa_chip::EcdsaChip;
+use crate::chips::integer_chip::{AssignedInteger, IntegerChipOps};
+use crate::five::base_gate::{FiveColumnBaseGate, FiveColumnBaseGateConfig};
+use crate::five::integer_chip::FiveColumnIntegerChip;
+use crate::five::range_gate::FiveColumnRangeGate;
+use crate::gates::base_gate::Context;
+use crate::gates::range_gate::RangeGateConfig;
+use crate::utils::{bn_to_field, field_to_bn};
+use group::ff::Field;
+use group::{Curve, Group};
+use halo2_proofs::arithmetic::CurveAffine;
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner},
+    dev::MockProver,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+use halo2curves::bn256::Fr;
+use halo2curves::secp256k1::{Fp, Fq, Secp256k1, Secp256k1Affine};
+use halo2curves::FieldExt;
+use num_bigint::BigUint;
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+// sk, msg_hash and nonce are sha256 of fixed strings, the signature also verifies
+// outside of the circuit.
+const VECTOR_PK_X: &str = "49a1de6487183f1f0934271c25848eda8850b877e8f4aac932c823a4e8337149";
+const VECTOR_PK_Y: &str = "4f8018c704417e5925d918bd3bf79bfcdf10f66b362dff74ed7b77a96d742ab0";
+const VECTOR_MSG_HASH: &str = "32b0f4081b056a02723a5c8e9fec9c3ebbb0962a7cec7a55086e6b608f5307d4";
+const VECTOR_R: &str = "4e0f6d6a594133517afec7816393091c232eb170e6d0469bae59ba2b7f9e6d4c";
+const VECTOR_S: &str = "9c272aa5f4342277a9970653111da3f88b4dee8bd85dba1755a96c431edb455a";
+
+enum TestCase {
+    Random,
+    Vector,
+    WrongMessage,
+    // r = x mod n for a small x, given as x or as x + p
+    CanonicalX,
+    NonCanonicalX,
+}
+
+impl Default for TestCase {
+    fn default() -> TestCase {
+        TestCase::Random
+    }
+}
+
+#[derive(Clone)]
+struct TestFiveColumnEcdsaChipConfig {
+    base_gate_config: FiveColumnBaseGateConfig,
+    range_gate_config: RangeGateConfig,
+    scalar_range_gate_config: RangeGateConfig,
+}
+
+#[derive(Default)]
+struct TestFiveColumnEcdsaChipCircuit {
+    test_case: TestCase,
+}
+
+// (pk, msg_hash, r, s)
+type Signed = (Secp256k1Affine, Fq, Fq, Fq);
+
+impl TestFiveColumnEcdsaChipCircuit {
+    fn random() -> Fq {
+        let seed = chrono::offset::Utc::now()
+            .timestamp_nanos()
+            .try_into()
+            .unwrap();
+        let rng = XorShiftRng::seed_from_u64(seed);
+        Fq::random(rng)
+    }
+
+    fn from_hex<F: FieldExt>(hex: &str) -> F {
+        bn_to_field(&BigUint::parse_bytes(hex.as_bytes(), 16).unwrap())
+    }
+
+    fn sign() -> Signed {
+        let sk = Self::random();
+        let msg_hash = Self::random();
+        let k = Self::random();
+
+        let pk = (Secp256k1::generator() * sk).to_affine();
+        let x = *(Secp256k1::generator() * k)
+            .to_affine()
+            .coordinates()
+            .unwrap()
+            .x();
+        let r = bn_to_field::<Fq>(&field_to_bn(&x));
+        let s = k.invert().unwrap() * (msg_hash + r * sk);
+        (pk, msg_hash, r, s)
+    }
+
+    fn vector() -> Signed {
+        let pk = Secp256k1Affine::from_xy(
+            Self::from_hex::<Fp>(VECTOR_PK_X),
+            Self::from_hex::<Fp>(VECTOR_PK_Y),
+        )
+        .unwrap();
+        (
+            pk,
+            Self::from_hex(VECTOR_MSG_HASH),
+            Self::from_hex(VECTOR_R),
+            Self::from_hex(VECTOR_S),
+        )
+    }
+
+    fn setup_test_verify(
+        &self,
+        ecdsa_chip: &EcdsaChip<'_, Secp256k1Affine, Fr>,
+        ctx: &mut Context<'_, Fr>,
+        (pk, msg_hash, r, s): Signed,
+    ) -> Result<(), Error> {
+        let ecc_chip = &ecdsa_chip.ecc_chip;
+        let mut pk = ecc_chip.assign_point(ctx, Secp256k1::from(pk))?;
+        let mut msg_hash = ecc_chip.scalar_integer_chip.assign_w(ctx, &msg_hash)?;
+        let mut sig = ecdsa_chip.assign_signature(ctx, r, s)?;
+
+        ecdsa_chip.verify(ctx, &mut pk, &mut msg_hash, &mut sig)
+    }
+
+    fn setup_test_r_of_x(
+        &self,
+        ecdsa_chip: &EcdsaChip<'_, Secp256k1Affine, Fr>,
+        ctx: &mut Context<'_, Fr>,
+        x: BigUint,
+    ) -> Result<(), Error> {
+        let ecc_chip = &ecdsa_chip.ecc_chip;
+        let n_modulus = field_to_bn(&-Fq::one()) + 1u64;
+        let r = bn_to_field::<Fq>(&(&x % n_modulus));
+        let r = ecc_chip.scalar_integer_chip.assign_w(ctx, &r)?;
+
+        // The limbs of x are only range checked, as if a witness of the ecc chip held
+        // them.
+        let limbs = ecc_chip.integer_chip().assign_integer(ctx, &x)?;
+        let mut x = AssignedInteger::<Fp, Fr>::new(limbs, 0);
+
+        ecdsa_chip.assert_r_of_x(ctx, &mut x, &r)
+    }
+}
+
+const COMMON_RANGE_BITS: usize = 17usize;
+
+impl Circuit<Fr> for TestFiveColumnEcdsaChipCircuit {
+    type Config = TestFiveColumnEcdsaChipConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let base_gate_config = FiveColumnBaseGate::<Fr>::configure(meta);
+        let range_gate_config = FiveColumnRangeGate::<'_, Fp, Fr, COMMON_RANGE_BITS>::configure(
+            meta,
+            &base_gate_config,
+        );
+        let scalar_range_gate_config =
+            FiveColumnRangeGate::<'_, Fq, Fr, COMMON_RANGE_BITS>::configure(
+                meta,
+                &base_gate_config,
+            );
+        TestFiveColumnEcdsaChipConfig {
+            base_gate_config,
+            range_gate_config,
+            scalar_range_gate_config,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let base_gate = FiveColumnBaseGate::new(config.base_gate_config);
+        let range_gate = FiveColumnRangeGate::<'_, Fp, Fr, COMMON_RANGE_BITS>::new(
+            config.range_gate_config,
+            &base_gate,
+        );
+        let scalar_range_gate = FiveColumnRangeGate::<'_, Fq, Fr, COMMON_RANGE_BITS>::new(
+            config.scalar_range_gate_config,
+            &base_gate,
+        );
+        let integer_gate = FiveColumnIntegerChip::new(&range_gate);
+        let scalar_integer_gate = FiveColumnIntegerChip::new(&scalar_range_gate);
+        let ecdsa_chip = EcdsaChip::new(&integer_gate, &scalar_integer_gate);
+
+        range_gate
+            .init_table(&mut layouter, &integer_gate.helper.integer_modulus)
+            .unwrap();
+        scalar_range_gate
+            .init_table(&mut layouter, &scalar_integer_gate.helper.integer_modulus)
+            .unwrap();
+
+        layouter.assign_region(
+            || "base",
+            |region| {
+                let base_offset = 0usize;
+                let mut aux = Context::new(region, base_offset);
+                let r = &mut aux;
+                match self.test_case {
+                    TestCase::Random => self.setup_test_verify(&ecdsa_chip, r, Self::sign()),
+                    TestCase::Vector => self.setup_test_verify(&ecdsa_chip, r, Self::vector()),
+                    TestCase::WrongMessage => {
+                        let (pk, msg_hash, sig_r, sig_s) = Self::vector();
+                        let signed = (pk, msg_hash + Fq::one(), sig_r, sig_s);
+                        self.setup_test_verify(&ecdsa_chip, r, signed)
+                    }
+                    TestCase::CanonicalX => {
+                        self.setup_test_r_of_x(&ecdsa_chip, r, BigUint::from(5u64))
+                    }
+                    TestCase::NonCanonicalX => {
+                        let p_modulus = field_to_bn(&-Fp::one()) + 1u64;
+                        self.setup_test_r_of_x(&ecdsa_chip, r, p_modulus + 5u64)
+                    }
+                }?;
+
+                Ok(())
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_five_column_ecdsa_chip_random() {
+    const K: u32 = (COMMON_RANGE_BITS + 3) as u32;
+    let circuit = TestFiveColumnEcdsaChipCircuit {
+        test_case: TestCase::Random,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn test_five_column_ecdsa_chip_vector() {
+    const K: u32 = (COMMON_RANGE_BITS + 3) as u32;
+    let circuit = TestFiveColumnEcdsaChipCircuit {
+        test_case: TestCase::Vector,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn test_five_column_ecdsa_chip_wrong_message() {
+    const K: u32 = (COMMON_RANGE_BITS + 3) as u32;
+    let circuit = TestFiveColumnEcdsaChipCircuit {
+        test_case: TestCase::WrongMessage,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_five_column_ecdsa_chip_canonical_x() {
+    const K: u32 = (COMMON_RANGE_BITS + 3) as u32;
+    let circuit = TestFiveColumnEcdsaChipCircuit {
+        test_case: TestCase::CanonicalX,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn test_five_column_ecdsa_chip_non_canonical_x() {
+    const K: u32 = (COMMON_RANGE_BITS + 3) as u32;
+    let circuit = TestFiveColumnEcdsaChipCircuit {
+        test_case: TestCase::NonCanonicalX,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert!(prover.verify().is_err());
+}
@@ -0,0 +1,249 @@
+use crate::chips::g2_ecc_chip::G2EccChip;
+use crate::five::base_gate::{FiveColumnBaseGate, FiveColumnBaseGateConfig};
+use crate::five::integer_chip::FiveColumnIntegerChip;
+use crate::five::range_gate::FiveColumnRangeGate;
+use crate::gates::base_gate::{BaseGateOps, Context};
+use crate::gates::range_gate::RangeGateConfig;
+use group::ff::Field;
+use group::Group;
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner},
+    dev::MockProver,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+use halo2curves::bn256::{Fq, Fr, G2};
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+enum TestCase {
+    Add,
+    Double,
+    Sub,
+    ConstantMul,
+}
+
+impl Default for TestCase {
+    fn default() -> TestCase {
+        TestCase::Add
+    }
+}
+
+#[derive(Clone)]
+struct TestFiveColumnG2EccChipConfig {
+    base_gate_config: FiveColumnBaseGateConfig,
+    range_gate_config: RangeGateConfig,
+}
+
+#[derive(Default)]
+struct TestFiveColumnG2EccChipCircuit {
+    test_case: TestCase,
+}
+
+impl TestFiveColumnG2EccChipCircuit {
+    fn random() -> Fr {
+        let seed = chrono::offset::Utc::now()
+            .timestamp_nanos()
+            .try_into()
+            .unwrap();
+        let rng = XorShiftRng::seed_from_u64(seed);
+        Fr::random(rng)
+    }
+
+    fn setup_test_add(
+        &self,
+        ecc_gate: &G2EccChip<'_, Fr>,
+        ctx: &mut Context<'_, Fr>,
+    ) -> Result<(), Error> {
+        let s1 = Self::random();
+        let s2 = Self::random();
+
+        let pi = ecc_gate.assign_identity(ctx)?;
+        let mut p1 = ecc_gate.assign_point(ctx, G2::generator() * s1)?;
+        let p2 = ecc_gate.assign_point(ctx, G2::generator() * s2)?;
+
+        let p1_ = ecc_gate.add(ctx, &mut p1, &pi)?;
+        ecc_gate.assert_equal(ctx, &p1, &p1_)?;
+
+        let p1_ = ecc_gate.add(ctx, &mut pi.clone(), &p1)?;
+        ecc_gate.assert_equal(ctx, &p1, &p1_)?;
+
+        let p3 = ecc_gate.assign_constant_point(ctx, G2::generator() * (s1 + s2))?;
+        let p3_ = ecc_gate.add(ctx, &mut p1, &p2)?;
+        ecc_gate.assert_equal(ctx, &p3, &p3_)?;
+
+        let p4 = ecc_gate.assign_constant_point(ctx, G2::generator() * (s1 + s1))?;
+        let p4_ = ecc_gate.add(ctx, &mut p1.clone(), &p1)?;
+        ecc_gate.assert_equal(ctx, &p4, &p4_)?;
+
+        let neg_p1 = ecc_gate.neg(ctx, &p1)?;
+        let pi_ = ecc_gate.add(ctx, &mut p1, &neg_p1)?;
+        ecc_gate.assert_equal(ctx, &pi, &pi_)?;
+
+        Ok(())
+    }
+
+    fn setup_test_double(
+        &self,
+        ecc_gate: &G2EccChip<'_, Fr>,
+        ctx: &mut Context<'_, Fr>,
+    ) -> Result<(), Error> {
+        let s1 = Self::random();
+
+        let mut p1 = ecc_gate.assign_point(ctx, G2::generator() * s1)?;
+        let p2 = ecc_gate.assign_constant_point(ctx, G2::generator() * (s1 + s1))?;
+        let p2_ = ecc_gate.double(ctx, &mut p1)?;
+        ecc_gate.assert_equal(ctx, &p2, &p2_)?;
+
+        let mut pi = ecc_gate.assign_identity(ctx)?;
+        let pi_ = ecc_gate.double(ctx, &mut pi)?;
+        ecc_gate.assert_equal(ctx, &pi, &pi_)?;
+
+        Ok(())
+    }
+
+    fn setup_test_sub(
+        &self,
+        ecc_gate: &G2EccChip<'_, Fr>,
+        ctx: &mut Context<'_, Fr>,
+    ) -> Result<(), Error> {
+        let s1 = Self::random();
+        let s2 = Self::random();
+
+        let mut p1 = ecc_gate.assign_point(ctx, G2::generator() * s1)?;
+        let p2 = ecc_gate.assign_point(ctx, G2::generator() * s2)?;
+        let p3 = ecc_gate.assign_constant_point(ctx, G2::generator() * (s1 - s2))?;
+        let p3_ = ecc_gate.sub(ctx, &mut p1, &p2)?;
+        ecc_gate.assert_equal(ctx, &p3, &p3_)?;
+
+        Ok(())
+    }
+
+    fn setup_test_constant_mul(
+        &self,
+        ecc_gate: &G2EccChip<'_, Fr>,
+        ctx: &mut Context<'_, Fr>,
+    ) -> Result<(), Error> {
+        let s1 = Self::random();
+        let s2 = Self::random();
+
+        let base = G2::generator() * s1;
+        let assigned_s2 = ecc_gate.base_gate().assign(ctx, s2)?;
+        let p = ecc_gate.assign_constant_point(ctx, base * s2)?;
+        let p_ = ecc_gate.constant_mul(ctx, base, &assigned_s2)?;
+        ecc_gate.assert_equal(ctx, &p, &p_)?;
+
+        Ok(())
+    }
+}
+
+const COMMON_RANGE_BITS: usize = 17usize;
+
+impl Circuit<Fr> for TestFiveColumnG2EccChipCircuit {
+    type Config = TestFiveColumnG2EccChipConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let base_gate_config = FiveColumnBaseGate::<Fr>::configure(meta);
+        let range_gate_config = FiveColumnRangeGate::<'_, Fq, Fr, COMMON_RANGE_BITS>::configure(
+            meta,
+            &base_gate_config,
+        );
+        TestFiveColumnG2EccChipConfig {
+            base_gate_config,
+            range_gate_config,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let base_gate = FiveColumnBaseGate::new(config.base_gate_config);
+        let range_gate = FiveColumnRangeGate::<'_, Fq, Fr, COMMON_RANGE_BITS>::new(
+            config.range_gate_config,
+            &base_gate,
+        );
+        let integer_gate = FiveColumnIntegerChip::new(&range_gate);
+        let ecc_gate = G2EccChip::new(&integer_gate);
+
+        range_gate
+            .init_table(&mut layouter, &integer_gate.helper.integer_modulus)
+            .unwrap();
+
+        layouter.assign_region(
+            || "base",
+            |region| {
+                let base_offset = 0usize;
+                let mut aux = Context::new(region, base_offset);
+                let r = &mut aux;
+                match self.test_case {
+                    TestCase::Add => self.setup_test_add(&ecc_gate, r),
+                    TestCase::Double => self.setup_test_double(&ecc_gate, r),
+                    TestCase::Sub => self.setup_test_sub(&ecc_gate, r),
+                    TestCase::ConstantMul => self.setup_test_constant_mul(&ecc_gate, r),
+                }?;
+
+                Ok(())
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_five_column_g2_ecc_chip_add() {
+    const K: u32 = (COMMON_RANGE_BITS + 1) as u32;
+    let circuit = TestFiveColumnG2EccChipCircuit {
+        test_case: TestCase::Add,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn test_five_column_g2_ecc_chip_double() {
+    const K: u32 = (COMMON_RANGE_BITS + 1) as u32;
+    let circuit = TestFiveColumnG2EccChipCircuit {
+        test_case: TestCase::Double,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn test_five_column_g2_ecc_chip_sub() {
+    const K: u32 = (COMMON_RANGE_BITS + 1) as u32;
+    let circuit = TestFiveColumnG2EccChipCircuit {
+        test_case: TestCase::Sub,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn test_five_column_g2_ecc_chip_constant_mul() {
+    const K: u32 = (COMMON_RANGE_BITS + 2) as u32;
+    let circuit = TestFiveColumnG2EccChipCircuit {
+        test_case: TestCase::ConstantMul,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
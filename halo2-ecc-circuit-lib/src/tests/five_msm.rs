@@ -0,0 +1,214 @@
+use crate::chips::ecc_chip::{EccChipOps, CONFIG_BUCKET_MSM_THRESHOLD, CONFIG_BUCKET_WINDOW_SIZE};
+use crate::chips::native_ecc_chip::NativeEccChip;
+use crate::five::base_gate::{FiveColumnBaseGate, FiveColumnBaseGateConfig};
+use crate::five::integer_chip::FiveColumnIntegerChip;
+use crate::five::range_gate::FiveColumnRangeGate;
+use crate::gates::base_gate::Context;
+use crate::gates::range_gate::RangeGateConfig;
+use group::ff::Field;
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner},
+    dev::MockProver,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+use halo2curves::bn256::{Fq, Fr, G1Affine};
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+use std::cell::Cell;
+
+#[derive(Clone, Copy, Debug)]
+enum TestCase {
+    Shamir,
+    Bucket,
+    GlvShamir,
+    GlvMsm,
+}
+
+impl Default for TestCase {
+    fn default() -> TestCase {
+        TestCase::Bucket
+    }
+}
+
+#[derive(Clone)]
+struct TestFiveColumnMsmConfig {
+    base_gate_config: FiveColumnBaseGateConfig,
+    range_gate_config: RangeGateConfig,
+}
+
+#[derive(Default)]
+struct TestFiveColumnMsmCircuit {
+    test_case: TestCase,
+    points: usize,
+    // Rows taken by the msm, for the bench
+    rows: Cell<usize>,
+}
+
+impl TestFiveColumnMsmCircuit {
+    fn random() -> Fr {
+        let seed = chrono::offset::Utc::now()
+            .timestamp_nanos()
+            .try_into()
+            .unwrap();
+        let rng = XorShiftRng::seed_from_u64(seed);
+        Fr::random(rng)
+    }
+
+    fn setup_test_msm(
+        &self,
+        ecc_gate: &NativeEccChip<'_, G1Affine>,
+        ctx: &mut Context<'_, Fr>,
+    ) -> Result<(), Error> {
+        let base_gate = ecc_gate.base_gate();
+
+        let mut points = vec![];
+        let mut scalars = vec![];
+        let mut expected = Fr::zero();
+        for _ in 0..self.points {
+            let s = Self::random();
+            let k = Self::random();
+            points.push(ecc_gate.assign_point_from_scalar(ctx, s)?);
+            scalars.push(base_gate.assign(ctx, k)?);
+            expected += s * k;
+        }
+
+        let start = *ctx.offset;
+        let mut p = match self.test_case {
+            TestCase::Shamir => ecc_gate.shamir(ctx, &mut points, &scalars),
+            TestCase::Bucket => {
+                ecc_gate.bucket_msm::<CONFIG_BUCKET_WINDOW_SIZE>(ctx, &mut points, &scalars)
+            }
+            TestCase::GlvShamir => ecc_gate.glv_shamir(ctx, &mut points, &scalars),
+            TestCase::GlvMsm => ecc_gate.glv_msm(ctx, &mut points, &scalars),
+        }?;
+        if !ctx.in_shape_mode() {
+            self.rows.set(*ctx.offset - start);
+        }
+
+        let mut p_ = ecc_gate.assign_constant_point_from_scalar(ctx, expected)?;
+        ecc_gate.assert_equal(ctx, &mut p, &mut p_)?;
+
+        Ok(())
+    }
+}
+
+const COMMON_RANGE_BITS: usize = 17usize;
+
+impl Circuit<Fr> for TestFiveColumnMsmCircuit {
+    type Config = TestFiveColumnMsmConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            test_case: self.test_case,
+            points: self.points,
+            rows: Cell::default(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let base_gate_config = FiveColumnBaseGate::<Fr>::configure(meta);
+        let range_gate_config = FiveColumnRangeGate::<'_, Fq, Fr, COMMON_RANGE_BITS>::configure(
+            meta,
+            &base_gate_config,
+        );
+        TestFiveColumnMsmConfig {
+            base_gate_config,
+            range_gate_config,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let base_gate = FiveColumnBaseGate::new(config.base_gate_config);
+        let range_gate = FiveColumnRangeGate::<'_, Fq, Fr, COMMON_RANGE_BITS>::new(
+            config.range_gate_config,
+            &base_gate,
+        );
+        let integer_gate = FiveColumnIntegerChip::new(&range_gate);
+        let ecc_gate = NativeEccChip::new(&integer_gate);
+
+        range_gate
+            .init_table(&mut layouter, &integer_gate.helper.integer_modulus)
+            .unwrap();
+
+        layouter.assign_region(
+            || "base",
+            |region| {
+                let base_offset = 0usize;
+                let mut aux = Context::new(region, base_offset);
+                let r = &mut aux;
+                r.in_shape_mode = base_gate.in_shape_mode(r)?;
+                self.setup_test_msm(&ecc_gate, r)?;
+
+                Ok(())
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+// Returns the rows taken by the msm
+fn run_msm(k: u32, test_case: TestCase, points: usize) -> usize {
+    let circuit = TestFiveColumnMsmCircuit {
+        test_case,
+        points,
+        ..Default::default()
+    };
+    let prover = match MockProver::run(k, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+    circuit.rows.get()
+}
+
+#[test]
+fn test_five_column_msm_bucket() {
+    // Not a multiple of the window, so the batches differ in size
+    run_msm((COMMON_RANGE_BITS + 4) as u32, TestCase::Bucket, 10);
+}
+
+#[test]
+fn test_five_column_msm_bucket_single_point() {
+    run_msm((COMMON_RANGE_BITS + 2) as u32, TestCase::Bucket, 1);
+}
+
+#[test]
+fn test_five_column_msm_glv_msm() {
+    run_msm((COMMON_RANGE_BITS + 4) as u32, TestCase::GlvMsm, 10);
+}
+
+// Prints the rows of each msm method and the point count from which `bucket_msm`
+// takes fewer rows than `shamir`, `CONFIG_BUCKET_MSM_THRESHOLD` is set from it. Run
+// with
+// cargo test --release bench_five_column_msm_rows -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_five_column_msm_rows() {
+    let mut crossover = None;
+    for points in [1, 2, 4, 6, 8, 12, 16, 32] {
+        let mut rows = vec![];
+        for test_case in [
+            TestCase::Shamir,
+            TestCase::Bucket,
+            TestCase::GlvShamir,
+            TestCase::GlvMsm,
+        ] {
+            let r = run_msm((COMMON_RANGE_BITS + 6) as u32, test_case, points);
+            println!("msm of {} points with {:?}: {} rows", points, test_case, r);
+            rows.push(r);
+        }
+        if crossover.is_none() && rows[1] < rows[0] {
+            crossover = Some(points);
+        }
+    }
+    println!(
+        "bucket_msm takes fewer rows than shamir from {:?} points, CONFIG_BUCKET_MSM_THRESHOLD is {}",
+        crossover, CONFIG_BUCKET_MSM_THRESHOLD
+    );
+}
@@ -26,6 +26,7 @@ enum TestCase {
     ShaMir,
     ConstantMul,
     Sub,
+    Glv,
 }
 
 impl Default for TestCase {
@@ -181,6 +182,44 @@ impl<C: CurveAffine> TestFiveColumnNativeEccChipCircuit<C> {
         Ok(())
     }
 
+    fn setup_test_glv(
+        &self,
+        ecc_gate: &NativeEccChip<'_, C>,
+        ctx: &mut Context<'_, C::ScalarExt>,
+    ) -> Result<(), Error> {
+        let base_gate = ecc_gate.base_gate();
+
+        let s1 = Self::random();
+        let s2 = Self::random();
+        let s3 = Self::random();
+        let s4 = Self::random();
+
+        let mut p1 = ecc_gate.assign_point_from_scalar(ctx, s1)?;
+        let p2 = ecc_gate.assign_point_from_scalar(ctx, s2)?;
+        let assigned_s3 = base_gate.assign(ctx, s3)?;
+        let assigned_s4 = base_gate.assign(ctx, s4)?;
+
+        let mut p = ecc_gate.glv_shamir(
+            ctx,
+            &mut vec![p1.clone(), p2],
+            &vec![assigned_s3, assigned_s4],
+        )?;
+        let mut p_ = ecc_gate.assign_constant_point_from_scalar(ctx, s1 * s3 + s2 * s4)?;
+        ecc_gate.assert_equal(ctx, &mut p, &mut p_)?;
+
+        let mut p = ecc_gate.glv_mul(ctx, &mut p1, &assigned_s3)?;
+        let mut p_ = ecc_gate.assign_constant_point_from_scalar(ctx, s1 * s3)?;
+        ecc_gate.assert_equal(ctx, &mut p, &mut p_)?;
+
+        // -1 splits into a negative k1 and a zero k2
+        let neg_one = base_gate.assign(ctx, -C::ScalarExt::one())?;
+        let mut p = ecc_gate.glv_mul(ctx, &mut p1, &neg_one)?;
+        let mut p_ = ecc_gate.assign_constant_point_from_scalar(ctx, -s1)?;
+        ecc_gate.assert_equal(ctx, &mut p, &mut p_)?;
+
+        Ok(())
+    }
+
     fn setup_test_constant_mul(
         &self,
         ecc_gate: &NativeEccChip<'_, C>,
@@ -295,6 +334,7 @@ impl<C: CurveAffine> Circuit<C::ScalarExt> for TestFiveColumnNativeEccChipCircui
                         TestCase::Sub => self.setup_test_sub(&ecc_gate, r),
                         TestCase::ConstantMul => self.setup_test_constant_mul(&ecc_gate, r),
                         TestCase::ShaMir => self.setup_test_shamir(&ecc_gate, r),
+                        TestCase::Glv => self.setup_test_glv(&ecc_gate, r),
                     }?;
                 }
 
@@ -395,3 +435,18 @@ fn test_five_column_natvie_ecc_chip_shamir() {
     };
     assert_eq!(prover.verify(), Ok(()));
 }
+
+#[test]
+fn test_five_column_natvie_ecc_chip_glv() {
+    const K: u32 = (COMMON_RANGE_BITS + 2) as u32;
+    let chip = TestFiveColumnNativeEccChipCircuit::<G1Affine> {
+        test_case: TestCase::Glv,
+        _phantom_w: PhantomData,
+        _phantom_n: PhantomData,
+    };
+    let prover = match MockProver::run(K, &chip, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
@@ -0,0 +1,300 @@
+use crate::chips::ecc_chip::AssignedPoint;
+use crate::chips::integer_chip::IntegerChipOps;
+use crate::chips::pairing_chip::PairingChip;
+use crate::five::base_gate::{FiveColumnBaseGate, FiveColumnBaseGateConfig};
+use crate::five::integer_chip::FiveColumnIntegerChip;
+use crate::five::range_gate::FiveColumnRangeGate;
+use crate::gates::base_gate::{BaseGateOps, Context};
+use crate::gates::range_gate::RangeGateConfig;
+use crate::utils::field_to_bn;
+use group::ff::Field;
+use group::{prime::PrimeCurveAffine, Curve};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner},
+    dev::MockProver,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+use halo2curves::bn256::{Fq, Fq12, Fr, G1Affine, G2Affine};
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+enum TestCase {
+    Fq12Mul,
+    Fq12Square,
+    Fq12Frobenius,
+    Fq12Invert,
+    Pairing,
+    PairingInvalid,
+}
+
+impl Default for TestCase {
+    fn default() -> TestCase {
+        TestCase::Fq12Mul
+    }
+}
+
+#[derive(Clone)]
+struct TestFiveColumnPairingChipConfig {
+    base_gate_config: FiveColumnBaseGateConfig,
+    range_gate_config: RangeGateConfig,
+}
+
+#[derive(Default)]
+struct TestFiveColumnPairingChipCircuit {
+    test_case: TestCase,
+}
+
+impl TestFiveColumnPairingChipCircuit {
+    fn rng() -> XorShiftRng {
+        let seed = chrono::offset::Utc::now()
+            .timestamp_nanos()
+            .try_into()
+            .unwrap();
+        XorShiftRng::seed_from_u64(seed)
+    }
+
+    fn setup_test_fq12_mul(
+        &self,
+        pairing_chip: &PairingChip<'_, Fr>,
+        ctx: &mut Context<'_, Fr>,
+    ) -> Result<(), Error> {
+        let fp12_chip = &pairing_chip.fp12_chip;
+        let a = Fq12::random(Self::rng());
+        let b = Fq12::random(Self::rng());
+        let c = a * b;
+
+        let mut assigned_a = fp12_chip.assign_constant(ctx, a)?;
+        let mut assigned_b = fp12_chip.assign_constant(ctx, b)?;
+        let assigned_c = fp12_chip.assign_constant(ctx, c)?;
+
+        let res = fp12_chip.mul(ctx, &mut assigned_a, &mut assigned_b)?;
+        fp12_chip.assert_equal(ctx, &assigned_c, &res)?;
+        Ok(())
+    }
+
+    fn setup_test_fq12_square(
+        &self,
+        pairing_chip: &PairingChip<'_, Fr>,
+        ctx: &mut Context<'_, Fr>,
+    ) -> Result<(), Error> {
+        let fp12_chip = &pairing_chip.fp12_chip;
+        let a = Fq12::random(Self::rng());
+        let c = a.square();
+
+        let mut assigned_a = fp12_chip.assign_constant(ctx, a)?;
+        let assigned_c = fp12_chip.assign_constant(ctx, c)?;
+
+        let res = fp12_chip.square(ctx, &mut assigned_a)?;
+        fp12_chip.assert_equal(ctx, &assigned_c, &res)?;
+        Ok(())
+    }
+
+    fn setup_test_fq12_frobenius(
+        &self,
+        pairing_chip: &PairingChip<'_, Fr>,
+        ctx: &mut Context<'_, Fr>,
+    ) -> Result<(), Error> {
+        let fp12_chip = &pairing_chip.fp12_chip;
+        let a = Fq12::random(Self::rng());
+        let assigned_a = fp12_chip.assign_constant(ctx, a)?;
+
+        let p = field_to_bn(&-Fq::one()) + 1u64;
+        for power in 1..4 {
+            let c = a.pow_vartime(p.pow(power as u32).to_u64_digits());
+            let assigned_c = fp12_chip.assign_constant(ctx, c)?;
+            let res = fp12_chip.frobenius_map(ctx, &assigned_a, power)?;
+            fp12_chip.assert_equal(ctx, &assigned_c, &res)?;
+        }
+        Ok(())
+    }
+
+    fn setup_test_fq12_invert(
+        &self,
+        pairing_chip: &PairingChip<'_, Fr>,
+        ctx: &mut Context<'_, Fr>,
+    ) -> Result<(), Error> {
+        let fp12_chip = &pairing_chip.fp12_chip;
+        let a = Fq12::random(Self::rng());
+        let c = a.invert().unwrap();
+
+        let mut assigned_a = fp12_chip.assign(ctx, a)?;
+        let assigned_c = fp12_chip.assign_constant(ctx, c)?;
+
+        let res = fp12_chip.invert(ctx, &mut assigned_a)?;
+        fp12_chip.assert_equal(ctx, &assigned_c, &res)?;
+        Ok(())
+    }
+
+    fn assign_g1(
+        pairing_chip: &PairingChip<'_, Fr>,
+        ctx: &mut Context<'_, Fr>,
+        p: G1Affine,
+    ) -> Result<AssignedPoint<G1Affine, Fr>, Error> {
+        let integer_chip = pairing_chip.fp2_chip().integer_chip;
+        let x = integer_chip.assign_w(ctx, &p.x)?;
+        let y = integer_chip.assign_w(ctx, &p.y)?;
+        let z = integer_chip.base_gate().assign_constant(ctx, Fr::zero())?;
+        Ok(AssignedPoint::new(x, y, z.into()))
+    }
+
+    fn setup_test_pairing(
+        &self,
+        pairing_chip: &PairingChip<'_, Fr>,
+        ctx: &mut Context<'_, Fr>,
+        valid: bool,
+    ) -> Result<(), Error> {
+        let k = Fr::random(Self::rng());
+        let p = (G1Affine::generator() * Fr::random(Self::rng())).to_affine();
+        let q = (G2Affine::generator() * Fr::random(Self::rng())).to_affine();
+
+        // e(k * p, q) * e(-p, k * q) = 1, while e(k * p, q) * e(-p, q) = e(p, q) ^ (k - 1)
+        let p1 = Self::assign_g1(pairing_chip, ctx, (p * k).to_affine())?;
+        let q1 = pairing_chip.assign_g2(ctx, q)?;
+        let p2 = Self::assign_g1(pairing_chip, ctx, -p)?;
+        let q2 = if valid { (q * k).to_affine() } else { q };
+        let q2 = pairing_chip.assign_g2(ctx, q2)?;
+
+        pairing_chip.pairing_check(ctx, &[(&p1, &q1), (&p2, &q2)])
+    }
+}
+
+const COMMON_RANGE_BITS: usize = 17usize;
+
+impl Circuit<Fr> for TestFiveColumnPairingChipCircuit {
+    type Config = TestFiveColumnPairingChipConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let base_gate_config = FiveColumnBaseGate::<Fr>::configure(meta);
+        let range_gate_config = FiveColumnRangeGate::<'_, Fq, Fr, COMMON_RANGE_BITS>::configure(
+            meta,
+            &base_gate_config,
+        );
+        TestFiveColumnPairingChipConfig {
+            base_gate_config,
+            range_gate_config,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let base_gate = FiveColumnBaseGate::new(config.base_gate_config);
+        let range_gate = FiveColumnRangeGate::<'_, Fq, Fr, COMMON_RANGE_BITS>::new(
+            config.range_gate_config,
+            &base_gate,
+        );
+        let integer_gate = FiveColumnIntegerChip::new(&range_gate);
+        let pairing_chip = PairingChip::new(&integer_gate);
+
+        range_gate
+            .init_table(&mut layouter, &integer_gate.helper.integer_modulus)
+            .unwrap();
+
+        layouter.assign_region(
+            || "base",
+            |region| {
+                let base_offset = 0usize;
+                let mut aux = Context::new(region, base_offset);
+                let r = &mut aux;
+                match self.test_case {
+                    TestCase::Fq12Mul => self.setup_test_fq12_mul(&pairing_chip, r),
+                    TestCase::Fq12Square => self.setup_test_fq12_square(&pairing_chip, r),
+                    TestCase::Fq12Frobenius => self.setup_test_fq12_frobenius(&pairing_chip, r),
+                    TestCase::Fq12Invert => self.setup_test_fq12_invert(&pairing_chip, r),
+                    TestCase::Pairing => self.setup_test_pairing(&pairing_chip, r, true),
+                    TestCase::PairingInvalid => self.setup_test_pairing(&pairing_chip, r, false),
+                }?;
+
+                Ok(())
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_five_column_pairing_chip_fq12_mul() {
+    const K: u32 = (COMMON_RANGE_BITS + 1) as u32;
+    let circuit = TestFiveColumnPairingChipCircuit {
+        test_case: TestCase::Fq12Mul,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn test_five_column_pairing_chip_fq12_square() {
+    const K: u32 = (COMMON_RANGE_BITS + 1) as u32;
+    let circuit = TestFiveColumnPairingChipCircuit {
+        test_case: TestCase::Fq12Square,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn test_five_column_pairing_chip_fq12_frobenius() {
+    const K: u32 = (COMMON_RANGE_BITS + 1) as u32;
+    let circuit = TestFiveColumnPairingChipCircuit {
+        test_case: TestCase::Fq12Frobenius,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn test_five_column_pairing_chip_fq12_invert() {
+    const K: u32 = (COMMON_RANGE_BITS + 1) as u32;
+    let circuit = TestFiveColumnPairingChipCircuit {
+        test_case: TestCase::Fq12Invert,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+// A full pairing check takes about 2^20 rows, this one is slow.
+#[test]
+fn test_five_column_pairing_chip_pairing() {
+    const K: u32 = 21;
+    let circuit = TestFiveColumnPairingChipCircuit {
+        test_case: TestCase::Pairing,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn test_five_column_pairing_chip_pairing_invalid() {
+    const K: u32 = 21;
+    let circuit = TestFiveColumnPairingChipCircuit {
+        test_case: TestCase::PairingInvalid,
+    };
+    let prover = match MockProver::run(K, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert!(prover.verify().is_err());
+}
@@ -1,4 +1,5 @@
 pub mod ast;
+pub mod bits;
 pub mod common;
 pub mod ecc;
 pub mod field;
@@ -0,0 +1,85 @@
+use super::field::ArithFieldChip;
+use halo2_proofs::arithmetic::Field;
+
+// Bits are assigned fields constrained to be 0 or 1, bit vectors are little-endian.
+pub trait ArithBitsChip: ArithFieldChip {
+    // `a` must be less than 2 ^ bits, which must not wrap around the field modulus.
+    fn to_bits(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedField,
+        bits: usize,
+    ) -> Result<Vec<Self::AssignedField>, Self::Error>;
+
+    // Bits of the canonical representation of `a`, `Self::Field::NUM_BITS` of them.
+    fn to_bits_canonical(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedField,
+    ) -> Result<Vec<Self::AssignedField>, Self::Error>;
+
+    fn from_bits(
+        &self,
+        ctx: &mut Self::Context,
+        bits: &[Self::AssignedField],
+    ) -> Result<Self::AssignedField, Self::Error> {
+        let two = Self::Field::from(2u64);
+        let mut coeff = Self::Field::one();
+        let mut elems = vec![];
+        for bit in bits.iter() {
+            elems.push((bit, coeff));
+            coeff *= two;
+        }
+
+        self.sum_with_coeff_and_constant(ctx, elems, Self::Field::zero())
+    }
+
+    fn assign_const_bits(
+        &self,
+        ctx: &mut Self::Context,
+        bytes: &[u8],
+    ) -> Result<Vec<Self::AssignedField>, Self::Error> {
+        let zero = self.assign_zero(ctx)?;
+        let one = self.assign_one(ctx)?;
+
+        Ok(bytes
+            .iter()
+            .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+            .map(|bit| if bit { one.clone() } else { zero.clone() })
+            .collect())
+    }
+
+    fn and(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedField,
+        b: &Self::AssignedField,
+    ) -> Result<Self::AssignedField, Self::Error> {
+        self.mul(ctx, a, b)
+    }
+
+    fn not(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedField,
+    ) -> Result<Self::AssignedField, Self::Error> {
+        let one = Self::Field::one();
+        self.sum_with_coeff_and_constant(ctx, vec![(a, -one)], one)
+    }
+
+    fn xor(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedField,
+        b: &Self::AssignedField,
+    ) -> Result<Self::AssignedField, Self::Error> {
+        let one = Self::Field::one();
+        let two = Self::Field::from(2u64);
+        let ab = self.mul(ctx, a, b)?;
+        self.sum_with_coeff_and_constant(
+            ctx,
+            vec![(a, one), (b, one), (&ab, -two)],
+            Self::Field::zero(),
+        )
+    }
+}
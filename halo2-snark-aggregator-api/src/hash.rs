@@ -1 +1,7 @@
+#[cfg(feature = "blake2b")]
+pub mod blake2b;
+#[cfg(feature = "keccak")]
+pub mod keccak;
 pub mod poseidon;
+#[cfg(any(feature = "blake2b", feature = "keccak"))]
+mod word;
@@ -0,0 +1,151 @@
+use super::word::{self, Word};
+use crate::arith::bits::ArithBitsChip;
+
+// Block size of blake2b in bytes.
+const BLOCK: usize = 128;
+
+// Length of the digest in bytes.
+const OUTPUT: usize = 64;
+
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const SIGMA: [[usize; 16]; 12] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+];
+
+// Unkeyed blake2b with a 64-byte digest and a personalization, as `Blake2bRead`.
+// Inputs and digests are bytes as little-endian bits.
+pub struct Blake2bChip<A: ArithBitsChip> {
+    h: Vec<Word<A>>,
+    // bytes compressed so far
+    t: u128,
+    absorbing: Vec<A::AssignedField>,
+}
+
+impl<A: ArithBitsChip> Blake2bChip<A> {
+    pub fn new(ctx: &mut A::Context, chip: &A, personal: &[u8; 16]) -> Result<Self, A::Error> {
+        // digest length, fanout 1 and depth 1 in the first word of the parameter block
+        let mut params = [0u64; 8];
+        params[0] = 0x0101_0000 | OUTPUT as u64;
+        params[6] = u64::from_le_bytes(personal[0..8].try_into().unwrap());
+        params[7] = u64::from_le_bytes(personal[8..16].try_into().unwrap());
+
+        let h = IV
+            .iter()
+            .zip(params.iter())
+            .map(|(iv, p)| word::const_word(ctx, chip, iv ^ p))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            h,
+            t: 0,
+            absorbing: Vec::new(),
+        })
+    }
+
+    pub fn update(&mut self, bits: &[A::AssignedField]) {
+        assert!(bits.len() % 8 == 0);
+        self.absorbing.extend_from_slice(bits);
+    }
+
+    // Digest of all the input so far, the chip keeps absorbing afterwards.
+    pub fn squeeze(
+        &mut self,
+        ctx: &mut A::Context,
+        chip: &A,
+    ) -> Result<Vec<A::AssignedField>, A::Error> {
+        // The last block can only be compressed once we know it is the last one.
+        while self.absorbing.len() > BLOCK * 8 {
+            let block = self.absorbing.drain(0..BLOCK * 8).collect::<Vec<_>>();
+            self.t += BLOCK as u128;
+            self.h = Self::compress(ctx, chip, &self.h, &block, self.t, false)?;
+        }
+
+        let t = self.t + (self.absorbing.len() / 8) as u128;
+        let mut block = self.absorbing.clone();
+        block.resize(BLOCK * 8, chip.assign_zero(ctx)?);
+        let h = Self::compress(ctx, chip, &self.h, &block, t, true)?;
+
+        Ok(h.concat())
+    }
+
+    fn compress(
+        ctx: &mut A::Context,
+        chip: &A,
+        h: &[Word<A>],
+        block: &[A::AssignedField],
+        t: u128,
+        last: bool,
+    ) -> Result<Vec<Word<A>>, A::Error> {
+        let m = block.chunks(64).collect::<Vec<_>>();
+
+        let mut v = h.to_vec();
+        for (i, iv) in IV.iter().enumerate() {
+            let c = match i {
+                4 => iv ^ t as u64,
+                5 => iv ^ (t >> 64) as u64,
+                6 if last => !iv,
+                _ => *iv,
+            };
+            v.push(word::const_word(ctx, chip, c)?);
+        }
+
+        for s in SIGMA.iter() {
+            Self::mix(ctx, chip, &mut v, [0, 4, 8, 12], m[s[0]], m[s[1]])?;
+            Self::mix(ctx, chip, &mut v, [1, 5, 9, 13], m[s[2]], m[s[3]])?;
+            Self::mix(ctx, chip, &mut v, [2, 6, 10, 14], m[s[4]], m[s[5]])?;
+            Self::mix(ctx, chip, &mut v, [3, 7, 11, 15], m[s[6]], m[s[7]])?;
+            Self::mix(ctx, chip, &mut v, [0, 5, 10, 15], m[s[8]], m[s[9]])?;
+            Self::mix(ctx, chip, &mut v, [1, 6, 11, 12], m[s[10]], m[s[11]])?;
+            Self::mix(ctx, chip, &mut v, [2, 7, 8, 13], m[s[12]], m[s[13]])?;
+            Self::mix(ctx, chip, &mut v, [3, 4, 9, 14], m[s[14]], m[s[15]])?;
+        }
+
+        h.iter()
+            .enumerate()
+            .map(|(i, h)| {
+                let x = word::xor(ctx, chip, h, &v[i])?;
+                word::xor(ctx, chip, &x, &v[i + 8])
+            })
+            .collect()
+    }
+
+    fn mix(
+        ctx: &mut A::Context,
+        chip: &A,
+        v: &mut [Word<A>],
+        [a, b, c, d]: [usize; 4],
+        x: &[A::AssignedField],
+        y: &[A::AssignedField],
+    ) -> Result<(), A::Error> {
+        v[a] = word::add(ctx, chip, &[&v[a][..], &v[b][..], x])?;
+        v[d] = word::rotate_right(&word::xor(ctx, chip, &v[d], &v[a])?, 32);
+        v[c] = word::add(ctx, chip, &[&v[c][..], &v[d][..]])?;
+        v[b] = word::rotate_right(&word::xor(ctx, chip, &v[b], &v[c])?, 24);
+        v[a] = word::add(ctx, chip, &[&v[a][..], &v[b][..], y])?;
+        v[d] = word::rotate_right(&word::xor(ctx, chip, &v[d], &v[a])?, 16);
+        v[c] = word::add(ctx, chip, &[&v[c][..], &v[d][..]])?;
+        v[b] = word::rotate_right(&word::xor(ctx, chip, &v[b], &v[c])?, 63);
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+use super::word::{self, Word};
+use crate::arith::bits::ArithBitsChip;
+
+// Rate of keccak-256 in bytes.
+const RATE: usize = 136;
+
+const RHO: [usize; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+const RC: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+// Keccak-256 with the original padding, as the EVM `keccak256`.
+// Inputs and digests are bytes as little-endian bits.
+pub struct KeccakChip<A: ArithBitsChip> {
+    // lanes after the blocks absorbed so far
+    state: Option<Vec<Word<A>>>,
+    absorbing: Vec<A::AssignedField>,
+}
+
+impl<A: ArithBitsChip> Default for KeccakChip<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: ArithBitsChip> KeccakChip<A> {
+    pub fn new() -> Self {
+        Self {
+            state: None,
+            absorbing: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, bits: &[A::AssignedField]) {
+        assert!(bits.len() % 8 == 0);
+        self.absorbing.extend_from_slice(bits);
+    }
+
+    // Digest of all the input so far, the chip keeps absorbing afterwards.
+    pub fn squeeze(
+        &mut self,
+        ctx: &mut A::Context,
+        chip: &A,
+    ) -> Result<Vec<A::AssignedField>, A::Error> {
+        self.squeeze_with_suffix(ctx, chip, &[])
+    }
+
+    // Digest of all the input so far followed by `suffix`, which is not absorbed.
+    pub fn squeeze_with_suffix(
+        &mut self,
+        ctx: &mut A::Context,
+        chip: &A,
+        suffix: &[A::AssignedField],
+    ) -> Result<Vec<A::AssignedField>, A::Error> {
+        assert!(suffix.len() % 8 == 0);
+
+        // Full blocks are absorbed for good, the padding only goes after the rest.
+        while self.absorbing.len() >= RATE * 8 {
+            let block = self.absorbing.drain(0..RATE * 8).collect::<Vec<_>>();
+            let state = self.state.take();
+            self.state = Some(Self::absorb(ctx, chip, state, &block)?);
+        }
+
+        let mut input_bits = self.absorbing.clone();
+        input_bits.extend_from_slice(suffix);
+
+        let padding_len = RATE - (input_bits.len() / 8) % RATE;
+        let mut padding = vec![0u8; padding_len];
+        padding[0] |= 0x01;
+        padding[padding_len - 1] |= 0x80;
+        input_bits.append(&mut chip.assign_const_bits(ctx, &padding)?);
+
+        let mut state = self.state.clone();
+        for block in input_bits.chunks(RATE * 8) {
+            state = Some(Self::absorb(ctx, chip, state, block)?);
+        }
+
+        Ok(state.unwrap()[0..4].concat())
+    }
+
+    fn absorb(
+        ctx: &mut A::Context,
+        chip: &A,
+        state: Option<Vec<Word<A>>>,
+        block: &[A::AssignedField],
+    ) -> Result<Vec<Word<A>>, A::Error> {
+        let mut lanes = match state {
+            None => {
+                let zero = word::const_word(ctx, chip, 0)?;
+                let mut lanes = block.chunks(64).map(|x| x.to_vec()).collect::<Vec<_>>();
+                lanes.resize(25, zero);
+                lanes
+            }
+            Some(mut lanes) => {
+                for (lane, x) in lanes.iter_mut().zip(block.chunks(64)) {
+                    *lane = word::xor(ctx, chip, lane, x)?;
+                }
+                lanes
+            }
+        };
+        Self::permutation(ctx, chip, &mut lanes)?;
+        Ok(lanes)
+    }
+
+    fn permutation(ctx: &mut A::Context, chip: &A, a: &mut [Word<A>]) -> Result<(), A::Error> {
+        for rc in RC.iter() {
+            // theta
+            let mut c = vec![];
+            for x in 0..5 {
+                let mut acc = a[x].clone();
+                for y in 1..5 {
+                    acc = word::xor(ctx, chip, &acc, &a[5 * y + x])?;
+                }
+                c.push(acc);
+            }
+            for x in 0..5 {
+                let rotated = word::rotate_left(&c[(x + 1) % 5], 1);
+                let d = word::xor(ctx, chip, &c[(x + 4) % 5], &rotated)?;
+                for y in 0..5 {
+                    a[5 * y + x] = word::xor(ctx, chip, &a[5 * y + x], &d)?;
+                }
+            }
+
+            // rho and pi
+            let mut last = a[1].clone();
+            for (pi, rho) in PI.iter().zip(RHO.iter()) {
+                let rotated = word::rotate_left(&last, *rho);
+                last = std::mem::replace(&mut a[*pi], rotated);
+            }
+
+            // chi
+            for y in 0..5 {
+                let row = a[5 * y..5 * y + 5].to_vec();
+                for x in 0..5 {
+                    let t = word::and_not(ctx, chip, &row[(x + 1) % 5], &row[(x + 2) % 5])?;
+                    a[5 * y + x] = word::xor(ctx, chip, &row[x], &t)?;
+                }
+            }
+
+            // iota
+            a[0] = word::xor_const(ctx, chip, &a[0], *rc)?;
+        }
+
+        Ok(())
+    }
+}
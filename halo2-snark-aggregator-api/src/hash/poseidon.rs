@@ -2,6 +2,22 @@ use crate::arith::field::ArithFieldChip;
 use halo2_proofs::arithmetic::Field;
 use poseidon::{SparseMDSMatrix, Spec, State};
 
+// Width `T`, rate `RATE` and number of full and partial rounds of a poseidon sponge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoseidonSpec<const T: usize, const RATE: usize> {
+    pub r_f: usize,
+    pub r_p: usize,
+}
+
+// The spec of halo2's `PoseidonRead` and `PoseidonWrite`. Only the round counts of a
+// transcript spec can differ from halo2's: the width and the rate are type parameters,
+// `PoseidonRead` is fixed to 9 and 8 and every transcript reader uses `TranscriptSpec`.
+pub const TRANSCRIPT_T: usize = 9;
+pub const TRANSCRIPT_RATE: usize = 8;
+pub type TranscriptSpec = PoseidonSpec<TRANSCRIPT_T, TRANSCRIPT_RATE>;
+
+pub const HALO2_TRANSCRIPT_SPEC: TranscriptSpec = PoseidonSpec { r_f: 8, r_p: 63 };
+
 struct PoseidonState<A: ArithFieldChip, const T: usize, const RATE: usize> {
     s: [A::AssignedValue; T],
 }
@@ -164,6 +180,14 @@ impl<A: ArithFieldChip, const T: usize, const RATE: usize> PoseidonChip<A, T, RA
         })
     }
 
+    pub fn from_spec(
+        ctx: &mut A::Context,
+        chip: &A,
+        spec: &PoseidonSpec<T, RATE>,
+    ) -> Result<Self, A::Error> {
+        Self::new(ctx, chip, spec.r_f, spec.r_p)
+    }
+
     pub fn update(&mut self, elements: &[A::AssignedValue]) {
         self.absorbing.extend_from_slice(elements);
     }
@@ -0,0 +1,95 @@
+use crate::arith::{bits::ArithBitsChip, field::ArithFieldChip};
+use halo2_proofs::arithmetic::Field;
+
+// 64-bit word as little-endian bits.
+pub(crate) type Word<A> = Vec<<A as ArithFieldChip>::AssignedField>;
+
+pub(crate) fn const_word<A: ArithBitsChip>(
+    ctx: &mut A::Context,
+    chip: &A,
+    c: u64,
+) -> Result<Word<A>, A::Error> {
+    chip.assign_const_bits(ctx, &c.to_le_bytes())
+}
+
+pub(crate) fn xor<A: ArithBitsChip>(
+    ctx: &mut A::Context,
+    chip: &A,
+    a: &[A::AssignedField],
+    b: &[A::AssignedField],
+) -> Result<Word<A>, A::Error> {
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| chip.xor(ctx, a, b))
+        .collect()
+}
+
+pub(crate) fn xor_const<A: ArithBitsChip>(
+    ctx: &mut A::Context,
+    chip: &A,
+    a: &[A::AssignedField],
+    c: u64,
+) -> Result<Word<A>, A::Error> {
+    a.iter()
+        .enumerate()
+        .map(|(i, bit)| {
+            if (c >> i) & 1 == 1 {
+                chip.not(ctx, bit)
+            } else {
+                Ok(bit.clone())
+            }
+        })
+        .collect()
+}
+
+// !a & b
+pub(crate) fn and_not<A: ArithBitsChip>(
+    ctx: &mut A::Context,
+    chip: &A,
+    a: &[A::AssignedField],
+    b: &[A::AssignedField],
+) -> Result<Word<A>, A::Error> {
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| {
+            let not_a = chip.not(ctx, a)?;
+            chip.and(ctx, &not_a, b)
+        })
+        .collect()
+}
+
+pub(crate) fn rotate_left<T: Clone>(a: &[T], n: usize) -> Vec<T> {
+    let mut res = a.to_vec();
+    res.rotate_right(n);
+    res
+}
+
+pub(crate) fn rotate_right<T: Clone>(a: &[T], n: usize) -> Vec<T> {
+    let mut res = a.to_vec();
+    res.rotate_left(n);
+    res
+}
+
+// Wrapping addition, the sum is taken on the field and the carries are dropped.
+pub(crate) fn add<A: ArithBitsChip>(
+    ctx: &mut A::Context,
+    chip: &A,
+    words: &[&[A::AssignedField]],
+) -> Result<Word<A>, A::Error> {
+    let two = A::Field::from(2u64);
+
+    let mut elems = vec![];
+    for word in words.iter() {
+        let mut coeff = A::Field::one();
+        for bit in word.iter() {
+            elems.push((bit, coeff));
+            coeff *= two;
+        }
+    }
+    let sum = chip.sum_with_coeff_and_constant(ctx, elems, A::Field::zero())?;
+
+    let carry_bits = (usize::BITS - (words.len() - 1).leading_zeros()) as usize;
+    let mut res = chip.to_bits(ctx, &sum, 64 + carry_bits)?;
+    res.truncate(64);
+    Ok(res)
+}
@@ -1,4 +1,4 @@
-use crate::arith::{common::ArithCommonChip, field::ArithFieldChip};
+use crate::arith::{bits::ArithBitsChip, common::ArithCommonChip, field::ArithFieldChip};
 use halo2_proofs::arithmetic::FieldExt;
 use std::marker::PhantomData;
 
@@ -144,3 +144,31 @@ impl<F: FieldExt, E> ArithFieldChip for MockFieldChip<F, E> {
         Ok(*a * *b + c)
     }
 }
+
+impl<F: FieldExt, E> ArithBitsChip for MockFieldChip<F, E> {
+    fn to_bits(
+        &self,
+        _ctx: &mut Self::Context,
+        a: &Self::AssignedField,
+        bits: usize,
+    ) -> Result<Vec<Self::AssignedField>, Self::Error> {
+        let mut res = a
+            .to_repr()
+            .as_ref()
+            .iter()
+            .flat_map(|byte| (0..8).map(move |i| F::from(((byte >> i) & 1) as u64)))
+            .collect::<Vec<_>>();
+        assert!(res[bits..].iter().all(|bit| *bit == self.zero));
+
+        res.truncate(bits);
+        Ok(res)
+    }
+
+    fn to_bits_canonical(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedField,
+    ) -> Result<Vec<Self::AssignedField>, Self::Error> {
+        self.to_bits(ctx, a, F::NUM_BITS as usize)
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     arith::{common::ArithCommonChip, ecc::ArithEccChip},
-    transcript::encode::Encode,
+    transcript::encode::{Encode, EncodeBits},
 };
 use halo2_proofs::arithmetic::{CurveAffine, Field, FieldExt};
 use num_bigint::BigUint;
@@ -72,3 +72,75 @@ impl<F: FieldExt, A: ArithEccChip<Scalar = F, Native = F>> Encode<A> for Poseido
         Ok(v)
     }
 }
+
+fn bytes_to_bits<A: ArithEccChip>(
+    ctx: &mut A::Context,
+    nchip: &A::NativeChip,
+    bytes: &[u8],
+) -> Result<Vec<A::AssignedNative>, A::Error> {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1))
+        .map(|bit| nchip.assign_var(ctx, A::Native::from(bit as u64)))
+        .collect()
+}
+
+pub struct BitsEncode();
+
+impl<F: FieldExt, A: ArithEccChip<Scalar = F, Native = F>> EncodeBits<A> for BitsEncode {
+    fn encode_point_bits(
+        ctx: &mut <A>::Context,
+        nchip: &<A as ArithEccChip>::NativeChip,
+        _schip: &<A as ArithEccChip>::ScalarChip,
+        pchip: &A,
+        v: &<A as ArithEccChip>::AssignedPoint,
+    ) -> Result<
+        (
+            Vec<<A as ArithEccChip>::AssignedNative>,
+            Vec<<A as ArithEccChip>::AssignedNative>,
+        ),
+        <A>::Error,
+    > {
+        let p = pchip.to_value(v)?;
+        let c = p.coordinates();
+        let x = c
+            .map(|v| *v.x())
+            .unwrap_or(<A::Point as CurveAffine>::Base::zero());
+        let y = c
+            .map(|v| *v.y())
+            .unwrap_or(<A::Point as CurveAffine>::Base::zero());
+
+        let px = bytes_to_bits::<A>(ctx, nchip, x.to_repr().as_ref())?;
+        let py = bytes_to_bits::<A>(ctx, nchip, y.to_repr().as_ref())?;
+
+        Ok((px, py))
+    }
+
+    fn encode_scalar_bits(
+        ctx: &mut <A>::Context,
+        nchip: &<A as ArithEccChip>::NativeChip,
+        schip: &<A as ArithEccChip>::ScalarChip,
+        v: &<A as ArithEccChip>::AssignedScalar,
+    ) -> Result<Vec<<A as ArithEccChip>::AssignedNative>, <A>::Error> {
+        let value = schip.to_value(v)?;
+        bytes_to_bits::<A>(ctx, nchip, value.to_repr().as_ref())
+    }
+
+    fn decode_scalar_bits(
+        ctx: &mut <A>::Context,
+        nchip: &<A as ArithEccChip>::NativeChip,
+        schip: &<A as ArithEccChip>::ScalarChip,
+        v: &[<A as ArithEccChip>::AssignedNative],
+    ) -> Result<<A as ArithEccChip>::AssignedScalar, <A>::Error> {
+        assert!(v.len() <= 512);
+
+        let mut bytes = [0u8; 64];
+        for (i, bit) in v.iter().enumerate() {
+            if nchip.to_value(bit)? != F::zero() {
+                bytes[i / 8] |= 1 << (i % 8);
+            }
+        }
+
+        schip.assign_var(ctx, F::from_bytes_wide(&bytes))
+    }
+}
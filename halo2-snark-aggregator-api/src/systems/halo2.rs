@@ -1,8 +1,10 @@
+pub mod coherent;
 pub mod evaluation;
 pub mod expression;
 pub mod lagrange;
 pub mod lookup;
 pub mod multiopen;
+pub mod native;
 pub mod params;
 pub mod permutation;
 pub mod transcript;
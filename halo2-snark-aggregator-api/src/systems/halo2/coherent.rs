@@ -0,0 +1,106 @@
+use halo2_proofs::{arithmetic::FieldExt, plonk::ConstraintSystem};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnKind {
+    Advice,
+    Fixed,
+    Instance,
+}
+
+// Name of a commitment in an aggregation batch. `phase` only applies to advice
+// columns, and `index` is the index of the column among the columns of the same
+// kind (and phase).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommitmentRef {
+    pub circuit: usize,
+    pub proof: usize,
+    pub phase: usize,
+    pub kind: ColumnKind,
+    pub index: usize,
+}
+
+impl CommitmentRef {
+    pub fn advice(circuit: usize, proof: usize, phase: usize, index: usize) -> Self {
+        CommitmentRef {
+            circuit,
+            proof,
+            phase,
+            kind: ColumnKind::Advice,
+            index,
+        }
+    }
+
+    pub fn fixed(circuit: usize, proof: usize, index: usize) -> Self {
+        CommitmentRef {
+            circuit,
+            proof,
+            phase: 0,
+            kind: ColumnKind::Fixed,
+            index,
+        }
+    }
+
+    pub fn instance(circuit: usize, proof: usize, index: usize) -> Self {
+        CommitmentRef {
+            circuit,
+            proof,
+            phase: 0,
+            kind: ColumnKind::Instance,
+            index,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ProofCommitments<P> {
+    // advice commitments grouped by phase
+    pub advice: Vec<Vec<P>>,
+    pub fixed: Vec<P>,
+    pub instance: Vec<P>,
+}
+
+impl<P> ProofCommitments<P> {
+    pub fn get(&self, phase: usize, kind: ColumnKind, index: usize) -> Option<&P> {
+        match kind {
+            ColumnKind::Advice => self.advice.get(phase)?.get(index),
+            ColumnKind::Fixed if phase == 0 => self.fixed.get(index),
+            ColumnKind::Instance if phase == 0 => self.instance.get(index),
+            _ => None,
+        }
+    }
+}
+
+// Look up a named commitment in the commitments returned by
+// `verify_aggregation_proofs_in_chip`.
+pub fn find_commitment<'a, P>(
+    commitments: &'a [Vec<ProofCommitments<P>>],
+    commitment: &CommitmentRef,
+) -> Option<&'a P> {
+    commitments
+        .get(commitment.circuit)?
+        .get(commitment.proof)?
+        .get(commitment.phase, commitment.kind, commitment.index)
+}
+
+// Number of columns of a kind in a phase of cs, None if the circuit has no such
+// phase. Phases are numbered as in `ProofCommitments::advice`.
+pub fn column_count<F: FieldExt>(
+    cs: &ConstraintSystem<F>,
+    phase: usize,
+    kind: ColumnKind,
+) -> Option<usize> {
+    match kind {
+        ColumnKind::Advice => {
+            let phase = cs.phases().nth(phase)?;
+            Some(
+                cs.advice_column_phase
+                    .iter()
+                    .filter(|p| **p == phase)
+                    .count(),
+            )
+        }
+        ColumnKind::Fixed if phase == 0 => Some(cs.num_fixed_columns),
+        ColumnKind::Instance if phase == 0 => Some(cs.num_instance_columns),
+        _ => None,
+    }
+}
@@ -93,6 +93,9 @@ pub struct EvaluationProof<'a, A: ArithEccChip> {
 pub struct EvaluationQuery<A: ArithEccChip> {
     pub point: A::AssignedScalar,
     pub rotation: i32,
+    pub commitment_key: String,
+    pub commitment: EvaluationQuerySchema<A::AssignedPoint, A::AssignedScalar>,
+    pub eval: A::AssignedScalar,
     pub s: EvaluationQuerySchema<A::AssignedPoint, A::AssignedScalar>,
 }
 
@@ -105,24 +108,36 @@ impl<A: ArithEccChip> EvaluationQuery<A> {
         eval: A::AssignedScalar,
     ) -> Self {
         let s = CommitQuery {
-            key: commitment_key,
+            key: commitment_key.clone(),
             commitment: Some(commitment),
-            eval: Some(eval),
+            eval: Some(eval.clone()),
         };
 
         EvaluationQuery {
             point,
             rotation,
+            commitment_key,
+            commitment: EvaluationQuerySchema::Commitment(s.clone()),
+            eval,
             s: EvaluationQuerySchema::Commitment(s.clone()) + EvaluationQuerySchema::Eval(s),
         }
     }
 
     pub fn new_from_query(
         rotation: i32,
+        commitment_key: String,
         point: A::AssignedScalar,
-        s: EvaluationQuerySchema<A::AssignedPoint, A::AssignedScalar>,
+        commitment: EvaluationQuerySchema<A::AssignedPoint, A::AssignedScalar>,
+        eval: A::AssignedScalar,
     ) -> Self {
-        EvaluationQuery { rotation, point, s }
+        EvaluationQuery {
+            rotation,
+            point,
+            commitment_key,
+            s: commitment.clone() + EvaluationQuerySchema::Scalar(eval.clone()),
+            commitment,
+            eval,
+        }
     }
 }
 
@@ -202,7 +217,7 @@ impl<P: Clone, S: Clone> EvaluationQuerySchema<P, S> {
         Ok((acc, s, point_names))
     }
 
-    fn eval_prepare<
+    pub(crate) fn eval_prepare<
         Scalar: FieldExt,
         A: ArithEccChip<AssignedPoint = P, AssignedScalar = S, Scalar = Scalar>,
     >(
@@ -1,17 +1,106 @@
 use crate::{
-    arith::ecc::ArithEccChip, commit, scalar, systems::halo2::evaluation::EvaluationQuerySchema,
+    arith::{common::ArithCommonChip, ecc::ArithEccChip, field::ArithFieldChip},
+    commit, scalar,
+    systems::halo2::evaluation::EvaluationQuerySchema,
+    transcript::read::TranscriptRead,
 };
 
 use super::{
     evaluation::{CommitQuery, EvaluationProof},
     params::VerifierParams,
 };
+use halo2_proofs::plonk::Error;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultiOpenKind {
+    GWC,
+    SHPLONK,
+}
+
+impl Default for MultiOpenKind {
+    fn default() -> Self {
+        MultiOpenKind::GWC
+    }
+}
 
 pub struct MultiOpenProof<A: ArithEccChip> {
     pub w_x: EvaluationQuerySchema<A::AssignedPoint, A::AssignedScalar>,
     pub w_g: EvaluationQuerySchema<A::AssignedPoint, A::AssignedScalar>,
 }
 
+// Challenges and points of the opening argument, read after the evaluations.
+pub struct MultiOpenWitness<A: ArithEccChip> {
+    pub v: A::AssignedScalar,
+    pub u: A::AssignedScalar,
+    pub w: Vec<A::AssignedPoint>,
+    // Extra challenges of the scheme, e.g. y of shplonk.
+    pub challenges: Vec<A::AssignedScalar>,
+}
+
+// Reduce the queries of VerifierParams to the pair (w_x, w_g) checked by
+// e(w_x, s_g2) == e(w_g, g2). It is generic over the chip, so mock, circuit
+// and solidity backends share the same implementation.
+pub trait MultiOpenScheme<A: ArithEccChip> {
+    fn read_witness<T: TranscriptRead<A>>(
+        &self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+        transcript: &mut T,
+    ) -> Result<MultiOpenWitness<A>, A::Error>;
+
+    fn batch_multi_open_proofs(
+        &self,
+        params: &VerifierParams<A>,
+        ctx: &mut A::Context,
+        schip: &A::ScalarChip,
+        pchip: &A,
+    ) -> Result<MultiOpenProof<A>, A::Error>;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GWCScheme;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SHPLONKScheme;
+
+impl<A: ArithEccChip> MultiOpenScheme<A> for MultiOpenKind
+where
+    A::Error: From<Error>,
+{
+    fn read_witness<T: TranscriptRead<A>>(
+        &self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+        transcript: &mut T,
+    ) -> Result<MultiOpenWitness<A>, A::Error> {
+        match self {
+            MultiOpenKind::GWC => GWCScheme.read_witness(ctx, nchip, schip, pchip, transcript),
+            MultiOpenKind::SHPLONK => {
+                SHPLONKScheme.read_witness(ctx, nchip, schip, pchip, transcript)
+            }
+        }
+    }
+
+    fn batch_multi_open_proofs(
+        &self,
+        params: &VerifierParams<A>,
+        ctx: &mut A::Context,
+        schip: &A::ScalarChip,
+        pchip: &A,
+    ) -> Result<MultiOpenProof<A>, A::Error> {
+        match self {
+            MultiOpenKind::GWC => GWCScheme.batch_multi_open_proofs(params, ctx, schip, pchip),
+            MultiOpenKind::SHPLONK => {
+                SHPLONKScheme.batch_multi_open_proofs(params, ctx, schip, pchip)
+            }
+        }
+    }
+}
+
 impl<A: ArithEccChip> std::fmt::Display for MultiOpenProof<A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let nb_points = self.w_x.estimate(None) + self.w_g.estimate(None);
@@ -67,14 +156,41 @@ impl<A: ArithEccChip> VerifierParams<A> {
             })
             .collect()
     }
+}
+
+impl<A: ArithEccChip> MultiOpenScheme<A> for GWCScheme {
+    fn read_witness<T: TranscriptRead<A>>(
+        &self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+        transcript: &mut T,
+    ) -> Result<MultiOpenWitness<A>, A::Error> {
+        let v = transcript.squeeze_challenge_scalar(ctx, nchip, schip)?;
+
+        let mut w = vec![];
+        while let Ok(p) = transcript.read_point(ctx, nchip, schip, pchip) {
+            w.push(p);
+        }
+
+        let u = transcript.squeeze_challenge_scalar(ctx, nchip, schip)?;
+        Ok(MultiOpenWitness {
+            v,
+            u,
+            w,
+            challenges: vec![],
+        })
+    }
 
-    pub fn batch_multi_open_proofs(
+    fn batch_multi_open_proofs(
         &self,
+        params: &VerifierParams<A>,
         ctx: &mut A::Context,
         schip: &A::ScalarChip,
         pchip: &A,
     ) -> Result<MultiOpenProof<A>, A::Error> {
-        let proofs = self.get_point_schemas(ctx, schip, pchip)?;
+        let proofs = params.get_point_schemas(ctx, schip, pchip)?;
 
         let mut w_x = None;
         let mut w_g = None;
@@ -82,16 +198,16 @@ impl<A: ArithEccChip> VerifierParams<A> {
         for (i, p) in proofs.into_iter().enumerate().rev() {
             let s = &p.s;
             let w = CommitQuery {
-                key: format!("{}_w{}", self.key, i),
+                key: format!("{}_w{}", params.key, i),
                 commitment: Some(p.w.clone()),
                 eval: None,
             };
             w_x = w_x.map_or(Some(commit!(w)), |w_x| {
-                Some(scalar!(self.u) * w_x + commit!(w))
+                Some(scalar!(params.u) * w_x + commit!(w))
             });
 
             w_g = w_g.map_or(Some(scalar!(p.point) * commit!(w) + s.clone()), |w_g| {
-                Some(scalar!(self.u) * w_g + scalar!(p.point) * commit!(w) + s.clone())
+                Some(scalar!(params.u) * w_g + scalar!(p.point) * commit!(w) + s.clone())
             });
         }
 
@@ -101,3 +217,221 @@ impl<A: ArithEccChip> VerifierParams<A> {
         })
     }
 }
+
+impl<A: ArithEccChip> VerifierParams<A> {
+    // Evaluate prod_i(u - points_i).
+    fn vanishing_eval(
+        &self,
+        ctx: &mut A::Context,
+        schip: &A::ScalarChip,
+        points: &[&A::AssignedScalar],
+    ) -> Result<A::AssignedScalar, A::Error> {
+        let mut acc = self.one.clone();
+        for point in points {
+            let diff = schip.sub(ctx, &self.u, point)?;
+            acc = schip.mul(ctx, &acc, &diff)?;
+        }
+        Ok(acc)
+    }
+
+    // Evaluate the lagrange interpolation of (points, evals) at u.
+    fn interpolate_eval(
+        &self,
+        ctx: &mut A::Context,
+        schip: &A::ScalarChip,
+        points: &[&A::AssignedScalar],
+        evals: &[A::AssignedScalar],
+    ) -> Result<A::AssignedScalar, A::Error> {
+        let mut acc = self.zero.clone();
+        for (i, eval) in evals.iter().enumerate() {
+            let mut numerator = eval.clone();
+            let mut denominator = self.one.clone();
+            for (j, point) in points.iter().enumerate() {
+                if i != j {
+                    let diff = schip.sub(ctx, &self.u, point)?;
+                    numerator = schip.mul(ctx, &numerator, &diff)?;
+                    let diff = schip.sub(ctx, points[i], point)?;
+                    denominator = schip.mul(ctx, &denominator, &diff)?;
+                }
+            }
+            let term = schip.div(ctx, &numerator, &denominator)?;
+            acc = schip.add(ctx, &acc, &term)?;
+        }
+        Ok(acc)
+    }
+}
+
+impl<A: ArithEccChip> MultiOpenScheme<A> for SHPLONKScheme
+where
+    A::Error: From<Error>,
+{
+    fn read_witness<T: TranscriptRead<A>>(
+        &self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+        transcript: &mut T,
+    ) -> Result<MultiOpenWitness<A>, A::Error> {
+        let y = transcript.squeeze_challenge_scalar(ctx, nchip, schip)?;
+        let v = transcript.squeeze_challenge_scalar(ctx, nchip, schip)?;
+        let h1 = transcript.read_point(ctx, nchip, schip, pchip)?;
+        let u = transcript.squeeze_challenge_scalar(ctx, nchip, schip)?;
+        let h2 = transcript.read_point(ctx, nchip, schip, pchip)?;
+        Ok(MultiOpenWitness {
+            v,
+            u,
+            w: vec![h1, h2],
+            challenges: vec![y],
+        })
+    }
+
+    // Follow the rotation set construction of halo2 shplonk verifier. Commitments are
+    // identified by key, the same as the pointer identity used in halo2.
+    fn batch_multi_open_proofs(
+        &self,
+        params: &VerifierParams<A>,
+        ctx: &mut A::Context,
+        schip: &A::ScalarChip,
+        _pchip: &A,
+    ) -> Result<MultiOpenProof<A>, A::Error> {
+        let queries = params.queries(ctx, schip)?;
+        // A witness not read by `read_witness` of this scheme, e.g. a gwc one
+        let (y, h1, h2) = match (&params.multiopen_challenges[..], &params.w[..]) {
+            ([y], [h1, h2]) => (y, h1, h2),
+            _ => return Err(Error::Opening.into()),
+        };
+
+        let mut super_point_set: Vec<(i32, A::AssignedScalar)> = vec![];
+        let mut commitment_map: Vec<(
+            String,
+            EvaluationQuerySchema<A::AssignedPoint, A::AssignedScalar>,
+            Vec<(i32, A::AssignedScalar)>,
+        )> = vec![];
+
+        for query in queries {
+            if !super_point_set.iter().any(|(r, _)| *r == query.rotation) {
+                super_point_set.push((query.rotation, query.point.clone()));
+            }
+
+            if let Some((_, _, evals)) = commitment_map
+                .iter_mut()
+                .find(|(key, _, _)| *key == query.commitment_key)
+            {
+                if !evals.iter().any(|(r, _)| *r == query.rotation) {
+                    evals.push((query.rotation, query.eval));
+                }
+            } else {
+                commitment_map.push((
+                    query.commitment_key,
+                    query.commitment,
+                    vec![(query.rotation, query.eval)],
+                ));
+            }
+        }
+
+        let mut rotation_sets: Vec<(
+            Vec<i32>,
+            Vec<(
+                EvaluationQuerySchema<A::AssignedPoint, A::AssignedScalar>,
+                Vec<A::AssignedScalar>,
+            )>,
+        )> = vec![];
+
+        for (_, commitment, mut evals) in commitment_map {
+            evals.sort_by_key(|(rotation, _)| *rotation);
+            let (rotations, evals): (Vec<_>, Vec<_>) = evals.into_iter().unzip();
+
+            if let Some((_, commitments)) =
+                rotation_sets.iter_mut().find(|(set, _)| *set == rotations)
+            {
+                commitments.push((commitment, evals));
+            } else {
+                rotation_sets.push((rotations, vec![(commitment, evals)]));
+            }
+        }
+
+        let mut z_0 = None;
+        let mut z_0_diff_inverse = None;
+        let mut power_of_v = params.one.clone();
+        let mut outer = None;
+        let mut r_outer = params.zero.clone();
+
+        for (i, (rotations, commitments)) in rotation_sets.iter().enumerate() {
+            let points = super_point_set
+                .iter()
+                .filter(|(r, _)| rotations.contains(r))
+                .map(|(_, p)| p)
+                .collect::<Vec<_>>();
+            let diffs = super_point_set
+                .iter()
+                .filter(|(r, _)| !rotations.contains(r))
+                .map(|(_, p)| p)
+                .collect::<Vec<_>>();
+
+            let z_diff_i = params.vanishing_eval(ctx, schip, &diffs[..])?;
+            let z_diff_i = if i == 0 {
+                z_0 = Some(params.vanishing_eval(ctx, schip, &points[..])?);
+                z_0_diff_inverse = Some(schip.div(ctx, &params.one, &z_diff_i)?);
+                params.one.clone()
+            } else {
+                schip.mul(ctx, &z_diff_i, z_0_diff_inverse.as_ref().unwrap())?
+            };
+
+            // points are taken in rotation order, as the evals.
+            let points = rotations
+                .iter()
+                .map(|rotation| {
+                    &super_point_set
+                        .iter()
+                        .find(|(r, _)| r == rotation)
+                        .unwrap()
+                        .1
+                })
+                .collect::<Vec<_>>();
+
+            // Ascending powers of y and v, as the zip with powers(y) and powers(v) of the
+            // halo2 prover.
+            let mut power_of_y = params.one.clone();
+            let mut inner = None;
+            let mut r_inner = params.zero.clone();
+            for (commitment, evals) in commitments.iter() {
+                let r_eval = params.interpolate_eval(ctx, schip, &points[..], &evals[..])?;
+                r_inner = schip.mul_add(ctx, &power_of_y, &r_eval, &r_inner)?;
+                inner = match inner {
+                    None => Some(scalar!(power_of_y) * commitment.clone()),
+                    Some(inner) => Some(inner + scalar!(power_of_y) * commitment.clone()),
+                };
+                power_of_y = schip.mul(ctx, &power_of_y, y)?;
+            }
+
+            let coeff = schip.mul(ctx, &power_of_v, &z_diff_i)?;
+            r_outer = schip.mul_add(ctx, &coeff, &r_inner, &r_outer)?;
+            outer = match outer {
+                None => Some(scalar!(coeff) * inner.unwrap()),
+                Some(outer) => Some(outer + scalar!(coeff) * inner.unwrap()),
+            };
+            power_of_v = schip.mul(ctx, &power_of_v, &params.v)?;
+        }
+
+        let neg_z_0 = schip.sub(ctx, &params.zero, z_0.as_ref().unwrap())?;
+        let h1 = CommitQuery {
+            key: format!("{}_h1", params.key),
+            commitment: Some(h1.clone()),
+            eval: None,
+        };
+        let h2 = CommitQuery {
+            key: format!("{}_h2", params.key),
+            commitment: Some(h2.clone()),
+            eval: None,
+        };
+
+        Ok(MultiOpenProof {
+            w_x: commit!(h2),
+            w_g: outer.unwrap()
+                + scalar!(neg_z_0) * commit!(h1)
+                + scalar!(params.u) * commit!(h2)
+                + scalar!(r_outer),
+        })
+    }
+}
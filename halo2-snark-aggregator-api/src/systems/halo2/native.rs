@@ -0,0 +1,209 @@
+pub mod arith;
+
+pub use self::arith::{NativeCtx, NativeEccChip, NativeFieldChip};
+use super::evaluation::EvaluationQuerySchema;
+use super::multiopen::{MultiOpenKind, MultiOpenProof};
+use super::transcript::{TargetTranscriptRead, TranscriptKind};
+use super::verify::verify_single_proof_no_eval;
+use crate::hash::poseidon::TranscriptSpec;
+use crate::mock::transcript_encode::{BitsEncode, PoseidonEncode};
+use crate::scalar;
+use crate::transcript::read::TranscriptRead;
+use group::{prime::PrimeCurveAffine, Curve};
+use halo2_proofs::arithmetic::{best_multiexp, CurveAffine, Field};
+use halo2_proofs::plonk::{Error, VerifyingKey};
+use halo2_proofs::poly::commitment::Params;
+use halo2_proofs::poly::kzg::commitment::ParamsVerifierKZG;
+use halo2curves::pairing::MultiMillerLoop;
+use rayon::prelude::*;
+use std::fmt::Debug;
+
+// Out-of-circuit counterpart of `verify_aggregation_proofs_in_chip`. The queries are
+// built by the generic verifier on the native chips, each proof is read on its own
+// thread and all the commitments of a side are summed by a single multiexp.
+
+type NativePoint<C> = <C as CurveAffine>::CurveExt;
+
+pub struct NativeProofData<'a, E: MultiMillerLoop> {
+    pub instances: &'a Vec<Vec<Vec<E::Scalar>>>,
+    pub transcript: &'a [u8],
+    pub key: String,
+    // Selector bit of a padding slot, see `ProofData::enabled`.
+    pub enabled: Option<bool>,
+}
+
+pub struct NativeCircuitProof<'a, E: MultiMillerLoop> {
+    pub name: String,
+    pub vk: &'a VerifyingKey<E::G1Affine>,
+    pub params: &'a ParamsVerifierKZG<E>,
+    pub proofs: Vec<NativeProofData<'a, E>>,
+    pub scheme: MultiOpenKind,
+    pub transcript_kind: TranscriptKind,
+    pub poseidon_spec: TranscriptSpec,
+}
+
+struct NativeProof<C: CurveAffine> {
+    proof: MultiOpenProof<NativeEccChip<C>>,
+    instances: Vec<C::ScalarExt>,
+    // challenge the aggregation transcript absorbs for this proof
+    challenge: C::ScalarExt,
+}
+
+fn instance_commitments<E: MultiMillerLoop>(
+    instances: &[Vec<Vec<E::Scalar>>],
+    vk: &VerifyingKey<E::G1Affine>,
+    params: &ParamsVerifierKZG<E>,
+) -> Result<Vec<Vec<NativePoint<E::G1Affine>>>, Error> {
+    instances
+        .iter()
+        .map(|instance| {
+            if params.k() != vk.get_domain().k() || instance.len() != vk.cs().num_instance_columns {
+                return Err(Error::InvalidInstances);
+            }
+            instance
+                .iter()
+                .map(|column| {
+                    if column.len() > params.n() as usize - (vk.cs().blinding_factors() + 1) {
+                        return Err(Error::InvalidInstances);
+                    }
+                    Ok(best_multiexp(column, &params.g_lagrange[..column.len()]))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn verify_proof_native<E: MultiMillerLoop + Debug>(
+    circuit: &NativeCircuitProof<E>,
+    proof: &NativeProofData<E>,
+) -> Result<NativeProof<E::G1Affine>, Error> {
+    let nchip = NativeFieldChip::<E::Scalar>::default();
+    let schip = NativeFieldChip::<E::Scalar>::default();
+    let pchip = NativeEccChip::<E::G1Affine>::default();
+    let ctx = &mut NativeCtx::default();
+
+    let mut transcript =
+        TargetTranscriptRead::<_, E::G1Affine, _, PoseidonEncode, BitsEncode>::new(
+            circuit.transcript_kind,
+            proof.transcript,
+            ctx,
+            &nchip,
+            &circuit.poseidon_spec,
+        )?;
+
+    let (multiopen_proof, _) = verify_single_proof_no_eval(
+        ctx,
+        &nchip,
+        &schip,
+        &pchip,
+        instance_commitments(proof.instances, circuit.vk, circuit.params)?,
+        circuit.vk,
+        circuit.params,
+        &mut transcript,
+        proof.key.clone(),
+        &circuit.scheme,
+    )?;
+    let challenge = transcript.squeeze_challenge_scalar(ctx, &nchip, &schip)?;
+
+    let instances = proof.instances.iter().flatten().flatten().cloned();
+    Ok(match proof.enabled {
+        Some(enabled) => {
+            let enabled = E::Scalar::from(enabled as u64);
+            NativeProof {
+                proof: MultiOpenProof {
+                    w_x: multiopen_proof.w_x * scalar!(enabled),
+                    w_g: multiopen_proof.w_g * scalar!(enabled),
+                },
+                instances: instances.map(|instance| instance * enabled).collect(),
+                challenge,
+            }
+        }
+        None => NativeProof {
+            proof: multiopen_proof,
+            instances: instances.collect(),
+            challenge,
+        },
+    })
+}
+
+// Same value as `EvaluationQuerySchema::eval`, plus `eval_sign * eval * G`, with the
+// generator folded into the multiexp.
+fn eval_native<C: CurveAffine>(
+    ctx: &mut NativeCtx,
+    schip: &NativeFieldChip<C::ScalarExt>,
+    schema: EvaluationQuerySchema<NativePoint<C>, C::ScalarExt>,
+    eval_sign: C::ScalarExt,
+) -> Result<NativePoint<C>, Error> {
+    let one = C::ScalarExt::one();
+    let mut points = vec![];
+    let mut scalars = vec![];
+    for (_, p, s) in schema.eval_prepare::<_, NativeEccChip<C>>(ctx, schip, &one, None)? {
+        let (p, s) = match (p, s) {
+            (Some(p), Some(s)) => (p, s),
+            (Some(p), None) => (p, one),
+            (None, Some(eval)) => (C::generator().to_curve(), eval * eval_sign),
+            (None, None) => return Err(Error::Synthesis),
+        };
+        points.push(p);
+        scalars.push(s);
+    }
+
+    let mut bases = vec![C::identity(); points.len()];
+    NativePoint::<C>::batch_normalize(&points, &mut bases);
+    Ok(best_multiexp(&scalars, &bases))
+}
+
+// As for `verify_aggregation_proofs_in_chip`, the caller checks that the params come
+// from the same SRS.
+pub fn verify_aggregation_proofs_parallel<
+    E: MultiMillerLoop + Debug,
+    T: TranscriptRead<NativeEccChip<E::G1Affine>>,
+>(
+    ctx: &mut NativeCtx,
+    circuits: &[NativeCircuitProof<E>],
+    transcript: &mut T,
+) -> Result<
+    (
+        NativePoint<E::G1Affine>, // w_x
+        NativePoint<E::G1Affine>, // w_g
+        Vec<E::Scalar>,           // plain instance
+    ),
+    Error,
+> {
+    let nchip = NativeFieldChip::<E::Scalar>::default();
+    let schip = NativeFieldChip::<E::Scalar>::default();
+
+    let proofs = circuits
+        .iter()
+        .flat_map(|circuit| circuit.proofs.iter().map(move |proof| (circuit, proof)))
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|(circuit, proof)| verify_proof_native(circuit, proof))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    for proof in proofs.iter() {
+        transcript.common_scalar(ctx, &nchip, &schip, &proof.challenge)?;
+    }
+    let aggregation_challenge = transcript.squeeze_challenge_scalar(ctx, &nchip, &schip)?;
+
+    let mut acc: Option<MultiOpenProof<NativeEccChip<E::G1Affine>>> = None;
+    let mut instances = vec![];
+    for proof in proofs.into_iter() {
+        acc = match acc {
+            None => Some(proof.proof),
+            Some(acc) => Some(MultiOpenProof {
+                w_x: acc.w_x * scalar!(aggregation_challenge) + proof.proof.w_x,
+                w_g: acc.w_g * scalar!(aggregation_challenge) + proof.proof.w_g,
+            }),
+        };
+        instances.extend(proof.instances);
+    }
+    // No proof to aggregate, as in `verify_aggregation_proofs_in_chip`
+    let aggregated_proof = acc.ok_or(Error::Synthesis)?;
+
+    let one = E::Scalar::one();
+    let w_x = eval_native::<E::G1Affine>(ctx, &schip, aggregated_proof.w_x, one)?;
+    let w_g = eval_native::<E::G1Affine>(ctx, &schip, aggregated_proof.w_g, -one)?;
+
+    Ok((w_x, w_g, instances))
+}
@@ -0,0 +1,225 @@
+use crate::arith::{
+    bits::ArithBitsChip, common::ArithCommonChip, ecc::ArithEccChip, field::ArithFieldChip,
+};
+use group::{Curve, Group};
+use halo2_proofs::arithmetic::{best_multiexp, CurveAffine, FieldExt};
+use halo2_proofs::plonk::Error;
+use std::marker::PhantomData;
+
+// Chips of the native verifier: values are plain field elements and curve points,
+// nothing is assigned or recorded, and a multiexp is a single `best_multiexp`. Unlike
+// the mock chips they fail with `Error::Synthesis` instead of panicking on a division
+// by zero or an out of range value.
+
+#[derive(Default, Clone, Debug)]
+pub struct NativeCtx;
+
+impl std::fmt::Display for NativeCtx {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(native)")
+    }
+}
+
+pub struct NativeFieldChip<F: FieldExt>(PhantomData<F>);
+
+impl<F: FieldExt> Default for NativeFieldChip<F> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<F: FieldExt> ArithCommonChip for NativeFieldChip<F> {
+    type Context = NativeCtx;
+    type Value = F;
+    type AssignedValue = F;
+    type Error = Error;
+
+    fn add(&self, _ctx: &mut NativeCtx, a: &F, b: &F) -> Result<F, Error> {
+        Ok(*a + *b)
+    }
+
+    fn sub(&self, _ctx: &mut NativeCtx, a: &F, b: &F) -> Result<F, Error> {
+        Ok(*a - *b)
+    }
+
+    fn assign_zero(&self, _ctx: &mut NativeCtx) -> Result<F, Error> {
+        Ok(F::zero())
+    }
+
+    fn assign_one(&self, _ctx: &mut NativeCtx) -> Result<F, Error> {
+        Ok(F::one())
+    }
+
+    fn assign_const(&self, _ctx: &mut NativeCtx, c: F) -> Result<F, Error> {
+        Ok(c)
+    }
+
+    fn assign_var(&self, _ctx: &mut NativeCtx, v: F) -> Result<F, Error> {
+        Ok(v)
+    }
+
+    fn to_value(&self, v: &F) -> Result<F, Error> {
+        Ok(*v)
+    }
+
+    fn normalize(&self, _ctx: &mut NativeCtx, v: &F) -> Result<F, Error> {
+        Ok(*v)
+    }
+}
+
+impl<F: FieldExt> ArithFieldChip for NativeFieldChip<F> {
+    type Field = F;
+    type AssignedField = F;
+
+    fn mul(&self, _ctx: &mut NativeCtx, a: &F, b: &F) -> Result<F, Error> {
+        Ok(*a * *b)
+    }
+
+    fn div(&self, _ctx: &mut NativeCtx, a: &F, b: &F) -> Result<F, Error> {
+        Option::from(b.invert())
+            .map(|b_inv: F| *a * b_inv)
+            .ok_or(Error::Synthesis)
+    }
+
+    fn square(&self, _ctx: &mut NativeCtx, a: &F) -> Result<F, Error> {
+        Ok(a.square())
+    }
+
+    fn sum_with_coeff_and_constant(
+        &self,
+        _ctx: &mut NativeCtx,
+        a_with_coeff: Vec<(&F, F)>,
+        b: F,
+    ) -> Result<F, Error> {
+        Ok(a_with_coeff
+            .into_iter()
+            .fold(b, |acc, (x, coeff)| acc + *x * coeff))
+    }
+
+    fn mul_add_constant(&self, _ctx: &mut NativeCtx, a: &F, b: &F, c: F) -> Result<F, Error> {
+        Ok(*a * *b + c)
+    }
+}
+
+impl<F: FieldExt> ArithBitsChip for NativeFieldChip<F> {
+    fn to_bits(&self, _ctx: &mut NativeCtx, a: &F, bits: usize) -> Result<Vec<F>, Error> {
+        let mut res = a
+            .to_repr()
+            .as_ref()
+            .iter()
+            .flat_map(|byte| (0..8).map(move |i| F::from(((byte >> i) & 1) as u64)))
+            .collect::<Vec<_>>();
+        if res[bits..].iter().any(|bit| *bit != F::zero()) {
+            return Err(Error::Synthesis);
+        }
+
+        res.truncate(bits);
+        Ok(res)
+    }
+
+    fn to_bits_canonical(&self, ctx: &mut NativeCtx, a: &F) -> Result<Vec<F>, Error> {
+        self.to_bits(ctx, a, F::NUM_BITS as usize)
+    }
+}
+
+pub struct NativeEccChip<C: CurveAffine>(PhantomData<C>);
+
+impl<C: CurveAffine> Default for NativeEccChip<C> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: CurveAffine> ArithCommonChip for NativeEccChip<C> {
+    type Context = NativeCtx;
+    type Value = C;
+    type AssignedValue = C::CurveExt;
+    type Error = Error;
+
+    fn add(
+        &self,
+        _ctx: &mut NativeCtx,
+        a: &C::CurveExt,
+        b: &C::CurveExt,
+    ) -> Result<C::CurveExt, Error> {
+        Ok(*a + *b)
+    }
+
+    fn sub(
+        &self,
+        _ctx: &mut NativeCtx,
+        a: &C::CurveExt,
+        b: &C::CurveExt,
+    ) -> Result<C::CurveExt, Error> {
+        Ok(*a - *b)
+    }
+
+    fn assign_zero(&self, _ctx: &mut NativeCtx) -> Result<C::CurveExt, Error> {
+        Ok(C::CurveExt::identity())
+    }
+
+    fn assign_one(&self, _ctx: &mut NativeCtx) -> Result<C::CurveExt, Error> {
+        Ok(C::CurveExt::generator())
+    }
+
+    fn assign_const(&self, _ctx: &mut NativeCtx, c: C) -> Result<C::CurveExt, Error> {
+        Ok(c.to_curve())
+    }
+
+    fn assign_var(&self, _ctx: &mut NativeCtx, v: C) -> Result<C::CurveExt, Error> {
+        Ok(v.to_curve())
+    }
+
+    fn to_value(&self, v: &C::CurveExt) -> Result<C, Error> {
+        Ok(v.to_affine())
+    }
+
+    fn normalize(&self, _ctx: &mut NativeCtx, v: &C::CurveExt) -> Result<C::CurveExt, Error> {
+        Ok(*v)
+    }
+}
+
+impl<C: CurveAffine> ArithEccChip for NativeEccChip<C> {
+    type Point = C;
+    type AssignedPoint = C::CurveExt;
+    type Scalar = C::ScalarExt;
+    type AssignedScalar = C::ScalarExt;
+    type Native = C::ScalarExt;
+    type AssignedNative = C::ScalarExt;
+
+    type ScalarChip = NativeFieldChip<C::ScalarExt>;
+    type NativeChip = NativeFieldChip<C::ScalarExt>;
+
+    fn scalar_mul(
+        &self,
+        _ctx: &mut NativeCtx,
+        lhs: &C::ScalarExt,
+        rhs: &C::CurveExt,
+    ) -> Result<C::CurveExt, Error> {
+        Ok(*rhs * *lhs)
+    }
+
+    fn scalar_mul_constant(
+        &self,
+        _ctx: &mut NativeCtx,
+        lhs: &C::ScalarExt,
+        rhs: C,
+    ) -> Result<C::CurveExt, Error> {
+        Ok(rhs * *lhs)
+    }
+
+    fn multi_exp(
+        &self,
+        _ctx: &mut NativeCtx,
+        points: Vec<C::CurveExt>,
+        scalars: Vec<C::ScalarExt>,
+    ) -> Result<C::CurveExt, Error> {
+        if points.is_empty() || points.len() != scalars.len() {
+            return Err(Error::Synthesis);
+        }
+
+        let mut bases = vec![C::identity(); points.len()];
+        C::CurveExt::batch_normalize(&points, &mut bases);
+        Ok(best_multiexp(&scalars, &bases))
+    }
+}
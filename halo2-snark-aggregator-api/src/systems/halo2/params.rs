@@ -20,7 +20,7 @@ pub struct VerifierParams<A: ArithEccChip> {
     pub instance_commitments: Vec<Vec<A::AssignedPoint>>,
     pub instance_evals: Vec<Vec<A::AssignedScalar>>,
     pub instance_queries: Vec<(usize, i32)>,
-    //pub challenges: Vec<Vec<A::AssignedScalar>>,
+    // shared by all proofs, indexed by `Challenge::index()`
     pub challenges: Vec<A::AssignedScalar>,
     pub advice_commitments: Vec<Vec<A::AssignedPoint>>,
     pub advice_evals: Vec<Vec<A::AssignedScalar>>,
@@ -33,6 +33,7 @@ pub struct VerifierParams<A: ArithEccChip> {
     pub vanish_commitments: Vec<A::AssignedPoint>,
     pub random_commitment: A::AssignedPoint,
     pub w: Vec<A::AssignedPoint>,
+    pub multiopen_challenges: Vec<A::AssignedScalar>,
     pub random_eval: A::AssignedScalar,
     pub beta: A::AssignedScalar,
     pub gamma: A::AssignedScalar,
@@ -1,10 +1,33 @@
+#[cfg(feature = "blake2b")]
+pub mod blake2b;
+#[cfg(feature = "keccak")]
+pub mod keccak;
+
+#[cfg(feature = "blake2b")]
+use self::blake2b::Blake2bTranscriptRead;
+#[cfg(feature = "keccak")]
+use self::keccak::KeccakTranscriptRead;
 use crate::{
-    arith::{common::ArithCommonChip, ecc::ArithEccChip},
-    hash::poseidon::PoseidonChip,
-    transcript::{encode::Encode, read::TranscriptRead},
+    arith::{bits::ArithBitsChip, common::ArithCommonChip, ecc::ArithEccChip},
+    hash::poseidon::{PoseidonChip, PoseidonSpec, TranscriptSpec, TRANSCRIPT_RATE, TRANSCRIPT_T},
+    mock::{
+        arith::{
+            ecc::MockEccChip,
+            field::{MockChipCtx, MockFieldChip},
+        },
+        transcript_encode::PoseidonEncode,
+    },
+    transcript::{
+        encode::{Encode, EncodeBits},
+        read::TranscriptRead,
+    },
 };
 use group::ff::PrimeField;
-use halo2_proofs::{arithmetic::CurveAffine, plonk::Error};
+use halo2_proofs::{
+    arithmetic::CurveAffine,
+    plonk::Error,
+    transcript::{Challenge255, PoseidonRead, Transcript, TranscriptRead as _},
+};
 use std::{io, marker::PhantomData};
 
 pub struct PoseidonTranscriptRead<
@@ -20,6 +43,10 @@ pub struct PoseidonTranscriptRead<
     _phantom: PhantomData<E>,
 }
 
+// `PoseidonTranscriptRead` with the width and rate of `TranscriptSpec`.
+pub type TranscriptSpecRead<R, C, A, E> =
+    PoseidonTranscriptRead<R, C, A, E, TRANSCRIPT_T, TRANSCRIPT_RATE>;
+
 impl<
         R: io::Read,
         C: CurveAffine,
@@ -42,6 +69,49 @@ impl<
             _phantom: PhantomData,
         })
     }
+
+    pub fn from_spec(
+        reader: R,
+        ctx: &mut A::Context,
+        schip: &A::NativeChip,
+        spec: &PoseidonSpec<T, RATE>,
+    ) -> Result<PoseidonTranscriptRead<R, C, A, E, T, RATE>, A::Error> {
+        Self::new(reader, ctx, schip, spec.r_f, spec.r_p)
+    }
+}
+
+// Squeezes a challenge after the first commitment of `proof`, both with halo2's
+// `PoseidonRead` and with a transcript built from `spec`. They only agree if `spec` is
+// the one the proof was created with. Only `r_f` and `r_p` are checked, the width and
+// the rate of `TranscriptSpec` are the ones of `PoseidonRead`.
+pub fn check_transcript_spec<C: CurveAffine>(
+    proof: &[u8],
+    spec: &TranscriptSpec,
+) -> Result<(), Error> {
+    let mut expected = PoseidonRead::<_, C, Challenge255<C>>::init(proof);
+    expected.read_point()?;
+    let expected: C::ScalarExt = *expected.squeeze_challenge_scalar::<()>();
+
+    let nchip = MockFieldChip::<C::ScalarExt, Error>::default();
+    let pchip = MockEccChip::<C, Error>::default();
+    let ctx = &mut MockChipCtx::default();
+
+    let mut transcript =
+        TranscriptSpecRead::<_, C, _, PoseidonEncode>::from_spec(proof, ctx, &nchip, spec)?;
+    transcript.read_point(ctx, &nchip, &nchip, &pchip)?;
+    let challenge = transcript.squeeze_challenge_scalar(ctx, &nchip, &nchip)?;
+
+    if nchip.to_value(&challenge)? != expected {
+        return Err(Error::Transcript(io::Error::new(
+            io::ErrorKind::Other,
+            format!(
+                "poseidon spec {:?} doesn't match the transcript of the proof",
+                spec
+            ),
+        )));
+    }
+
+    Ok(())
 }
 
 impl<
@@ -177,3 +247,177 @@ impl<
         Ok(())
     }
 }
+
+// Transcript the target proofs of a circuit are created with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TranscriptKind {
+    Poseidon,
+    // halo2's `Blake2bWrite`, needs the `blake2b` feature.
+    Blake2b,
+    // `Keccak256Write`, needs the `keccak` feature.
+    Keccak256,
+}
+
+impl Default for TranscriptKind {
+    fn default() -> Self {
+        TranscriptKind::Poseidon
+    }
+}
+
+impl TranscriptKind {
+    // Whether the in-circuit reader of the kind is built.
+    pub fn is_supported(&self) -> bool {
+        match self {
+            TranscriptKind::Poseidon => true,
+            TranscriptKind::Blake2b => cfg!(feature = "blake2b"),
+            TranscriptKind::Keccak256 => cfg!(feature = "keccak"),
+        }
+    }
+}
+
+// Reader of a target proof with the transcript of its circuit. `E` encodes proof
+// elements for poseidon and `EB` for the transcripts hashing bytes.
+pub enum TargetTranscriptRead<
+    R: io::Read,
+    C: CurveAffine,
+    A: ArithEccChip<Point = C, Scalar = C::Scalar>,
+    E: Encode<A>,
+    EB: EncodeBits<A>,
+> where
+    A::NativeChip: ArithBitsChip,
+{
+    Poseidon(TranscriptSpecRead<R, C, A, E>, PhantomData<EB>),
+    #[cfg(feature = "blake2b")]
+    Blake2b(Blake2bTranscriptRead<R, C, A, EB>),
+    #[cfg(feature = "keccak")]
+    Keccak256(KeccakTranscriptRead<R, C, A, EB>),
+}
+
+impl<
+        R: io::Read,
+        C: CurveAffine,
+        A: ArithEccChip<Point = C, Scalar = C::Scalar, Error = Error>,
+        E: Encode<A>,
+        EB: EncodeBits<A>,
+    > TargetTranscriptRead<R, C, A, E, EB>
+where
+    A::NativeChip: ArithBitsChip,
+{
+    // `spec` is only used by the poseidon transcript.
+    pub fn new(
+        kind: TranscriptKind,
+        reader: R,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        spec: &TranscriptSpec,
+    ) -> Result<Self, Error> {
+        match kind {
+            TranscriptKind::Poseidon => Ok(TargetTranscriptRead::Poseidon(
+                TranscriptSpecRead::from_spec(reader, ctx, nchip, spec)?,
+                PhantomData,
+            )),
+            #[cfg(feature = "blake2b")]
+            TranscriptKind::Blake2b => Ok(TargetTranscriptRead::Blake2b(
+                Blake2bTranscriptRead::new(reader, ctx, nchip)?,
+            )),
+            #[cfg(feature = "keccak")]
+            TranscriptKind::Keccak256 => Ok(TargetTranscriptRead::Keccak256(
+                KeccakTranscriptRead::new(reader),
+            )),
+            #[allow(unreachable_patterns)]
+            kind => Err(Error::Transcript(io::Error::new(
+                io::ErrorKind::Other,
+                format!("{:?} transcript is not enabled in this build", kind),
+            ))),
+        }
+    }
+
+    fn reader(&mut self) -> &mut dyn TranscriptRead<A> {
+        match self {
+            TargetTranscriptRead::Poseidon(transcript, _) => transcript,
+            #[cfg(feature = "blake2b")]
+            TargetTranscriptRead::Blake2b(transcript) => transcript,
+            #[cfg(feature = "keccak")]
+            TargetTranscriptRead::Keccak256(transcript) => transcript,
+        }
+    }
+}
+
+impl<
+        R: io::Read,
+        C: CurveAffine,
+        A: ArithEccChip<Point = C, Scalar = C::Scalar, Error = Error>,
+        E: Encode<A>,
+        EB: EncodeBits<A>,
+    > TranscriptRead<A> for TargetTranscriptRead<R, C, A, E, EB>
+where
+    A::NativeChip: ArithBitsChip,
+{
+    fn read_point(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+    ) -> Result<A::AssignedPoint, A::Error> {
+        self.reader().read_point(ctx, nchip, schip, pchip)
+    }
+
+    fn read_constant_point(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+    ) -> Result<A::AssignedPoint, A::Error> {
+        self.reader().read_constant_point(ctx, nchip, schip, pchip)
+    }
+
+    fn read_scalar(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+    ) -> Result<A::AssignedScalar, A::Error> {
+        self.reader().read_scalar(ctx, nchip, schip)
+    }
+
+    fn read_constant_scalar(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+    ) -> Result<A::AssignedScalar, A::Error> {
+        self.reader().read_constant_scalar(ctx, nchip, schip)
+    }
+
+    fn squeeze_challenge_scalar(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+    ) -> Result<A::AssignedScalar, A::Error> {
+        self.reader().squeeze_challenge_scalar(ctx, nchip, schip)
+    }
+
+    fn common_point(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+        p: &A::AssignedPoint,
+    ) -> Result<(), A::Error> {
+        self.reader().common_point(ctx, nchip, schip, pchip, p)
+    }
+
+    fn common_scalar(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        s: &A::AssignedScalar,
+    ) -> Result<(), A::Error> {
+        self.reader().common_scalar(ctx, nchip, schip, s)
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{
+    arith::{bits::ArithBitsChip, common::ArithCommonChip, ecc::ArithEccChip},
+    hash::blake2b::Blake2bChip,
+    transcript::{encode::EncodeBits, read::TranscriptRead},
+};
+use group::ff::PrimeField;
+use halo2_proofs::{arithmetic::CurveAffine, plonk::Error};
+use std::{io, marker::PhantomData};
+
+// Same as halo2's `Blake2bRead`.
+const BLAKE2B_PERSONAL: &[u8; 16] = b"Halo2-Transcript";
+const BLAKE2B_PREFIX_CHALLENGE: u8 = 0;
+const BLAKE2B_PREFIX_POINT: u8 = 1;
+const BLAKE2B_PREFIX_SCALAR: u8 = 2;
+
+pub struct Blake2bTranscriptRead<
+    R: io::Read,
+    C: CurveAffine,
+    A: ArithEccChip<Point = C, Scalar = C::Scalar>,
+    E: EncodeBits<A>,
+> where
+    A::NativeChip: ArithBitsChip,
+{
+    hash: Blake2bChip<A::NativeChip>,
+    reader: R,
+    _phantom: PhantomData<E>,
+}
+
+impl<
+        R: io::Read,
+        C: CurveAffine,
+        A: ArithEccChip<Point = C, Scalar = C::Scalar>,
+        E: EncodeBits<A>,
+    > Blake2bTranscriptRead<R, C, A, E>
+where
+    A::NativeChip: ArithBitsChip,
+{
+    pub fn new(
+        reader: R,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+    ) -> Result<Blake2bTranscriptRead<R, C, A, E>, A::Error> {
+        Ok(Blake2bTranscriptRead {
+            hash: Blake2bChip::new(ctx, nchip, BLAKE2B_PERSONAL)?,
+            reader,
+            _phantom: PhantomData,
+        })
+    }
+
+    fn update_prefix(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        prefix: u8,
+    ) -> Result<(), A::Error> {
+        let bits = nchip.assign_const_bits(ctx, &[prefix])?;
+        self.hash.update(&bits);
+        Ok(())
+    }
+}
+
+impl<
+        R: io::Read,
+        C: CurveAffine,
+        A: ArithEccChip<Point = C, Scalar = C::Scalar, Error = Error>,
+        E: EncodeBits<A>,
+    > TranscriptRead<A> for Blake2bTranscriptRead<R, C, A, E>
+where
+    A::NativeChip: ArithBitsChip,
+{
+    fn read_point(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+    ) -> Result<A::AssignedPoint, A::Error> {
+        let mut compressed = C::Repr::default();
+        self.reader.read_exact(compressed.as_mut())?;
+        let point: C = Option::from(C::from_bytes(&compressed)).ok_or_else(|| {
+            Error::Transcript(io::Error::new(
+                io::ErrorKind::Other,
+                "invalid point encoding in proof",
+            ))
+        })?;
+        let assigned_point = pchip.assign_var(ctx, point)?;
+
+        self.common_point(ctx, nchip, schip, pchip, &assigned_point)?;
+
+        Ok(assigned_point)
+    }
+
+    fn read_constant_point(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+    ) -> Result<A::AssignedPoint, A::Error> {
+        let mut compressed = C::Repr::default();
+        self.reader.read_exact(compressed.as_mut())?;
+        let point: C = Option::from(C::from_bytes(&compressed)).ok_or_else(|| {
+            Error::Transcript(io::Error::new(
+                io::ErrorKind::Other,
+                "invalid point encoding in proof",
+            ))
+        })?;
+        let assigned_point = pchip.assign_const(ctx, point)?;
+
+        self.common_point(ctx, nchip, schip, pchip, &assigned_point)?;
+
+        Ok(assigned_point)
+    }
+
+    fn read_scalar(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+    ) -> Result<A::AssignedScalar, A::Error> {
+        let mut data = <C::Scalar as PrimeField>::Repr::default();
+        self.reader.read_exact(data.as_mut())?;
+        let scalar: C::Scalar = Option::from(C::Scalar::from_repr(data)).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "invalid field element encoding in proof",
+            )
+        })?;
+        let assigned_scalar = schip.assign_var(ctx, scalar)?;
+
+        self.common_scalar(ctx, nchip, schip, &assigned_scalar)?;
+
+        Ok(assigned_scalar)
+    }
+
+    fn read_constant_scalar(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+    ) -> Result<A::AssignedScalar, A::Error> {
+        let mut data = <C::Scalar as PrimeField>::Repr::default();
+        self.reader.read_exact(data.as_mut())?;
+        let scalar: C::Scalar = Option::from(C::Scalar::from_repr(data)).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "invalid field element encoding in proof",
+            )
+        })?;
+        let assigned_scalar = schip.assign_const(ctx, scalar)?;
+
+        self.common_scalar(ctx, nchip, schip, &assigned_scalar)?;
+
+        Ok(assigned_scalar)
+    }
+
+    fn squeeze_challenge_scalar(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+    ) -> Result<A::AssignedScalar, A::Error> {
+        self.update_prefix(ctx, nchip, BLAKE2B_PREFIX_CHALLENGE)?;
+        let digest = self.hash.squeeze(ctx, nchip)?;
+        E::decode_scalar_bits(ctx, nchip, schip, &digest)
+    }
+
+    fn common_point(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+        p: &A::AssignedPoint,
+    ) -> Result<(), A::Error> {
+        let (x, y) = E::encode_point_bits(ctx, nchip, schip, pchip, p)?;
+
+        self.update_prefix(ctx, nchip, BLAKE2B_PREFIX_POINT)?;
+        self.hash.update(&x);
+        self.hash.update(&y);
+        Ok(())
+    }
+
+    fn common_scalar(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        s: &A::AssignedScalar,
+    ) -> Result<(), A::Error> {
+        let encoded = E::encode_scalar_bits(ctx, nchip, schip, s)?;
+
+        self.update_prefix(ctx, nchip, BLAKE2B_PREFIX_SCALAR)?;
+        self.hash.update(&encoded);
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{
+    arith::{bits::ArithBitsChip, common::ArithCommonChip, ecc::ArithEccChip},
+    hash::keccak::KeccakChip,
+    transcript::{encode::EncodeBits, read::TranscriptRead},
+};
+use group::ff::PrimeField;
+use halo2_proofs::{arithmetic::CurveAffine, plonk::Error};
+use std::{io, marker::PhantomData};
+
+// Same as `Keccak256Read`, the transcript of halo2 target proofs, not the `ShaRead`
+// framing of `ShaKeccakRead`.
+const KECCAK256_PREFIX_CHALLENGE: u8 = 0;
+const KECCAK256_PREFIX_CHALLENGE_LO: u8 = 10;
+const KECCAK256_PREFIX_CHALLENGE_HI: u8 = 11;
+const KECCAK256_PREFIX_POINT: u8 = 1;
+const KECCAK256_PREFIX_SCALAR: u8 = 2;
+
+pub struct KeccakTranscriptRead<
+    R: io::Read,
+    C: CurveAffine,
+    A: ArithEccChip<Point = C, Scalar = C::Scalar>,
+    E: EncodeBits<A>,
+> where
+    A::NativeChip: ArithBitsChip,
+{
+    hash: KeccakChip<A::NativeChip>,
+    reader: R,
+    _phantom: PhantomData<E>,
+}
+
+impl<
+        R: io::Read,
+        C: CurveAffine,
+        A: ArithEccChip<Point = C, Scalar = C::Scalar>,
+        E: EncodeBits<A>,
+    > KeccakTranscriptRead<R, C, A, E>
+where
+    A::NativeChip: ArithBitsChip,
+{
+    pub fn new(reader: R) -> KeccakTranscriptRead<R, C, A, E> {
+        KeccakTranscriptRead {
+            hash: KeccakChip::new(),
+            reader,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn read_point_value(&mut self) -> Result<C, Error> {
+        let mut compressed = C::Repr::default();
+        self.reader.read_exact(compressed.as_mut())?;
+        Option::from(C::from_bytes(&compressed)).ok_or_else(|| {
+            Error::Transcript(io::Error::new(
+                io::ErrorKind::Other,
+                "invalid point encoding in proof",
+            ))
+        })
+    }
+
+    fn update_prefix(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        prefix: u8,
+    ) -> Result<(), A::Error> {
+        let bits = nchip.assign_const_bits(ctx, &[prefix])?;
+        self.hash.update(&bits);
+        Ok(())
+    }
+
+    // Digest of the state followed by `prefix`, which is not absorbed.
+    fn squeeze_half(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        prefix: u8,
+    ) -> Result<Vec<A::AssignedNative>, A::Error> {
+        let bits = nchip.assign_const_bits(ctx, &[prefix])?;
+        self.hash.squeeze_with_suffix(ctx, nchip, &bits)
+    }
+}
+
+impl<
+        R: io::Read,
+        C: CurveAffine,
+        A: ArithEccChip<Point = C, Scalar = C::Scalar, Error = Error>,
+        E: EncodeBits<A>,
+    > TranscriptRead<A> for KeccakTranscriptRead<R, C, A, E>
+where
+    A::NativeChip: ArithBitsChip,
+{
+    fn read_point(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+    ) -> Result<A::AssignedPoint, A::Error> {
+        let point = self.read_point_value()?;
+        let assigned_point = pchip.assign_var(ctx, point)?;
+
+        self.common_point(ctx, nchip, schip, pchip, &assigned_point)?;
+
+        Ok(assigned_point)
+    }
+
+    fn read_constant_point(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+    ) -> Result<A::AssignedPoint, A::Error> {
+        let point = self.read_point_value()?;
+        let assigned_point = pchip.assign_const(ctx, point)?;
+
+        self.common_point(ctx, nchip, schip, pchip, &assigned_point)?;
+
+        Ok(assigned_point)
+    }
+
+    fn read_scalar(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+    ) -> Result<A::AssignedScalar, A::Error> {
+        let mut data = <C::Scalar as PrimeField>::Repr::default();
+        self.reader.read_exact(data.as_mut())?;
+        let scalar: C::Scalar = Option::from(C::Scalar::from_repr(data)).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "invalid field element encoding in proof",
+            )
+        })?;
+        let assigned_scalar = schip.assign_var(ctx, scalar)?;
+
+        self.common_scalar(ctx, nchip, schip, &assigned_scalar)?;
+
+        Ok(assigned_scalar)
+    }
+
+    fn read_constant_scalar(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+    ) -> Result<A::AssignedScalar, A::Error> {
+        let mut data = <C::Scalar as PrimeField>::Repr::default();
+        self.reader.read_exact(data.as_mut())?;
+        let scalar: C::Scalar = Option::from(C::Scalar::from_repr(data)).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "invalid field element encoding in proof",
+            )
+        })?;
+        let assigned_scalar = schip.assign_const(ctx, scalar)?;
+
+        self.common_scalar(ctx, nchip, schip, &assigned_scalar)?;
+
+        Ok(assigned_scalar)
+    }
+
+    fn squeeze_challenge_scalar(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+    ) -> Result<A::AssignedScalar, A::Error> {
+        self.update_prefix(ctx, nchip, KECCAK256_PREFIX_CHALLENGE)?;
+        let mut digest = self.squeeze_half(ctx, nchip, KECCAK256_PREFIX_CHALLENGE_LO)?;
+        digest.append(&mut self.squeeze_half(ctx, nchip, KECCAK256_PREFIX_CHALLENGE_HI)?);
+        E::decode_scalar_bits(ctx, nchip, schip, &digest)
+    }
+
+    fn common_point(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+        p: &A::AssignedPoint,
+    ) -> Result<(), A::Error> {
+        let (x, y) = E::encode_point_bits(ctx, nchip, schip, pchip, p)?;
+
+        self.update_prefix(ctx, nchip, KECCAK256_PREFIX_POINT)?;
+        self.hash.update(&x);
+        self.hash.update(&y);
+        Ok(())
+    }
+
+    fn common_scalar(
+        &mut self,
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        s: &A::AssignedScalar,
+    ) -> Result<(), A::Error> {
+        let encoded = E::encode_scalar_bits(ctx, nchip, schip, s)?;
+
+        self.update_prefix(ctx, nchip, KECCAK256_PREFIX_SCALAR)?;
+        self.hash.update(&encoded);
+        Ok(())
+    }
+}
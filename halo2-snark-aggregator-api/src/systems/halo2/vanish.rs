@@ -57,9 +57,10 @@ impl<'a, A: ArithEccChip> Evaluated<'a, A> {
         vec![
             EvaluationQuery::new_from_query(
                 0,
+                format!("{}_h_commitment", self.key),
                 x.clone(),
-                self.h_commitment.clone()
-                    + EvaluationQuerySchema::Scalar(self.expected_h_eval.clone()),
+                self.h_commitment.clone(),
+                self.expected_h_eval.clone(),
             ),
             EvaluationQuery::new(
                 0,
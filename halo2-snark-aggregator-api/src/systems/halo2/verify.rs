@@ -1,5 +1,6 @@
+use super::coherent::ProofCommitments;
 use super::evaluation::EvaluationQuerySchema;
-use super::multiopen::MultiOpenProof;
+use super::multiopen::{MultiOpenKind, MultiOpenProof, MultiOpenScheme};
 use super::params::{PlonkCommonSetup, VerifierParams};
 use super::{
     lookup::{self, PermutationCommitments},
@@ -17,7 +18,7 @@ use halo2_proofs::poly::kzg::commitment::{ParamsKZG, ParamsVerifierKZG};
 use halo2_proofs::poly::Rotation;
 use halo2_proofs::{
     arithmetic::CurveAffine,
-    plonk::{Expression, VerifyingKey},
+    plonk::{Error, Expression, VerifyingKey},
 };
 use halo2curves::pairing::{MultiMillerLoop, MillerLoopResult, Engine};
 use std::fmt::Debug;
@@ -339,56 +340,63 @@ impl<
         Ok(lookup_evaluated)
     }
 
-    pub fn build_params(mut self) -> Result<VerifierParams<A>, A::Error> {
-        self.init_transcript()?;
+    // Follow the prover phase by phase: the advice commitments of a phase are read
+    // for every proof, then the challenges usable after that phase are squeezed.
+    // Challenges are shared by all proofs and indexed by `Challenge::index()`.
+    fn load_advice_commitments_and_challenges(
+        &mut self,
+        num_proofs: usize,
+    ) -> Result<(Vec<Vec<A::AssignedPoint>>, Vec<A::AssignedScalar>), A::Error> {
+        let vk = self.vk;
+        let cs = vk.cs();
+        let assigned_zero = self
+            .schip
+            .assign_const(self.ctx, <E::G1Affine as CurveAffine>::ScalarExt::zero())?;
+
+        let mut advice_commitments = vec![vec![None; cs.num_advice_columns()]; num_proofs];
+        let mut challenges = vec![assigned_zero; cs.num_challenges()];
+
+        for current_phase in cs.phases() {
+            for commitments in advice_commitments.iter_mut() {
+                for (phase, commitment) in cs.advice_column_phase.iter().zip(commitments.iter_mut())
+                {
+                    if current_phase == *phase {
+                        *commitment = Some(
+                            self.transcript
+                                .read_point(self.ctx, self.nchip, self.schip, self.pchip)?,
+                        );
+                    }
+                }
+            }
 
-        self.squeeze_instance_commitment()?;
-        let instance_commitments = &self.assigned_instances;
+            for (phase, challenge) in cs.challenge_phase.iter().zip(challenges.iter_mut()) {
+                if current_phase == *phase {
+                    *challenge = self.squeeze_challenge_scalar()?;
+                }
+            }
+        }
 
-        let num_proofs = instance_commitments.len();
+        let advice_commitments = advice_commitments
+            .into_iter()
+            .map(|commitments| commitments.into_iter().map(|c| c.unwrap()).collect())
+            .collect();
 
-        let (advice_commitments, challenges) = {
-            let mut advice_commitments = vec![
-                vec![None;self.vk.cs().num_advice_columns()];
-                num_proofs
-            ];
+        Ok((advice_commitments, challenges))
+    }
 
-            let scalar_zero = <E::G1Affine as CurveAffine>::ScalarExt::zero();
-            let assigned_scalar_zero = self.schip.assign_const(self.ctx, scalar_zero)?;
+    pub fn build_params<M: MultiOpenScheme<A>>(
+        mut self,
+        scheme: &M,
+    ) -> Result<VerifierParams<A>, A::Error> {
+        self.init_transcript()?;
 
-            let mut challenges = vec![assigned_scalar_zero; self.vk.cs().num_challenges()];
-            for current_phase in self.vk.cs().phases() {
-                for advice_commitments in advice_commitments.iter_mut() {
-                    for (phase, commitment) in self.vk
-                        .cs()
-                        .advice_column_phase.iter()
-                        .zip(advice_commitments.iter_mut())
-                    {
-                        if current_phase == *phase {
-                            let point = self.transcript.read_point(self.ctx, self.nchip, self.schip, self.pchip)?;
-                            *commitment = Some(point);
-                        }
-                    }
-                }
-                for (phase, challenge) in self.vk.cs().challenge_phase.iter().zip(challenges.iter_mut()) {
-                    if current_phase == *phase {
-                        *challenge = self.squeeze_challenge_scalar()?
-                    }
-                };
-            };
-            let commitments = advice_commitments
-                .into_iter()
-                .map(|a| a.into_iter()
-                     .map(|b| b.unwrap())
-                     .collect())
-                .collect();
+        self.squeeze_instance_commitment()?;
+        let instance_commitments = &self.assigned_instances;
 
-            (commitments, challenges.to_vec())
-        };
+        let num_proofs = instance_commitments.len();
 
-        /* FIXME: The above handling of multi-phase seems does not handle the multi-proof correctly
-        let challenges = self.load_n_m_scalars(num_proofs, self.vk.cs().num_challenges())?;
-        */
+        let (advice_commitments, challenges) =
+            self.load_advice_commitments_and_challenges(num_proofs)?;
 
         let theta = self.squeeze_challenge_scalar()?;
 
@@ -438,7 +446,7 @@ impl<
 
         let h_commitments = self.load_n_points(self.vk.get_domain().get_quotient_poly_degree())?;
         let l = self.vk.cs().blinding_factors() as u32 + 1;
-        let n = self.params.n() as u32;
+        let n = 1u32 << self.vk.get_domain().k();
         let omega = self.vk.get_domain().get_omega();
 
         let x = self.squeeze_challenge_scalar()?;
@@ -468,14 +476,13 @@ impl<
             .map(|&affine| self.pchip.assign_const(self.ctx, affine))
             .collect::<Result<Vec<_>, _>>()?;
 
-        let v = self.squeeze_challenge_scalar()?;
-
-        let mut w = vec![];
-        while let Ok(p) = self.load_point() {
-            w.push(p);
-        }
-
-        let u = self.squeeze_challenge_scalar()?;
+        let multiopen_witness = scheme.read_witness(
+            self.ctx,
+            self.nchip,
+            self.schip,
+            self.pchip,
+            self.transcript,
+        )?;
 
         let x_next = self.rotate_omega(&x, omega, 1)?;
         let x_last = self.rotate_omega(&x, omega, -(l as i32))?;
@@ -551,12 +558,13 @@ impl<
             x_inv,
             xn,
             y,
-            u,
-            v,
+            u: multiopen_witness.u,
+            v: multiopen_witness.v,
             omega: self
                 .schip
                 .assign_const(self.ctx, self.vk.get_domain().get_omega())?,
-            w,
+            w: multiopen_witness.w,
+            multiopen_challenges: multiopen_witness.challenges,
             zero: self
                 .schip
                 .assign_const(self.ctx, <E::G1Affine as CurveAffine>::ScalarExt::zero())?,
@@ -585,11 +593,21 @@ pub fn assign_instance_commitment<
     instances: &[&[&[E::Scalar]]],
     vk: &VerifyingKey<E::G1Affine>,
     params: &ParamsVerifierKZG<E>,
-) -> Result<(Vec<A::AssignedScalar>, Vec<Vec<A::AssignedPoint>>), A::Error> {
+) -> Result<(Vec<A::AssignedScalar>, Vec<Vec<A::AssignedPoint>>), A::Error>
+where
+    A::Error: From<Error>,
+{
     let mut plain_assigned_instances = vec![];
 
+    // instances are committed with the lagrange basis of the circuit domain
+    if params.k() != vk.get_domain().k() {
+        return Err(Error::InvalidInstances.into());
+    }
+
     for instances in instances.iter() {
-        assert!(instances.len() == vk.cs().num_instance_columns)
+        if instances.len() != vk.cs().num_instance_columns {
+            return Err(Error::InvalidInstances.into());
+        }
     }
 
     let instances = instances
@@ -598,9 +616,9 @@ pub fn assign_instance_commitment<
             instance
                 .iter()
                 .map(|instance| {
-                    assert!(
-                        instance.len() <= params.n() as usize - (vk.cs().blinding_factors() + 1)
-                    );
+                    if instance.len() > params.n() as usize - (vk.cs().blinding_factors() + 1) {
+                        return Err(Error::InvalidInstances.into());
+                    }
 
                     let mut assigned_scalars = vec![];
                     for instance in instance.iter() {
@@ -656,6 +674,7 @@ pub fn verify_single_proof_no_eval<
         Native = <E::G1Affine as CurveAffine>::ScalarExt,
     >,
     T: TranscriptRead<A>,
+    M: MultiOpenScheme<A>,
 >(
     ctx: &mut A::Context,
     nchip: &A::NativeChip,
@@ -666,7 +685,8 @@ pub fn verify_single_proof_no_eval<
     params: &ParamsVerifierKZG<E>,
     transcript: &mut T,
     key: String,
-) -> Result<(MultiOpenProof<A>, Vec<<A as ArithEccChip>::AssignedPoint>), A::Error> {
+    scheme: &M,
+) -> Result<(MultiOpenProof<A>, ProofCommitments<A::AssignedPoint>), A::Error> {
     let params_builder = VerifierParamsBuilder {
         ctx,
         nchip,
@@ -679,38 +699,73 @@ pub fn verify_single_proof_no_eval<
         key,
     };
 
-    let chip_params = params_builder.build_params()?;
-    let advice_commitments = chip_params.advice_commitments.clone();
+    let chip_params = params_builder.build_params(scheme)?;
+
+    let phases = vk.cs().phases().collect::<Vec<_>>();
+    let mut advice = vec![vec![]; phases.len()];
+    for (phase, commitment) in vk
+        .cs()
+        .advice_column_phase
+        .iter()
+        .zip(chip_params.advice_commitments[0].iter())
+    {
+        let phase = phases.iter().position(|p| p == phase).unwrap();
+        advice[phase].push(commitment.clone());
+    }
+    let commitments = ProofCommitments {
+        advice,
+        fixed: chip_params.fixed_commitments.clone(),
+        instance: chip_params.instance_commitments[0].clone(),
+    };
+
     Ok((
-        chip_params.batch_multi_open_proofs(ctx, schip, pchip)?,
-        advice_commitments[0].clone(),
+        scheme.batch_multi_open_proofs(&chip_params, ctx, schip, pchip)?,
+        commitments,
     ))
 }
 
-fn evaluate_multiopen_proof<
+/// Circuits of one aggregation are checked by a single pairing, on the g2 and s_g2 of
+/// the first circuit, so the params of all of them must come from the same SRS. Each
+/// circuit keeps params of its own k, for the lagrange basis of its instances.
+pub fn same_srs<E: MultiMillerLoop>(a: &ParamsVerifierKZG<E>, b: &ParamsVerifierKZG<E>) -> bool {
+    a.g2() == b.g2() && a.s_g2() == b.s_g2()
+}
+
+// e(w_x, s_g2) * e(w_g, -g2) == 1
+fn check_multiopen_pairing<E: MultiMillerLoop>(
+    params: &ParamsVerifierKZG<E>,
+    w_x: &E::G1Affine,
+    w_g: &E::G1Affine,
+) -> bool {
+    let s_g2_prepared = E::G2Prepared::from(params.s_g2());
+    let n_g2_prepared = E::G2Prepared::from(-params.g2());
+    bool::from(
+        E::multi_miller_loop(&[(w_x, &s_g2_prepared), (w_g, &n_g2_prepared)])
+            .final_exponentiation()
+            .is_identity(),
+    )
+}
+
+fn eval_multiopen_pair<
     E: MultiMillerLoop + Debug,
     A: ArithEccChip<
         Point = E::G1Affine,
         Scalar = <E::G1Affine as CurveAffine>::ScalarExt,
         Native = <E::G1Affine as CurveAffine>::ScalarExt,
     >,
-    T: TranscriptRead<A>,
 >(
     ctx: &mut A::Context,
     schip: &A::ScalarChip,
     pchip: &A,
     proof: MultiOpenProof<A>,
-    params: &ParamsVerifierKZG<E>,
-) -> Result<(A::AssignedPoint, A::AssignedPoint), A::Error> {
+) -> Result<(A::AssignedPoint, A::AssignedPoint, Vec<String>), A::Error> {
     let one = schip.assign_one(ctx)?;
 
-    println!("debug context before evaluate multiopen proof: {}", ctx);
     let mut points = Vec::new();
     let (left_s, left_e, mut points_wx) = proof.w_x.eval::<_, A>(ctx, schip, pchip, &one)?;
     let (right_s, right_e, mut points_wg) = proof.w_g.eval::<_, A>(ctx, schip, pchip, &one)?;
     points.append(&mut points_wx);
     points.append(&mut points_wg);
-    print_points_profiling(&points);
     let generator = pchip.assign_one(ctx)?;
     let left = match left_e {
         None => left_s,
@@ -727,19 +782,37 @@ fn evaluate_multiopen_proof<
         }
     };
 
-    let left_v = pchip.to_value(&left)?;
-    let right_v = pchip.to_value(&right)?;
+    Ok((left, right, points))
+}
 
-    let s_g2_prepared = E::G2Prepared::from(params.s_g2());
-    let n_g2_prepared = E::G2Prepared::from(-params.g2());
-    let success = bool::from(
-        E::multi_miller_loop(&[(&left_v, &s_g2_prepared), (&right_v, &n_g2_prepared)])
-            .final_exponentiation()
-            .is_identity(),
-    );
-    println!("evaluate_multiopen_proof pairing check {}", success);
+fn evaluate_multiopen_proof<
+    E: MultiMillerLoop + Debug,
+    A: ArithEccChip<
+        Point = E::G1Affine,
+        Scalar = <E::G1Affine as CurveAffine>::ScalarExt,
+        Native = <E::G1Affine as CurveAffine>::ScalarExt,
+    >,
+    T: TranscriptRead<A>,
+>(
+    ctx: &mut A::Context,
+    schip: &A::ScalarChip,
+    pchip: &A,
+    proof: MultiOpenProof<A>,
+    params: &ParamsVerifierKZG<E>,
+) -> Result<(A::AssignedPoint, A::AssignedPoint), A::Error>
+where
+    A::Error: From<Error>,
+{
+    let (left, right, points) = eval_multiopen_pair::<E, A>(ctx, schip, pchip, proof)?;
+    print_points_profiling(&points);
 
-    println!("debug context after evaluate multiopen proof: {}", ctx);
+    // The pair of proofs that do not open their commitments would only fail the final
+    // pairing, after the verify circuit is proved.
+    let left_v = pchip.to_value(&left)?;
+    let right_v = pchip.to_value(&right)?;
+    if !check_multiopen_pairing(params, &left_v, &right_v) {
+        return Err(Error::Opening.into());
+    }
 
     Ok((left, right))
 }
@@ -757,6 +830,9 @@ pub struct ProofData<
     pub instances: &'a Vec<Vec<Vec<E::Scalar>>>,
     pub transcript: T,
     pub key: String,
+    // Selector bit of a padding slot, the proof is dropped from the accumulator
+    // and its instances are masked when it is zero.
+    pub enabled: Option<A::AssignedScalar>,
     pub _phantom: PhantomData<A>,
 }
 
@@ -769,11 +845,13 @@ pub struct CircuitProof<
         Native = <E::G1Affine as CurveAffine>::ScalarExt,
     >,
     T: TranscriptRead<A>,
+    M: MultiOpenScheme<A> = MultiOpenKind,
 > {
     pub name: String,
     pub vk: &'a VerifyingKey<E::G1Affine>,
     pub params: &'a ParamsVerifierKZG<E>,
     pub proofs: Vec<ProofData<'a, E, A, T>>,
+    pub scheme: M,
 }
 
 pub fn verify_single_proof_in_chip<
@@ -784,22 +862,26 @@ pub fn verify_single_proof_in_chip<
         Native = <E::G1Affine as CurveAffine>::ScalarExt,
     >,
     T: TranscriptRead<A>,
+    M: MultiOpenScheme<A>,
 >(
     ctx: &mut A::Context,
     nchip: &A::NativeChip,
     schip: &A::ScalarChip,
     pchip: &A,
-    circuit: &mut CircuitProof<E, A, T>,
+    circuit: &mut CircuitProof<E, A, T, M>,
     transcript: &mut T,
 ) -> Result<
     (
-        A::AssignedPoint,       // w_x
-        A::AssignedPoint,       // w_g
-        Vec<A::AssignedScalar>, // plain assigned instance
-        Vec<A::AssignedPoint>,  // advice commitments
+        A::AssignedPoint,                   // w_x
+        A::AssignedPoint,                   // w_g
+        Vec<A::AssignedScalar>,             // plain assigned instance
+        ProofCommitments<A::AssignedPoint>, // commitments
     ),
     A::Error,
-> {
+>
+where
+    A::Error: From<Error>,
+{
     let instances1: Vec<Vec<&[E::Scalar]>> = circuit.proofs[0]
         .instances
         .iter()
@@ -815,7 +897,7 @@ pub fn verify_single_proof_in_chip<
         circuit.params,
     )?;
 
-    let (proof, advice_commitments) = verify_single_proof_no_eval(
+    let (proof, commitments) = verify_single_proof_no_eval(
         ctx,
         nchip,
         schip,
@@ -825,13 +907,17 @@ pub fn verify_single_proof_in_chip<
         circuit.params,
         transcript,
         "".to_owned(),
+        &circuit.scheme,
     )?;
 
     print!("get single proof {}", circuit.name);
     let (w_x, w_g) = evaluate_multiopen_proof::<E, A, T>(ctx, schip, pchip, proof, circuit.params)?;
-    Ok((w_x, w_g, plain_assigned_instances, advice_commitments))
+    Ok((w_x, w_g, plain_assigned_instances, commitments))
 }
 
+/// The params of the circuits must come from the same SRS, see `same_srs`, it is up to
+/// the caller to check them: the final pair of circuits from different SRS only fails
+/// the pairing.
 pub fn verify_aggregation_proofs_in_chip<
     E: MultiMillerLoop + Debug,
     A: ArithEccChip<
@@ -840,25 +926,34 @@ pub fn verify_aggregation_proofs_in_chip<
         Native = <E::G1Affine as CurveAffine>::ScalarExt,
     >,
     T: TranscriptRead<A>,
+    M: MultiOpenScheme<A>,
 >(
     ctx: &mut A::Context,
     nchip: &A::NativeChip,
     schip: &A::ScalarChip,
     pchip: &A,
-    mut circuits: Vec<CircuitProof<E, A, T>>,
+    mut circuits: Vec<CircuitProof<E, A, T, M>>,
     transcript: &mut T,
 ) -> Result<
     (
-        A::AssignedPoint,           // w_x
-        A::AssignedPoint,           // w_g
-        Vec<A::AssignedScalar>,     // plain assigned instance
-        Vec<Vec<A::AssignedPoint>>, // advice commitments
+        A::AssignedPoint,                             // w_x
+        A::AssignedPoint,                             // w_g
+        Vec<A::AssignedScalar>,                       // plain assigned instance
+        Vec<Vec<ProofCommitments<A::AssignedPoint>>>, // commitments by circuit and proof
     ),
     A::Error,
-> {
+>
+where
+    A::Error: From<Error>,
+{
+    let params = match circuits.first() {
+        Some(circuit) => circuit.params,
+        None => return Err(Error::Synthesis.into()),
+    };
+
     let mut plain_assigned_instances = vec![];
 
-    let multiopen_proofs: Vec<Vec<(MultiOpenProof<A>, Vec<A::AssignedPoint>)>> = circuits
+    let multiopen_proofs = circuits
         //let multiopen_proofs: Vec<Vec<MultiOpenProof<A>>> = circuits
         .iter_mut()
         .map(|circuit_proof| {
@@ -885,6 +980,10 @@ pub fn verify_aggregation_proofs_in_chip<
                         )?;
 
                     for assigned_instance in assigned_instances {
+                        let assigned_instance = match &proof.enabled {
+                            Some(enabled) => schip.mul(ctx, &assigned_instance, enabled)?,
+                            None => assigned_instance,
+                        };
                         plain_assigned_instances.push(assigned_instance)
                     }
 
@@ -898,13 +997,20 @@ pub fn verify_aggregation_proofs_in_chip<
                         circuit_proof.params,
                         &mut proof.transcript,
                         proof.key.clone(),
+                        &circuit_proof.scheme,
                     )?;
 
-                    println!("get proof {} {}", circuit_proof.name, p);
+                    let p = match &proof.enabled {
+                        Some(enabled) => MultiOpenProof {
+                            w_x: p.w_x * scalar!(enabled),
+                            w_g: p.w_g * scalar!(enabled),
+                        },
+                        None => p,
+                    };
 
                     Ok((p, c))
                 })
-                .collect::<Result<Vec<(MultiOpenProof<A>, Vec<A::AssignedPoint>)>, A::Error>>();
+                .collect::<Result<Vec<(MultiOpenProof<A>, ProofCommitments<A::AssignedPoint>)>, A::Error>>();
 
             /* update aggregation challenge */
             for p in circuit_proof.proofs.iter_mut() {
@@ -914,29 +1020,144 @@ pub fn verify_aggregation_proofs_in_chip<
 
             r
         })
-        .collect::<Result<Vec<Vec<(MultiOpenProof<A>, Vec<A::AssignedPoint>)>>, A::Error>>()?;
-
-    let proofs = multiopen_proofs
-        .into_iter()
-        .flatten()
-        .collect::<Vec<(MultiOpenProof<A>, Vec<A::AssignedPoint>)>>();
+        .collect::<Result<Vec<Vec<(MultiOpenProof<A>, ProofCommitments<A::AssignedPoint>)>>, A::Error>>()?;
 
     let aggregation_challenge = transcript.squeeze_challenge_scalar(ctx, nchip, schip)?;
 
     let mut acc: Option<MultiOpenProof<A>> = None;
-    let mut commits: Vec<Vec<A::AssignedPoint>> = vec![];
-    for (proof, c) in proofs.into_iter() {
-        acc = match acc {
-            None => Some(proof),
-            Some(acc) => Some(MultiOpenProof {
-                w_x: acc.w_x * scalar!(aggregation_challenge) + proof.w_x,
-                w_g: acc.w_g * scalar!(aggregation_challenge) + proof.w_g,
-            }),
-        };
-        commits.push(c)
+    let mut commits: Vec<Vec<ProofCommitments<A::AssignedPoint>>> = vec![];
+    for proofs in multiopen_proofs.into_iter() {
+        let mut circuit_commits = vec![];
+        for (proof, c) in proofs.into_iter() {
+            acc = match acc {
+                None => Some(proof),
+                Some(acc) => Some(MultiOpenProof {
+                    w_x: acc.w_x * scalar!(aggregation_challenge) + proof.w_x,
+                    w_g: acc.w_g * scalar!(aggregation_challenge) + proof.w_g,
+                }),
+            };
+            circuit_commits.push(c)
+        }
+        commits.push(circuit_commits)
     }
-    let aggregated_proof = acc.unwrap();
+    // No proof to aggregate, e.g. circuits without proofs
+    let aggregated_proof = acc.ok_or(Error::Synthesis)?;
 
-    evaluate_multiopen_proof::<E, A, T>(ctx, schip, pchip, aggregated_proof, circuits[0].params)
+    evaluate_multiopen_proof::<E, A, T>(ctx, schip, pchip, aggregated_proof, params)
         .map(|pair| (pair.0, pair.1, plain_assigned_instances, commits))
 }
+
+/// Diagnostic counterpart of `verify_aggregation_proofs_in_chip`, meant for the mock
+/// chips. Each proof is evaluated and pairing checked on its own, before any random
+/// linear combination, so a failing aggregation can be traced to its inputs. The
+/// report gives the pairing result of every proof under its `ProofData::key`.
+pub fn diagnose_aggregation_proofs_in_chip<
+    E: MultiMillerLoop + Debug,
+    A: ArithEccChip<
+        Point = E::G1Affine,
+        Scalar = <E::G1Affine as CurveAffine>::ScalarExt,
+        Native = <E::G1Affine as CurveAffine>::ScalarExt,
+    >,
+    T: TranscriptRead<A>,
+    M: MultiOpenScheme<A>,
+>(
+    ctx: &mut A::Context,
+    nchip: &A::NativeChip,
+    schip: &A::ScalarChip,
+    pchip: &A,
+    mut circuits: Vec<CircuitProof<E, A, T, M>>,
+) -> Result<Vec<(String, bool)>, A::Error>
+where
+    A::Error: From<Error>,
+{
+    let mut report = vec![];
+
+    for circuit_proof in circuits.iter_mut() {
+        for proof in circuit_proof.proofs.iter_mut() {
+            let instances1: Vec<Vec<&[E::Scalar]>> = proof
+                .instances
+                .iter()
+                .map(|x| x.iter().map(|y| &y[..]).collect())
+                .collect();
+            let instances2: Vec<&[&[E::Scalar]]> = instances1.iter().map(|x| &x[..]).collect();
+
+            let (_, assigned_instance_commitments) = assign_instance_commitment(
+                ctx,
+                schip,
+                pchip,
+                &instances2[..],
+                circuit_proof.vk,
+                circuit_proof.params,
+            )?;
+
+            let (p, _) = verify_single_proof_no_eval(
+                ctx,
+                nchip,
+                schip,
+                pchip,
+                assigned_instance_commitments,
+                circuit_proof.vk,
+                circuit_proof.params,
+                &mut proof.transcript,
+                proof.key.clone(),
+                &circuit_proof.scheme,
+            )?;
+
+            let (w_x, w_g, _) = eval_multiopen_pair::<E, A>(ctx, schip, pchip, p)?;
+            let success = check_multiopen_pairing(
+                circuit_proof.params,
+                &pchip.to_value(&w_x)?,
+                &pchip.to_value(&w_g)?,
+            );
+            report.push((proof.key.clone(), success));
+        }
+    }
+
+    Ok(report)
+}
+
+/// Fold accumulators of previous aggregation proofs, i.e. final pairs that satisfy
+/// e(w_x, s_g2) * e(w_g, -g2) == 1 on the same SRS, into the pair produced by
+/// `verify_aggregation_proofs_in_chip`.
+pub fn fold_accumulators_in_chip<
+    E: MultiMillerLoop + Debug,
+    A: ArithEccChip<
+        Point = E::G1Affine,
+        Scalar = <E::G1Affine as CurveAffine>::ScalarExt,
+        Native = <E::G1Affine as CurveAffine>::ScalarExt,
+    >,
+    T: TranscriptRead<A>,
+>(
+    ctx: &mut A::Context,
+    nchip: &A::NativeChip,
+    schip: &A::ScalarChip,
+    pchip: &A,
+    w_x: A::AssignedPoint,
+    w_g: A::AssignedPoint,
+    accumulators: Vec<(A::AssignedPoint, A::AssignedPoint)>,
+    transcript: &mut T,
+) -> Result<(A::AssignedPoint, A::AssignedPoint), A::Error> {
+    if accumulators.is_empty() {
+        return Ok((w_x, w_g));
+    }
+
+    transcript.common_point(ctx, nchip, schip, pchip, &w_x)?;
+    transcript.common_point(ctx, nchip, schip, pchip, &w_g)?;
+    for (acc_x, acc_g) in accumulators.iter() {
+        transcript.common_point(ctx, nchip, schip, pchip, acc_x)?;
+        transcript.common_point(ctx, nchip, schip, pchip, acc_g)?;
+    }
+
+    let accumulator_challenge = transcript.squeeze_challenge_scalar(ctx, nchip, schip)?;
+
+    let mut w_x = w_x;
+    let mut w_g = w_g;
+    for (acc_x, acc_g) in accumulators.iter() {
+        let s_x = pchip.scalar_mul(ctx, &accumulator_challenge, &w_x)?;
+        let s_g = pchip.scalar_mul(ctx, &accumulator_challenge, &w_g)?;
+        w_x = pchip.add(ctx, &s_x, acc_x)?;
+        w_g = pchip.add(ctx, &s_g, acc_g)?;
+    }
+
+    Ok((pchip.normalize(ctx, &w_x)?, pchip.normalize(ctx, &w_g)?))
+}
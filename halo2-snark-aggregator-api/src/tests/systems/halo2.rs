@@ -1,5 +1,10 @@
 pub mod add_mul_test;
+pub mod diagnose_test;
 pub mod lookup_test;
+pub mod multi_phase_test;
+pub mod native_test;
+pub mod srs_test;
+pub mod transcript_test;
 
 #[cfg(test)]
 pub mod zkevm_test;
@@ -6,6 +6,7 @@ use crate::transcript::encode::Encode;
 use crate::{
     arith::{common::ArithCommonChip, ecc::ArithEccChip, field::ArithFieldChip},
     systems::halo2::{
+        multiopen::MultiOpenKind,
         transcript::PoseidonTranscriptRead,
         verify::{verify_aggregation_proofs_in_chip, ProofData},
     },
@@ -13,7 +14,7 @@ use crate::{
 use halo2_proofs::arithmetic::{CurveAffine, Field};
 use halo2_proofs::poly::commitment::ParamsProver;
 use halo2_proofs::poly::kzg::commitment::ParamsVerifierKZG;
-use halo2_proofs::poly::kzg::multiopen::ProverGWC;
+use halo2_proofs::poly::kzg::multiopen::{ProverGWC, ProverSHPLONK};
 use halo2_proofs::{
     plonk::{create_proof, keygen_pk, keygen_vk},
     poly::kzg::commitment::KZGCommitmentScheme,
@@ -39,6 +40,7 @@ pub fn test_verify_aggregation_proof_in_chip<
     schip: &ScalarChip,
     pchip: &EccChip,
     ctx: &mut <EccChip as ArithCommonChip>::Context,
+    kind: MultiOpenKind,
 ) where
     NativeChip: ArithFieldChip<Field = <G1Affine as CurveAffine>::ScalarExt>,
     ScalarChip: ArithFieldChip<Field = <G1Affine as CurveAffine>::ScalarExt>,
@@ -88,14 +90,28 @@ pub fn test_verify_aggregation_proof_in_chip<
             .collect();
         let instances2: Vec<&[&[Fp]]> = instances1.iter().map(|x| &x[..]).collect();
 
-        create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
-            &params,
-            &pk,
-            &[circuit],
-            &instances2[..],
-            Pcg32::seed_from_u64(0),
-            &mut transcript,
-        )
+        match kind {
+            MultiOpenKind::GWC => {
+                create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
+                    &params,
+                    &pk,
+                    &[circuit],
+                    &instances2[..],
+                    Pcg32::seed_from_u64(0),
+                    &mut transcript,
+                )
+            }
+            MultiOpenKind::SHPLONK => {
+                create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<Bn256>, _, _, _, _>(
+                    &params,
+                    &pk,
+                    &[circuit],
+                    &instances2[..],
+                    Pcg32::seed_from_u64(0),
+                    &mut transcript,
+                )
+            }
+        }
         .expect("proof generation should not fail");
         let proof = transcript.finalize();
         n_proof.push(proof);
@@ -119,6 +135,7 @@ pub fn test_verify_aggregation_proof_in_chip<
             instances,
             transcript,
             key: format!("p{}", i),
+            enabled: None,
             _phantom: PhantomData,
         })
     }
@@ -142,6 +159,7 @@ pub fn test_verify_aggregation_proof_in_chip<
             vk: &vk,
             params: params_verifier,
             proofs: proof_data_list,
+            scheme: kind,
         }],
         &mut transcript,
     )
@@ -171,6 +189,20 @@ mod tests {
             MockFieldChip<Fp, Error>,
             MockEccChip<G1Affine, Error>,
             PoseidonEncode,
-        >(&nchip, &schip, &pchip, ctx);
+        >(&nchip, &schip, &pchip, ctx, MultiOpenKind::GWC);
+    }
+
+    #[test]
+    fn test_verify_aggreation_proof_in_chip_code_shplonk() {
+        let nchip = MockFieldChip::default();
+        let schip = MockFieldChip::default();
+        let pchip = MockEccChip::default();
+        let ctx = &mut MockChipCtx::default();
+        test_verify_aggregation_proof_in_chip::<
+            MockFieldChip<Fp, Error>,
+            MockFieldChip<Fp, Error>,
+            MockEccChip<G1Affine, Error>,
+            PoseidonEncode,
+        >(&nchip, &schip, &pchip, ctx, MultiOpenKind::SHPLONK);
     }
 }
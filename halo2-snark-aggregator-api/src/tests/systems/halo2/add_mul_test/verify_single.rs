@@ -3,6 +3,7 @@ use std::marker::PhantomData;
 use crate::{
     arith::{common::ArithCommonChip, ecc::ArithEccChip, field::ArithFieldChip},
     systems::halo2::{
+        multiopen::MultiOpenKind,
         transcript::PoseidonTranscriptRead,
         verify::{verify_single_proof_in_chip, CircuitProof, ProofData},
     },
@@ -15,7 +16,7 @@ use halo2_proofs::{
         commitment::ParamsProver,
         kzg::{
             commitment::{KZGCommitmentScheme, ParamsKZG, ParamsVerifierKZG},
-            multiopen::ProverGWC,
+            multiopen::{ProverGWC, ProverSHPLONK},
         },
     },
 };
@@ -41,6 +42,7 @@ pub fn test_verify_single_proof_in_chip<
     schip: &ScalarChip,
     pchip: &EccChip,
     ctx: &mut <EccChip as ArithCommonChip>::Context,
+    kind: MultiOpenKind,
 ) where
     NativeChip: ArithFieldChip<Field = <G1Affine as CurveAffine>::ScalarExt>,
     ScalarChip: ArithFieldChip<Field = <G1Affine as CurveAffine>::ScalarExt>,
@@ -75,14 +77,28 @@ pub fn test_verify_single_proof_in_chip<
     let pk = keygen_pk(&params, vk, &circuit).expect("keygen_pk should not fail");
 
     let mut transcript = PoseidonWrite::<Vec<u8>, G1Affine, Challenge255<G1Affine>>::init(vec![]);
-    create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
-        &params,
-        &pk,
-        &[circuit],
-        instances,
-        Pcg32::seed_from_u64(0),
-        &mut transcript,
-    )
+    match kind {
+        MultiOpenKind::GWC => {
+            create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
+                &params,
+                &pk,
+                &[circuit],
+                instances,
+                Pcg32::seed_from_u64(0),
+                &mut transcript,
+            )
+        }
+        MultiOpenKind::SHPLONK => {
+            create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<Bn256>, _, _, _, _>(
+                &params,
+                &pk,
+                &[circuit],
+                instances,
+                Pcg32::seed_from_u64(0),
+                &mut transcript,
+            )
+        }
+    }
     .expect("proof generation should not fail");
     let proof = transcript.finalize();
 
@@ -104,6 +120,7 @@ pub fn test_verify_single_proof_in_chip<
             .collect::<Vec<Vec<Vec<Fp>>>>(),
         transcript,
         key: format!("p{}", 0),
+        enabled: None,
         _phantom: PhantomData,
     };
 
@@ -126,6 +143,7 @@ pub fn test_verify_single_proof_in_chip<
             vk: pk.get_vk(),
             params: params_verifier,
             proofs: vec![pdata],
+            scheme: kind,
         },
         &mut transcript,
     )
@@ -155,6 +173,20 @@ mod tests {
             MockFieldChip<Fp, Error>,
             MockEccChip<G1Affine, Error>,
             PoseidonEncode,
-        >(&nchip, &schip, &pchip, ctx);
+        >(&nchip, &schip, &pchip, ctx, MultiOpenKind::GWC);
+    }
+
+    #[test]
+    fn test_verify_single_proof_in_chip_code_shplonk() {
+        let nchip = MockFieldChip::default();
+        let schip = MockFieldChip::default();
+        let pchip = MockEccChip::default();
+        let ctx = &mut MockChipCtx::default();
+        test_verify_single_proof_in_chip::<
+            MockFieldChip<Fp, Error>,
+            MockFieldChip<Fp, Error>,
+            MockEccChip<G1Affine, Error>,
+            PoseidonEncode,
+        >(&nchip, &schip, &pchip, ctx, MultiOpenKind::SHPLONK);
     }
 }
@@ -0,0 +1,147 @@
+use std::marker::PhantomData;
+
+use crate::{
+    hash::poseidon::HALO2_TRANSCRIPT_SPEC,
+    mock::{
+        arith::{
+            ecc::MockEccChip,
+            field::{MockChipCtx, MockFieldChip},
+        },
+        transcript_encode::PoseidonEncode,
+    },
+    systems::halo2::{
+        multiopen::MultiOpenKind,
+        transcript::TranscriptSpecRead,
+        verify::{diagnose_aggregation_proofs_in_chip, CircuitProof, ProofData},
+    },
+    tests::systems::halo2::add_mul_test::test_circuit::test_circuit_builder,
+};
+use halo2_proofs::{
+    arithmetic::Field,
+    plonk::{create_proof, keygen_pk, keygen_vk, Error},
+    poly::{
+        commitment::ParamsProver,
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG, ParamsVerifierKZG},
+            multiopen::{ProverGWC, ProverSHPLONK},
+        },
+    },
+    transcript::{Challenge255, PoseidonWrite},
+};
+use halo2curves::bn256::Fr as Fp;
+use halo2curves::bn256::{Bn256, G1Affine};
+use rand::{thread_rng, SeedableRng};
+use rand_pcg::Pcg32;
+
+const K: u32 = 10;
+
+// Diagnoses add-mul proofs, one per entry of `valid`. An invalid proof is checked
+// against a wrong instance, only its key must be reported as failing.
+pub fn test_diagnose_aggregation_proofs(kind: MultiOpenKind, valid: &[bool]) {
+    let mut test_rng = thread_rng();
+    let params = ParamsKZG::<Bn256>::setup(K, &mut test_rng);
+    let vk = keygen_vk(&params, &test_circuit_builder(Fp::zero(), Fp::zero()))
+        .expect("keygen_vk should not fail");
+    let params_verifier: &ParamsVerifierKZG<Bn256> = params.verifier_params();
+
+    let mut n_instances = vec![];
+    let mut n_proof = vec![];
+    let constant = Fp::from(7);
+    for valid in valid.iter() {
+        let a = Fp::random(&mut test_rng);
+        let b = Fp::random(&mut test_rng);
+        let c = constant * a.square() * b.square();
+        let circuit = test_circuit_builder(a, b);
+        let vk = keygen_vk(&params, &circuit).expect("keygen_vk should not fail");
+        let pk = keygen_pk(&params, vk, &circuit).expect("keygen_pk should not fail");
+        let instances: &[&[&[_]]] = &[&[&[c]]];
+        let mut transcript =
+            PoseidonWrite::<Vec<u8>, G1Affine, Challenge255<G1Affine>>::init(vec![]);
+
+        match kind {
+            MultiOpenKind::GWC => {
+                create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
+                    &params,
+                    &pk,
+                    &[circuit],
+                    instances,
+                    Pcg32::seed_from_u64(0),
+                    &mut transcript,
+                )
+            }
+            MultiOpenKind::SHPLONK => {
+                create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<Bn256>, _, _, _, _>(
+                    &params,
+                    &pk,
+                    &[circuit],
+                    instances,
+                    Pcg32::seed_from_u64(0),
+                    &mut transcript,
+                )
+            }
+        }
+        .expect("proof generation should not fail");
+        n_proof.push(transcript.finalize());
+        n_instances.push(vec![vec![vec![if *valid { c } else { c + Fp::one() }]]]);
+    }
+
+    let nchip = MockFieldChip::<Fp, Error>::default();
+    let schip = MockFieldChip::<Fp, Error>::default();
+    let pchip = MockEccChip::<G1Affine, Error>::default();
+    let ctx = &mut MockChipCtx::default();
+
+    let proofs = n_instances
+        .iter()
+        .enumerate()
+        .map(|(i, instances)| ProofData {
+            instances,
+            transcript: TranscriptSpecRead::<_, G1Affine, _, PoseidonEncode>::from_spec(
+                &n_proof[i][..],
+                ctx,
+                &nchip,
+                &HALO2_TRANSCRIPT_SPEC,
+            )
+            .unwrap(),
+            key: format!("test_circuit_add_mul_p{}", i),
+            enabled: None,
+            _phantom: PhantomData,
+        })
+        .collect();
+
+    let report = diagnose_aggregation_proofs_in_chip(
+        ctx,
+        &nchip,
+        &schip,
+        &pchip,
+        vec![CircuitProof {
+            name: String::from("test_circuit_add_mul"),
+            vk: &vk,
+            params: params_verifier,
+            proofs,
+            scheme: kind,
+        }],
+    )
+    .unwrap();
+
+    let expected = valid
+        .iter()
+        .enumerate()
+        .map(|(i, valid)| (format!("test_circuit_add_mul_p{}", i), *valid))
+        .collect::<Vec<_>>();
+    assert_eq!(report, expected);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose_aggregation_proofs_gwc() {
+        test_diagnose_aggregation_proofs(MultiOpenKind::GWC, &[true, false, true]);
+    }
+
+    #[test]
+    fn test_diagnose_aggregation_proofs_shplonk() {
+        test_diagnose_aggregation_proofs(MultiOpenKind::SHPLONK, &[false, true]);
+    }
+}
@@ -3,6 +3,7 @@ use crate::transcript::encode::Encode;
 use crate::{
     arith::{common::ArithCommonChip, ecc::ArithEccChip, field::ArithFieldChip},
     systems::halo2::{
+        multiopen::MultiOpenKind,
         transcript::PoseidonTranscriptRead,
         verify::{verify_aggregation_proofs_in_chip, CircuitProof, ProofData},
     },
@@ -10,7 +11,7 @@ use crate::{
 use halo2_proofs::arithmetic::CurveAffine;
 use halo2_proofs::poly::commitment::ParamsProver;
 use halo2_proofs::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG, ParamsVerifierKZG};
-use halo2_proofs::poly::kzg::multiopen::ProverGWC;
+use halo2_proofs::poly::kzg::multiopen::{ProverGWC, ProverSHPLONK};
 use halo2_proofs::{
     plonk::{create_proof, keygen_pk, keygen_vk},
     transcript::{Challenge255, PoseidonWrite},
@@ -34,6 +35,7 @@ pub fn test_verify_aggregation_proof_in_chip<
     schip: &ScalarChip,
     pchip: &EccChip,
     ctx: &mut <EccChip as ArithCommonChip>::Context,
+    kind: MultiOpenKind,
 ) where
     NativeChip: ArithFieldChip<Field = <G1Affine as CurveAffine>::ScalarExt>,
     ScalarChip: ArithFieldChip<Field = <G1Affine as CurveAffine>::ScalarExt>,
@@ -77,14 +79,28 @@ pub fn test_verify_aggregation_proof_in_chip<
             .collect();
         let instances2: Vec<&[&[Fp]]> = instances1.iter().map(|x| &x[..]).collect();
 
-        create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
-            &params,
-            &pk,
-            &[circuit],
-            &instances2[..],
-            OsRng,
-            &mut transcript,
-        )
+        match kind {
+            MultiOpenKind::GWC => {
+                create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
+                    &params,
+                    &pk,
+                    &[circuit],
+                    &instances2[..],
+                    OsRng,
+                    &mut transcript,
+                )
+            }
+            MultiOpenKind::SHPLONK => {
+                create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<Bn256>, _, _, _, _>(
+                    &params,
+                    &pk,
+                    &[circuit],
+                    &instances2[..],
+                    OsRng,
+                    &mut transcript,
+                )
+            }
+        }
         .expect("proof generation should not fail");
         let proof = transcript.finalize();
         n_proof.push(proof);
@@ -108,6 +124,7 @@ pub fn test_verify_aggregation_proof_in_chip<
             instances,
             transcript,
             key: format!("p{}", i),
+            enabled: None,
             _phantom: PhantomData,
         })
     }
@@ -132,6 +149,7 @@ pub fn test_verify_aggregation_proof_in_chip<
             vk: &vk,
             params: params_verifier,
             proofs: proof_data_list,
+            scheme: kind,
         }],
         &mut transcript,
     )
@@ -161,6 +179,20 @@ mod tests {
             MockFieldChip<Fp, Error>,
             MockEccChip<G1Affine, Error>,
             PoseidonEncode,
-        >(&nchip, &schip, &pchip, ctx);
+        >(&nchip, &schip, &pchip, ctx, MultiOpenKind::GWC);
+    }
+
+    #[test]
+    fn test_verify_aggreation_proof_in_chip_code_shplonk() {
+        let nchip = MockFieldChip::default();
+        let schip = MockFieldChip::default();
+        let pchip = MockEccChip::default();
+        let ctx = &mut MockChipCtx::default();
+        test_verify_aggregation_proof_in_chip::<
+            MockFieldChip<Fp, Error>,
+            MockFieldChip<Fp, Error>,
+            MockEccChip<G1Affine, Error>,
+            PoseidonEncode,
+        >(&nchip, &schip, &pchip, ctx, MultiOpenKind::SHPLONK);
     }
 }
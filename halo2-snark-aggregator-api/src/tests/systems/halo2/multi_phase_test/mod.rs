@@ -0,0 +1,3 @@
+pub(crate) mod test_circuit;
+pub mod verify_aggregation;
+pub mod verify_single;
@@ -0,0 +1,144 @@
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{
+        Advice, Challenge, Circuit, Column, ConstraintSystem, Error, FirstPhase, Instance,
+        SecondPhase, Selector, ThirdPhase,
+    },
+    poly::Rotation,
+};
+use halo2curves::bn256::Fr as Fp;
+use std::marker::PhantomData;
+
+pub(crate) const ROWS: usize = 4;
+
+// Column advice[i] lives in the (i + 1)-th phase and is derived from advice[i - 1]
+// with challenges[i - 1], which is squeezed after the i-th phase:
+//     advice[i][row] = advice[i - 1][row] * challenges[i - 1] + advice[i - 1][row + 1]
+#[derive(Clone, Debug)]
+pub struct MyConfig<F: FieldExt, const PHASES: usize> {
+    q: Selector,
+    advice: Vec<Column<Advice>>,
+    challenges: Vec<Challenge>,
+    instance: Column<Instance>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt, const PHASES: usize> MyConfig<F, PHASES> {
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self {
+        assert!(PHASES == 2 || PHASES == 3);
+
+        let q = meta.selector();
+        let instance = meta.instance_column();
+
+        let mut advice = vec![meta.advice_column_in(FirstPhase)];
+        let mut challenges = vec![meta.challenge_usable_after(FirstPhase)];
+        advice.push(meta.advice_column_in(SecondPhase));
+        if PHASES == 3 {
+            challenges.push(meta.challenge_usable_after(SecondPhase));
+            advice.push(meta.advice_column_in(ThirdPhase));
+        }
+
+        meta.enable_equality(instance);
+        meta.enable_equality(advice[0]);
+
+        for i in 1..PHASES {
+            meta.create_gate("next phase", |meta| {
+                let q = meta.query_selector(q);
+                let prev = meta.query_advice(advice[i - 1], Rotation::cur());
+                let prev_next = meta.query_advice(advice[i - 1], Rotation::next());
+                let challenge = meta.query_challenge(challenges[i - 1]);
+                let cur = meta.query_advice(advice[i], Rotation::cur());
+
+                vec![q * (prev * challenge + prev_next - cur)]
+            });
+        }
+
+        Self {
+            q,
+            advice,
+            challenges,
+            instance,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct MyCircuit<F: FieldExt, const PHASES: usize> {
+    inputs: Value<[F; ROWS]>,
+}
+
+impl<F: FieldExt, const PHASES: usize> Circuit<F> for MyCircuit<F, PHASES> {
+    type Config = MyConfig<F, PHASES>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        MyConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // Unknown until the phase the challenge is usable after has been committed.
+        let challenges = config
+            .challenges
+            .iter()
+            .map(|challenge| layouter.get_challenge(*challenge))
+            .collect::<Vec<_>>();
+
+        let first = layouter.assign_region(
+            || "phases",
+            |mut region| {
+                for row in 0..ROWS - 1 {
+                    config.q.enable(&mut region, row)?;
+                }
+
+                let mut column = (0..ROWS)
+                    .map(|row| self.inputs.map(|inputs| inputs[row]))
+                    .collect::<Vec<_>>();
+
+                let mut first = None;
+                for (row, value) in column.iter().enumerate() {
+                    let cell =
+                        region.assign_advice(|| "input", config.advice[0], row, || *value)?;
+                    first.get_or_insert(cell);
+                }
+
+                for (advice, challenge) in config.advice.iter().skip(1).zip(challenges.iter()) {
+                    column = (0..ROWS)
+                        .map(|row| {
+                            let next = column
+                                .get(row + 1)
+                                .copied()
+                                .unwrap_or_else(|| Value::known(F::zero()));
+                            column[row] * *challenge + next
+                        })
+                        .collect();
+
+                    for (row, value) in column.iter().enumerate() {
+                        region.assign_advice(|| "derived", *advice, row, || *value)?;
+                    }
+                }
+
+                Ok(first.unwrap())
+            },
+        )?;
+
+        layouter.constrain_instance(first.cell(), config.instance, 0)
+    }
+}
+
+pub(crate) fn test_circuit_builder<const PHASES: usize>(
+    inputs: [Fp; ROWS],
+) -> MyCircuit<Fp, PHASES> {
+    MyCircuit {
+        inputs: Value::known(inputs),
+    }
+}
@@ -0,0 +1,207 @@
+use std::marker::PhantomData;
+
+use crate::{
+    arith::{common::ArithCommonChip, ecc::ArithEccChip, field::ArithFieldChip},
+    systems::halo2::{
+        multiopen::MultiOpenKind,
+        transcript::PoseidonTranscriptRead,
+        verify::{verify_single_proof_in_chip, CircuitProof, ProofData},
+    },
+    tests::systems::halo2::multi_phase_test::test_circuit::{test_circuit_builder, ROWS},
+    transcript::encode::Encode,
+};
+use halo2_proofs::{
+    arithmetic::{CurveAffine, Field},
+    poly::{
+        commitment::ParamsProver,
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG, ParamsVerifierKZG},
+            multiopen::{ProverGWC, ProverSHPLONK},
+        },
+    },
+};
+use halo2_proofs::{
+    plonk::{create_proof, keygen_pk, keygen_vk},
+    transcript::{Challenge255, PoseidonWrite},
+};
+use halo2curves::bn256::Fr as Fp;
+use halo2curves::bn256::{Bn256, G1Affine};
+use rand::{thread_rng, SeedableRng};
+use rand_pcg::Pcg32;
+use rand_xorshift::XorShiftRng;
+
+const K: u32 = 10;
+
+pub fn test_verify_single_proof_in_chip<
+    ScalarChip,
+    NativeChip,
+    EccChip,
+    EncodeChip: Encode<EccChip>,
+    const PHASES: usize,
+>(
+    nchip: &NativeChip,
+    schip: &ScalarChip,
+    pchip: &EccChip,
+    ctx: &mut <EccChip as ArithCommonChip>::Context,
+    kind: MultiOpenKind,
+) where
+    NativeChip: ArithFieldChip<Field = <G1Affine as CurveAffine>::ScalarExt>,
+    ScalarChip: ArithFieldChip<Field = <G1Affine as CurveAffine>::ScalarExt>,
+    EccChip: ArithEccChip<
+        Point = G1Affine,
+        Scalar = ScalarChip::Field,
+        Native = NativeChip::Field,
+        NativeChip = NativeChip,
+        ScalarChip = ScalarChip,
+        Error = halo2_proofs::plonk::Error,
+    >,
+{
+    fn random() -> Fp {
+        let seed = chrono::offset::Utc::now()
+            .timestamp_nanos()
+            .try_into()
+            .unwrap();
+        let rng = XorShiftRng::seed_from_u64(seed);
+        Fp::random(rng)
+    }
+    let mut test_rng = thread_rng();
+    let circuit = test_circuit_builder::<PHASES>([(); ROWS].map(|_| random()));
+    let params = ParamsKZG::<Bn256>::setup(K, &mut test_rng);
+    let vk = keygen_vk(&params, &circuit).expect("keygen_vk should not fail");
+
+    let inputs = [(); ROWS].map(|_| random());
+    let instances: &[&[&[_]]] = &[&[&[inputs[0]]]];
+    let circuit = test_circuit_builder::<PHASES>(inputs);
+    let pk = keygen_pk(&params, vk, &circuit).expect("keygen_pk should not fail");
+
+    let mut transcript = PoseidonWrite::<Vec<u8>, G1Affine, Challenge255<G1Affine>>::init(vec![]);
+    match kind {
+        MultiOpenKind::GWC => {
+            create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
+                &params,
+                &pk,
+                &[circuit],
+                instances,
+                Pcg32::seed_from_u64(0),
+                &mut transcript,
+            )
+        }
+        MultiOpenKind::SHPLONK => {
+            create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<Bn256>, _, _, _, _>(
+                &params,
+                &pk,
+                &[circuit],
+                instances,
+                Pcg32::seed_from_u64(0),
+                &mut transcript,
+            )
+        }
+    }
+    .expect("proof generation should not fail");
+    let proof = transcript.finalize();
+
+    let params_verifier: &ParamsVerifierKZG<Bn256> = params.verifier_params();
+
+    let transcript = PoseidonTranscriptRead::<_, G1Affine, _, EncodeChip, 9usize, 8usize>::new(
+        &proof[..],
+        ctx,
+        nchip,
+        8usize,
+        63usize,
+    )
+    .unwrap();
+
+    let pdata = ProofData {
+        instances: &instances
+            .iter()
+            .map(|x| x.iter().map(|y| y.to_vec()).collect::<Vec<Vec<Fp>>>())
+            .collect::<Vec<Vec<Vec<Fp>>>>(),
+        transcript,
+        key: format!("p{}", 0),
+        enabled: None,
+        _phantom: PhantomData,
+    };
+
+    let mut transcript = PoseidonTranscriptRead::<_, G1Affine, _, EncodeChip, 9usize, 8usize>::new(
+        &proof[..],
+        ctx,
+        nchip,
+        8usize,
+        63usize,
+    )
+    .unwrap();
+
+    verify_single_proof_in_chip(
+        ctx,
+        nchip,
+        schip,
+        pchip,
+        &mut CircuitProof {
+            name: format!("test_circuit_multi_phase_{}", PHASES),
+            vk: pk.get_vk(),
+            params: params_verifier,
+            proofs: vec![pdata],
+            scheme: kind,
+        },
+        &mut transcript,
+    )
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{
+        arith::{
+            ecc::MockEccChip,
+            field::{MockChipCtx, MockFieldChip},
+        },
+        transcript_encode::PoseidonEncode,
+    };
+    use halo2_proofs::plonk::Error;
+
+    #[test]
+    fn test_verify_single_proof_in_chip_code_two_phases() {
+        let nchip = MockFieldChip::default();
+        let schip = MockFieldChip::default();
+        let pchip = MockEccChip::default();
+        let ctx = &mut MockChipCtx::default();
+        test_verify_single_proof_in_chip::<
+            MockFieldChip<Fp, Error>,
+            MockFieldChip<Fp, Error>,
+            MockEccChip<G1Affine, Error>,
+            PoseidonEncode,
+            2,
+        >(&nchip, &schip, &pchip, ctx, MultiOpenKind::GWC);
+    }
+
+    #[test]
+    fn test_verify_single_proof_in_chip_code_three_phases() {
+        let nchip = MockFieldChip::default();
+        let schip = MockFieldChip::default();
+        let pchip = MockEccChip::default();
+        let ctx = &mut MockChipCtx::default();
+        test_verify_single_proof_in_chip::<
+            MockFieldChip<Fp, Error>,
+            MockFieldChip<Fp, Error>,
+            MockEccChip<G1Affine, Error>,
+            PoseidonEncode,
+            3,
+        >(&nchip, &schip, &pchip, ctx, MultiOpenKind::GWC);
+    }
+
+    #[test]
+    fn test_verify_single_proof_in_chip_code_three_phases_shplonk() {
+        let nchip = MockFieldChip::default();
+        let schip = MockFieldChip::default();
+        let pchip = MockEccChip::default();
+        let ctx = &mut MockChipCtx::default();
+        test_verify_single_proof_in_chip::<
+            MockFieldChip<Fp, Error>,
+            MockFieldChip<Fp, Error>,
+            MockEccChip<G1Affine, Error>,
+            PoseidonEncode,
+            3,
+        >(&nchip, &schip, &pchip, ctx, MultiOpenKind::SHPLONK);
+    }
+}
@@ -0,0 +1,316 @@
+use std::marker::PhantomData;
+
+use crate::{
+    arith::common::ArithCommonChip,
+    hash::poseidon::HALO2_TRANSCRIPT_SPEC,
+    mock::{
+        arith::{
+            ecc::MockEccChip,
+            field::{MockChipCtx, MockFieldChip},
+        },
+        transcript_encode::PoseidonEncode,
+    },
+    systems::halo2::{
+        multiopen::MultiOpenKind,
+        native::{
+            verify_aggregation_proofs_parallel, NativeCircuitProof, NativeCtx, NativeFieldChip,
+            NativeProofData,
+        },
+        transcript::{TranscriptKind, TranscriptSpecRead},
+        verify::{verify_aggregation_proofs_in_chip, CircuitProof, ProofData},
+    },
+    tests::systems::halo2::add_mul_test::test_circuit::test_circuit_builder,
+};
+use halo2_proofs::{
+    arithmetic::Field,
+    plonk::{create_proof, keygen_pk, keygen_vk, Error},
+    poly::{
+        commitment::ParamsProver,
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG, ParamsVerifierKZG},
+            multiopen::{ProverGWC, ProverSHPLONK},
+        },
+    },
+    transcript::{Challenge255, PoseidonWrite},
+};
+use halo2curves::bn256::Fr as Fp;
+use halo2curves::bn256::{Bn256, G1Affine};
+use rand::{thread_rng, SeedableRng};
+use rand_pcg::Pcg32;
+
+const K: u32 = 10;
+
+// Instances and transcripts of `n` add-mul proofs.
+fn add_mul_proofs(
+    params: &ParamsKZG<Bn256>,
+    kind: MultiOpenKind,
+    n: usize,
+) -> (Vec<Vec<Vec<Vec<Fp>>>>, Vec<Vec<u8>>) {
+    let mut test_rng = thread_rng();
+    let mut n_instances = vec![];
+    let mut n_proof = vec![];
+    let constant = Fp::from(7);
+    for _ in 0..n {
+        let a = Fp::random(&mut test_rng);
+        let b = Fp::random(&mut test_rng);
+        let c = constant * a.square() * b.square();
+        let circuit = test_circuit_builder(a, b);
+        let vk = keygen_vk(params, &circuit).expect("keygen_vk should not fail");
+        let pk = keygen_pk(params, vk, &circuit).expect("keygen_pk should not fail");
+        let instances: &[&[&[_]]] = &[&[&[c]]];
+        let mut transcript =
+            PoseidonWrite::<Vec<u8>, G1Affine, Challenge255<G1Affine>>::init(vec![]);
+
+        match kind {
+            MultiOpenKind::GWC => {
+                create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
+                    params,
+                    &pk,
+                    &[circuit],
+                    instances,
+                    Pcg32::seed_from_u64(0),
+                    &mut transcript,
+                )
+            }
+            MultiOpenKind::SHPLONK => {
+                create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<Bn256>, _, _, _, _>(
+                    params,
+                    &pk,
+                    &[circuit],
+                    instances,
+                    Pcg32::seed_from_u64(0),
+                    &mut transcript,
+                )
+            }
+        }
+        .expect("proof generation should not fail");
+        n_proof.push(transcript.finalize());
+        n_instances.push(vec![vec![vec![c]]]);
+    }
+
+    (n_instances, n_proof)
+}
+
+// Aggregates add-mul proofs, one per entry of `enabled`, with the parallel verifier and
+// with the in-chip verifier on the mock chips, the final pair and the instances must be
+// the same.
+pub fn test_verify_aggregation_proof_parallel(kind: MultiOpenKind, enabled: &[Option<bool>]) {
+    let params = ParamsKZG::<Bn256>::setup(K, thread_rng());
+    let vk = keygen_vk(&params, &test_circuit_builder(Fp::zero(), Fp::zero()))
+        .expect("keygen_vk should not fail");
+    let params_verifier: &ParamsVerifierKZG<Bn256> = params.verifier_params();
+    let (n_instances, n_proof) = add_mul_proofs(&params, kind, enabled.len());
+
+    let nchip = MockFieldChip::<Fp, Error>::default();
+    let schip = MockFieldChip::<Fp, Error>::default();
+    let pchip = MockEccChip::<G1Affine, Error>::default();
+    let ctx = &mut MockChipCtx::default();
+
+    let proofs = n_instances
+        .iter()
+        .enumerate()
+        .map(|(i, instances)| ProofData {
+            instances,
+            transcript: TranscriptSpecRead::<_, G1Affine, _, PoseidonEncode>::from_spec(
+                &n_proof[i][..],
+                ctx,
+                &nchip,
+                &HALO2_TRANSCRIPT_SPEC,
+            )
+            .unwrap(),
+            key: format!("p{}", i),
+            enabled: enabled[i]
+                .map(|enabled| schip.assign_var(ctx, Fp::from(enabled as u64)).unwrap()),
+            _phantom: PhantomData,
+        })
+        .collect();
+
+    let empty_vec = vec![];
+    let mut transcript = TranscriptSpecRead::<_, G1Affine, _, PoseidonEncode>::from_spec(
+        &empty_vec[..],
+        ctx,
+        &nchip,
+        &HALO2_TRANSCRIPT_SPEC,
+    )
+    .unwrap();
+    let (w_x, w_g, instances, _) = verify_aggregation_proofs_in_chip(
+        ctx,
+        &nchip,
+        &schip,
+        &pchip,
+        vec![CircuitProof {
+            name: String::from("test_circuit_add_mul"),
+            vk: &vk,
+            params: params_verifier,
+            proofs,
+            scheme: kind,
+        }],
+        &mut transcript,
+    )
+    .unwrap();
+
+    let proofs = n_instances
+        .iter()
+        .enumerate()
+        .map(|(i, instances)| NativeProofData {
+            instances,
+            transcript: &n_proof[i][..],
+            key: format!("p{}", i),
+            enabled: enabled[i],
+        })
+        .collect();
+
+    let nchip = NativeFieldChip::<Fp>::default();
+    let ctx = &mut NativeCtx::default();
+    let mut transcript = TranscriptSpecRead::<_, G1Affine, _, PoseidonEncode>::from_spec(
+        &empty_vec[..],
+        ctx,
+        &nchip,
+        &HALO2_TRANSCRIPT_SPEC,
+    )
+    .unwrap();
+    let native = verify_aggregation_proofs_parallel(
+        ctx,
+        &[NativeCircuitProof {
+            name: String::from("test_circuit_add_mul"),
+            vk: &vk,
+            params: params_verifier,
+            proofs,
+            scheme: kind,
+            transcript_kind: TranscriptKind::Poseidon,
+            poseidon_spec: HALO2_TRANSCRIPT_SPEC,
+        }],
+        &mut transcript,
+    )
+    .unwrap();
+
+    assert_eq!(native, (w_x, w_g, instances));
+}
+
+// A proof checked against other instances does not open its commitments, the in-chip
+// verifier must report it rather than return a pair that fails the final pairing.
+pub fn test_verify_aggregation_proof_wrong_instance(kind: MultiOpenKind) {
+    let params = ParamsKZG::<Bn256>::setup(K, thread_rng());
+    let vk = keygen_vk(&params, &test_circuit_builder(Fp::zero(), Fp::zero()))
+        .expect("keygen_vk should not fail");
+    let params_verifier: &ParamsVerifierKZG<Bn256> = params.verifier_params();
+    let (mut n_instances, n_proof) = add_mul_proofs(&params, kind, 1);
+    n_instances[0][0][0][0] += Fp::one();
+
+    let nchip = MockFieldChip::<Fp, Error>::default();
+    let schip = MockFieldChip::<Fp, Error>::default();
+    let pchip = MockEccChip::<G1Affine, Error>::default();
+    let ctx = &mut MockChipCtx::default();
+
+    let proofs = vec![ProofData {
+        instances: &n_instances[0],
+        transcript: TranscriptSpecRead::<_, G1Affine, _, PoseidonEncode>::from_spec(
+            &n_proof[0][..],
+            ctx,
+            &nchip,
+            &HALO2_TRANSCRIPT_SPEC,
+        )
+        .unwrap(),
+        key: String::from("p0"),
+        enabled: None,
+        _phantom: PhantomData,
+    }];
+
+    let empty_vec = vec![];
+    let mut transcript = TranscriptSpecRead::<_, G1Affine, _, PoseidonEncode>::from_spec(
+        &empty_vec[..],
+        ctx,
+        &nchip,
+        &HALO2_TRANSCRIPT_SPEC,
+    )
+    .unwrap();
+    let result = verify_aggregation_proofs_in_chip(
+        ctx,
+        &nchip,
+        &schip,
+        &pchip,
+        vec![CircuitProof {
+            name: String::from("test_circuit_add_mul"),
+            vk: &vk,
+            params: params_verifier,
+            proofs,
+            scheme: kind,
+        }],
+        &mut transcript,
+    );
+    assert!(matches!(result, Err(Error::Opening)));
+}
+
+// Without proofs there is no aggregated proof to evaluate, the verifier must fail
+// instead of panicking.
+pub fn test_verify_aggregation_proof_empty() {
+    let nchip = MockFieldChip::<Fp, Error>::default();
+    let schip = MockFieldChip::<Fp, Error>::default();
+    let pchip = MockEccChip::<G1Affine, Error>::default();
+    let ctx = &mut MockChipCtx::default();
+
+    let empty_vec = vec![];
+    let mut transcript = TranscriptSpecRead::<_, G1Affine, _, PoseidonEncode>::from_spec(
+        &empty_vec[..],
+        ctx,
+        &nchip,
+        &HALO2_TRANSCRIPT_SPEC,
+    )
+    .unwrap();
+    let result = verify_aggregation_proofs_in_chip::<Bn256, _, _, MultiOpenKind>(
+        ctx,
+        &nchip,
+        &schip,
+        &pchip,
+        vec![],
+        &mut transcript,
+    );
+    assert!(result.is_err());
+
+    let nchip = NativeFieldChip::<Fp>::default();
+    let ctx = &mut NativeCtx::default();
+    let mut transcript = TranscriptSpecRead::<_, G1Affine, _, PoseidonEncode>::from_spec(
+        &empty_vec[..],
+        ctx,
+        &nchip,
+        &HALO2_TRANSCRIPT_SPEC,
+    )
+    .unwrap();
+    let native = verify_aggregation_proofs_parallel::<Bn256, _>(ctx, &[], &mut transcript);
+    assert!(native.is_err());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_aggregation_proof_empty_batch() {
+        test_verify_aggregation_proof_empty();
+    }
+
+    #[test]
+    fn test_verify_aggregation_proof_parallel_gwc() {
+        test_verify_aggregation_proof_parallel(MultiOpenKind::GWC, &[None, None, None]);
+    }
+
+    #[test]
+    fn test_verify_aggregation_proof_parallel_shplonk() {
+        test_verify_aggregation_proof_parallel(MultiOpenKind::SHPLONK, &[None, None]);
+    }
+
+    #[test]
+    fn test_verify_aggregation_proof_wrong_instance_gwc() {
+        test_verify_aggregation_proof_wrong_instance(MultiOpenKind::GWC);
+    }
+
+    #[test]
+    fn test_verify_aggregation_proof_wrong_instance_shplonk() {
+        test_verify_aggregation_proof_wrong_instance(MultiOpenKind::SHPLONK);
+    }
+
+    #[test]
+    fn test_verify_aggregation_proof_parallel_padding() {
+        test_verify_aggregation_proof_parallel(MultiOpenKind::GWC, &[Some(true), Some(false)]);
+    }
+}
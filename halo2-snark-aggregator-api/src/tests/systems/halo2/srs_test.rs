@@ -0,0 +1,158 @@
+use std::marker::PhantomData;
+
+use crate::{
+    hash::poseidon::HALO2_TRANSCRIPT_SPEC,
+    mock::{
+        arith::{
+            ecc::MockEccChip,
+            field::{MockChipCtx, MockFieldChip},
+        },
+        transcript_encode::PoseidonEncode,
+    },
+    systems::halo2::{
+        multiopen::MultiOpenKind,
+        transcript::TranscriptSpecRead,
+        verify::{same_srs, verify_aggregation_proofs_in_chip, CircuitProof, ProofData},
+    },
+    tests::systems::halo2::add_mul_test::test_circuit::test_circuit_builder,
+};
+use group::{Curve, Group};
+use halo2_proofs::{
+    arithmetic::Field,
+    plonk::{create_proof, keygen_pk, keygen_vk, Error, VerifyingKey},
+    poly::{
+        commitment::Params,
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::ProverGWC,
+        },
+    },
+    transcript::{Challenge255, PoseidonWrite},
+};
+use halo2curves::bn256::Fr as Fp;
+use halo2curves::bn256::{Bn256, G1Affine};
+use halo2curves::pairing::{MillerLoopResult, MultiMillerLoop};
+use rand::{thread_rng, SeedableRng};
+use rand_pcg::Pcg32;
+
+const K: u32 = 11;
+
+fn add_mul_proof(
+    params: &ParamsKZG<Bn256>,
+) -> (VerifyingKey<G1Affine>, Vec<Vec<Vec<Fp>>>, Vec<u8>) {
+    let mut test_rng = thread_rng();
+    let a = Fp::random(&mut test_rng);
+    let b = Fp::random(&mut test_rng);
+    let c = Fp::from(7) * a.square() * b.square();
+    let circuit = test_circuit_builder(a, b);
+    let vk = keygen_vk(params, &circuit).expect("keygen_vk should not fail");
+    let pk = keygen_pk(params, vk.clone(), &circuit).expect("keygen_pk should not fail");
+    let mut transcript = PoseidonWrite::<Vec<u8>, G1Affine, Challenge255<G1Affine>>::init(vec![]);
+
+    create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
+        params,
+        &pk,
+        &[circuit],
+        &[&[&[c]]],
+        Pcg32::seed_from_u64(0),
+        &mut transcript,
+    )
+    .expect("proof generation should not fail");
+
+    (vk, vec![vec![vec![c]]], transcript.finalize())
+}
+
+// Aggregates one add-mul proof with params of k = K and one with params of k = K - 1.
+// The smaller params are cut from the same setup when `shared`, else from another one.
+pub fn test_aggregate_different_k(shared: bool) {
+    let mut test_rng = thread_rng();
+    let params = ParamsKZG::<Bn256>::setup(K, &mut test_rng);
+    let mut small_params = if shared {
+        params.clone()
+    } else {
+        ParamsKZG::<Bn256>::setup(K, &mut test_rng)
+    };
+    small_params.downsize(K - 1);
+
+    let all_params = [&params, &small_params];
+    let circuits = all_params
+        .iter()
+        .map(|params| add_mul_proof(params))
+        .collect::<Vec<_>>();
+
+    let nchip = MockFieldChip::<Fp, Error>::default();
+    let schip = MockFieldChip::<Fp, Error>::default();
+    let pchip = MockEccChip::<G1Affine, Error>::default();
+    let ctx = &mut MockChipCtx::default();
+
+    let circuit_proofs = circuits
+        .iter()
+        .zip(all_params.iter())
+        .enumerate()
+        .map(|(i, ((vk, instances, proof), params))| CircuitProof {
+            name: format!("test_circuit_add_mul_{}", i),
+            vk,
+            params: *params,
+            proofs: vec![ProofData {
+                instances,
+                transcript: TranscriptSpecRead::<_, G1Affine, _, PoseidonEncode>::from_spec(
+                    &proof[..],
+                    ctx,
+                    &nchip,
+                    &HALO2_TRANSCRIPT_SPEC,
+                )
+                .unwrap(),
+                key: format!("test_circuit_add_mul_{}_p0", i),
+                enabled: None,
+                _phantom: PhantomData,
+            }],
+            scheme: MultiOpenKind::GWC,
+        })
+        .collect();
+
+    let empty_vec = vec![];
+    let mut transcript = TranscriptSpecRead::<_, G1Affine, _, PoseidonEncode>::from_spec(
+        &empty_vec[..],
+        ctx,
+        &nchip,
+        &HALO2_TRANSCRIPT_SPEC,
+    )
+    .unwrap();
+    assert_eq!(same_srs(&params, &small_params), shared);
+
+    let (w_x, w_g, _, _) = verify_aggregation_proofs_in_chip(
+        ctx,
+        &nchip,
+        &schip,
+        &pchip,
+        circuit_proofs,
+        &mut transcript,
+    )
+    .unwrap();
+    let s_g2_prepared = <Bn256 as MultiMillerLoop>::G2Prepared::from(params.s_g2());
+    let n_g2_prepared = <Bn256 as MultiMillerLoop>::G2Prepared::from(-params.g2());
+    let success = bool::from(
+        Bn256::multi_miller_loop(&[
+            (&w_x.to_affine(), &s_g2_prepared),
+            (&w_g.to_affine(), &n_g2_prepared),
+        ])
+        .final_exponentiation()
+        .is_identity(),
+    );
+    assert_eq!(success, shared);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_different_k_shared_srs() {
+        test_aggregate_different_k(true);
+    }
+
+    #[test]
+    fn test_aggregate_different_srs() {
+        test_aggregate_different_k(false);
+    }
+}
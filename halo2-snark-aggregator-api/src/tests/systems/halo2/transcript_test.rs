@@ -0,0 +1,158 @@
+use std::{io, marker::PhantomData};
+
+use crate::{
+    mock::arith::{
+        ecc::MockEccChip,
+        field::{MockChipCtx, MockFieldChip},
+    },
+    systems::halo2::{
+        multiopen::MultiOpenKind,
+        verify::{verify_single_proof_in_chip, CircuitProof, ProofData},
+    },
+    tests::systems::halo2::add_mul_test::test_circuit::test_circuit_builder,
+    transcript::read::TranscriptRead,
+};
+use halo2_proofs::{
+    arithmetic::Field,
+    plonk::{create_proof, keygen_pk, keygen_vk, Error},
+    poly::{
+        commitment::ParamsProver,
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG, ParamsVerifierKZG},
+            multiopen::ProverGWC,
+        },
+    },
+    transcript::{Challenge255, TranscriptWrite},
+};
+use halo2curves::bn256::Fr as Fp;
+use halo2curves::bn256::{Bn256, G1Affine};
+use rand::{thread_rng, SeedableRng};
+use rand_pcg::Pcg32;
+
+const K: u32 = 10;
+
+// Proves the add-mul circuit with `writer` and verifies it in the mock chips with the
+// transcript built by `reader`, both sides must agree on the hash.
+pub fn test_verify_single_proof_with_transcript<W, T>(
+    mut writer: W,
+    finalize: impl FnOnce(W) -> Vec<u8>,
+    reader: impl Fn(io::Cursor<Vec<u8>>, &mut MockChipCtx, &MockFieldChip<Fp, Error>) -> T,
+) where
+    W: TranscriptWrite<G1Affine, Challenge255<G1Affine>>,
+    T: TranscriptRead<MockEccChip<G1Affine, Error>>,
+{
+    let mut test_rng = thread_rng();
+    let circuit = test_circuit_builder(Fp::random(&mut test_rng), Fp::random(&mut test_rng));
+    let params = ParamsKZG::<Bn256>::setup(K, &mut test_rng);
+    let vk = keygen_vk(&params, &circuit).expect("keygen_vk should not fail");
+
+    let constant = Fp::from(7);
+    let a = Fp::random(&mut test_rng);
+    let b = Fp::random(&mut test_rng);
+    let c = constant * a.square() * b.square();
+    let instances: &[&[&[_]]] = &[&[&[c]]];
+    let circuit = test_circuit_builder(a, b);
+    let pk = keygen_pk(&params, vk, &circuit).expect("keygen_pk should not fail");
+
+    create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
+        &params,
+        &pk,
+        &[circuit],
+        instances,
+        Pcg32::seed_from_u64(0),
+        &mut writer,
+    )
+    .expect("proof generation should not fail");
+    let proof = finalize(writer);
+
+    let params_verifier: &ParamsVerifierKZG<Bn256> = params.verifier_params();
+
+    let nchip = MockFieldChip::default();
+    let schip = MockFieldChip::default();
+    let pchip = MockEccChip::default();
+    let ctx = &mut MockChipCtx::default();
+
+    let instances = vec![vec![vec![c]]];
+    let pdata = ProofData {
+        instances: &instances,
+        transcript: reader(io::Cursor::new(proof.clone()), ctx, &nchip),
+        key: format!("p{}", 0),
+        enabled: None,
+        _phantom: PhantomData,
+    };
+
+    let mut transcript = reader(io::Cursor::new(proof), ctx, &nchip);
+
+    verify_single_proof_in_chip(
+        ctx,
+        &nchip,
+        &schip,
+        &pchip,
+        &mut CircuitProof {
+            name: String::from("test_circuit_add_mul"),
+            vk: pk.get_vk(),
+            params: params_verifier,
+            proofs: vec![pdata],
+            scheme: MultiOpenKind::GWC,
+        },
+        &mut transcript,
+    )
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hash::poseidon::{PoseidonSpec, HALO2_TRANSCRIPT_SPEC},
+        systems::halo2::transcript::check_transcript_spec,
+    };
+    use group::GroupEncoding;
+    use halo2_proofs::arithmetic::CurveAffine;
+
+    #[test]
+    fn test_check_transcript_spec() {
+        let proof = G1Affine::generator().to_bytes();
+
+        assert!(check_transcript_spec::<G1Affine>(proof.as_ref(), &HALO2_TRANSCRIPT_SPEC).is_ok());
+        assert!(check_transcript_spec::<G1Affine>(
+            proof.as_ref(),
+            &PoseidonSpec { r_f: 8, r_p: 57 }
+        )
+        .is_err());
+    }
+
+    #[cfg(feature = "blake2b")]
+    #[test]
+    fn test_verify_single_proof_in_chip_blake2b() {
+        use crate::{
+            mock::transcript_encode::BitsEncode,
+            systems::halo2::transcript::blake2b::Blake2bTranscriptRead,
+        };
+        use halo2_proofs::transcript::{Blake2bWrite, TranscriptWriterBuffer};
+
+        test_verify_single_proof_with_transcript(
+            Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]),
+            |writer| writer.finalize(),
+            |proof, ctx, nchip| {
+                Blake2bTranscriptRead::<_, G1Affine, _, BitsEncode>::new(proof, ctx, nchip).unwrap()
+            },
+        );
+    }
+
+    #[cfg(feature = "keccak")]
+    #[test]
+    fn test_verify_single_proof_in_chip_keccak() {
+        use crate::{
+            mock::transcript_encode::BitsEncode,
+            systems::halo2::transcript::keccak::KeccakTranscriptRead,
+            transcript::keccak::Keccak256Write,
+        };
+
+        test_verify_single_proof_with_transcript(
+            Keccak256Write::<_, G1Affine, Challenge255<_>>::init(vec![]),
+            |writer| writer.finalize(),
+            |proof, _, _| KeccakTranscriptRead::<_, G1Affine, _, BitsEncode>::new(proof),
+        );
+    }
+}
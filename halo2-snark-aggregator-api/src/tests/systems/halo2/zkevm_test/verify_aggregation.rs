@@ -5,6 +5,7 @@ use crate::transcript::encode::Encode;
 use crate::{
     arith::{common::ArithCommonChip, ecc::ArithEccChip, field::ArithFieldChip},
     systems::halo2::{
+        multiopen::MultiOpenKind,
         transcript::PoseidonTranscriptRead,
         verify::{verify_aggregation_proofs_in_chip, CircuitProof, ProofData},
     },
@@ -109,6 +110,7 @@ pub fn test_verify_aggregation_proof_in_chip<
             instances,
             transcript,
             key: format!("p{}", i),
+            enabled: None,
             _phantom: PhantomData,
         })
     }
@@ -136,6 +138,7 @@ pub fn test_verify_aggregation_proof_in_chip<
             vk: pk.get_vk(),
             params: params_verifier,
             proofs: proof_data_list,
+            scheme: MultiOpenKind::GWC,
         }],
         &mut transcript,
     )
@@ -3,6 +3,7 @@ use std::marker::PhantomData;
 use crate::{
     arith::{common::ArithCommonChip, ecc::ArithEccChip, field::ArithFieldChip},
     systems::halo2::{
+        multiopen::MultiOpenKind,
         transcript::PoseidonTranscriptRead,
         verify::{verify_single_proof_in_chip, CircuitProof, ProofData},
     },
@@ -98,6 +99,7 @@ pub fn test_verify_single_proof_in_chip<
             .collect::<Vec<Vec<Vec<Fr>>>>(),
         transcript,
         key: format!("p{}", 0),
+        enabled: None,
         _phantom: PhantomData,
     };
 
@@ -122,6 +124,7 @@ pub fn test_verify_single_proof_in_chip<
             vk: pk.get_vk(),
             params: params_verifier,
             proofs: vec![pdata],
+            scheme: MultiOpenKind::GWC,
         },
         &mut transcript,
     )
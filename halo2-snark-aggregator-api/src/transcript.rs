@@ -1,3 +1,4 @@
 pub mod encode;
+pub mod keccak;
 pub mod read;
 pub mod sha;
@@ -21,3 +21,29 @@ pub trait Encode<A: ArithEccChip> {
         v: &[A::AssignedNative],
     ) -> Result<A::AssignedScalar, A::Error>;
 }
+
+// Little-endian bits of the canonical representation of proof elements, for the
+// transcripts hashing bytes instead of native field elements.
+pub trait EncodeBits<A: ArithEccChip> {
+    // bits of the x and y coordinates, each as long as the repr of the base field
+    fn encode_point_bits(
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        pchip: &A,
+        v: &A::AssignedPoint,
+    ) -> Result<(Vec<A::AssignedNative>, Vec<A::AssignedNative>), A::Error>;
+    fn encode_scalar_bits(
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        v: &A::AssignedScalar,
+    ) -> Result<Vec<A::AssignedNative>, A::Error>;
+    // scalar of up to 512 bits reduced by the scalar modulus, as `from_bytes_wide`
+    fn decode_scalar_bits(
+        ctx: &mut A::Context,
+        nchip: &A::NativeChip,
+        schip: &A::ScalarChip,
+        v: &[A::AssignedNative],
+    ) -> Result<A::AssignedScalar, A::Error>;
+}
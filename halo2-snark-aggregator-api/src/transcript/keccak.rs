@@ -0,0 +1,177 @@
+use digest::Digest;
+use group::ff::PrimeField;
+use halo2_proofs::arithmetic::CurveAffine;
+use halo2_proofs::transcript::{
+    Challenge255, EncodedChallenge, Transcript, TranscriptRead, TranscriptWrite,
+};
+use halo2curves::Coordinates;
+use sha3::Keccak256;
+use std::io::{self, Read, Write};
+use std::marker::PhantomData;
+
+/// Prefix to a prover's message soliciting a challenge
+const KECCAK256_PREFIX_CHALLENGE: u8 = 0;
+/// First prefix to a challenge, the digest gives its lower 32 bytes
+const KECCAK256_PREFIX_CHALLENGE_LO: u8 = 10;
+/// Second prefix to a challenge, the digest gives its upper 32 bytes
+const KECCAK256_PREFIX_CHALLENGE_HI: u8 = 11;
+/// Prefix to a prover's message containing a curve point
+const KECCAK256_PREFIX_POINT: u8 = 1;
+/// Prefix to a prover's message containing a scalar
+const KECCAK256_PREFIX_SCALAR: u8 = 2;
+
+/// Keccak transcript of halo2 (`Keccak256Read` of later releases) for target proofs:
+/// points are compressed, elements are hashed in their little-endian repr after a
+/// single prefix byte, and the state is never reset.
+#[derive(Debug, Clone)]
+pub struct Keccak256Read<R: Read, C: CurveAffine, E: EncodedChallenge<C>> {
+    state: Keccak256,
+    reader: R,
+    _marker: PhantomData<(C, E)>,
+}
+
+impl<R: Read, C: CurveAffine, E: EncodedChallenge<C>> Keccak256Read<R, C, E> {
+    /// Initialize a transcript given an input buffer.
+    pub fn init(reader: R) -> Self {
+        Keccak256Read {
+            state: Keccak256::new(),
+            reader,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<R: Read, C: CurveAffine> TranscriptRead<C, Challenge255<C>>
+    for Keccak256Read<R, C, Challenge255<C>>
+{
+    fn read_point(&mut self) -> io::Result<C> {
+        let mut compressed = C::Repr::default();
+        self.reader.read_exact(compressed.as_mut())?;
+        let point: C = Option::from(C::from_bytes(&compressed)).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::Other, "invalid point encoding in proof")
+        })?;
+        self.common_point(point)?;
+
+        Ok(point)
+    }
+
+    fn read_scalar(&mut self) -> io::Result<C::Scalar> {
+        let mut data = <C::Scalar as PrimeField>::Repr::default();
+        self.reader.read_exact(data.as_mut())?;
+        let scalar: C::Scalar = Option::from(C::Scalar::from_repr(data)).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "invalid field element encoding in proof",
+            )
+        })?;
+        self.common_scalar(scalar)?;
+
+        Ok(scalar)
+    }
+}
+
+impl<R: Read, C: CurveAffine> Transcript<C, Challenge255<C>>
+    for Keccak256Read<R, C, Challenge255<C>>
+{
+    fn squeeze_challenge(&mut self) -> Challenge255<C> {
+        squeeze_challenge(&mut self.state)
+    }
+
+    fn common_point(&mut self, point: C) -> io::Result<()> {
+        common_point(&mut self.state, point)
+    }
+
+    fn common_scalar(&mut self, scalar: C::Scalar) -> io::Result<()> {
+        common_scalar::<C>(&mut self.state, scalar);
+        Ok(())
+    }
+}
+
+/// Writer of `Keccak256Read`.
+#[derive(Debug, Clone)]
+pub struct Keccak256Write<W: Write, C: CurveAffine, E: EncodedChallenge<C>> {
+    state: Keccak256,
+    writer: W,
+    _marker: PhantomData<(C, E)>,
+}
+
+impl<W: Write, C: CurveAffine, E: EncodedChallenge<C>> Keccak256Write<W, C, E> {
+    /// Initialize a transcript given an output buffer.
+    pub fn init(writer: W) -> Self {
+        Keccak256Write {
+            state: Keccak256::new(),
+            writer,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Conclude the interaction and return the output buffer (writer).
+    pub fn finalize(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write, C: CurveAffine> TranscriptWrite<C, Challenge255<C>>
+    for Keccak256Write<W, C, Challenge255<C>>
+{
+    fn write_point(&mut self, point: C) -> io::Result<()> {
+        self.common_point(point)?;
+        let compressed = point.to_bytes();
+        self.writer.write_all(compressed.as_ref())
+    }
+
+    fn write_scalar(&mut self, scalar: C::Scalar) -> io::Result<()> {
+        self.common_scalar(scalar)?;
+        let data = scalar.to_repr();
+        self.writer.write_all(data.as_ref())
+    }
+}
+
+impl<W: Write, C: CurveAffine> Transcript<C, Challenge255<C>>
+    for Keccak256Write<W, C, Challenge255<C>>
+{
+    fn squeeze_challenge(&mut self) -> Challenge255<C> {
+        squeeze_challenge(&mut self.state)
+    }
+
+    fn common_point(&mut self, point: C) -> io::Result<()> {
+        common_point(&mut self.state, point)
+    }
+
+    fn common_scalar(&mut self, scalar: C::Scalar) -> io::Result<()> {
+        common_scalar::<C>(&mut self.state, scalar);
+        Ok(())
+    }
+}
+
+fn squeeze_challenge<C: CurveAffine>(state: &mut Keccak256) -> Challenge255<C> {
+    state.update(&[KECCAK256_PREFIX_CHALLENGE]);
+
+    let mut state_lo = state.clone();
+    let mut state_hi = state.clone();
+    state_lo.update(&[KECCAK256_PREFIX_CHALLENGE_LO]);
+    state_hi.update(&[KECCAK256_PREFIX_CHALLENGE_HI]);
+
+    let mut bytes = state_lo.finalize().to_vec();
+    bytes.extend_from_slice(state_hi.finalize().as_slice());
+    Challenge255::<C>::new(&bytes.try_into().unwrap())
+}
+
+fn common_point<C: CurveAffine>(state: &mut Keccak256, point: C) -> io::Result<()> {
+    state.update(&[KECCAK256_PREFIX_POINT]);
+    let coords: Coordinates<C> = Option::from(point.coordinates()).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::Other,
+            "cannot write points at infinity to the transcript",
+        )
+    })?;
+    state.update(coords.x().to_repr().as_ref());
+    state.update(coords.y().to_repr().as_ref());
+
+    Ok(())
+}
+
+fn common_scalar<C: CurveAffine>(state: &mut Keccak256, scalar: C::Scalar) {
+    state.update(&[KECCAK256_PREFIX_SCALAR]);
+    state.update(scalar.to_repr().as_ref());
+}
@@ -8,6 +8,7 @@ use halo2_proofs::transcript::Transcript;
 use halo2_proofs::transcript::TranscriptRead;
 use halo2_proofs::transcript::TranscriptWrite;
 use halo2curves::Coordinates;
+use sha3::Keccak256;
 use std::io::{self, Read, Write};
 use std::marker::PhantomData;
 
@@ -19,6 +20,27 @@ const SHA_PREFIX_POINT: u8 = 1;
 const SHA_PREFIX_SCALAR: u8 = 2;
 ///
 
+/// Digest used by `ShaRead` and `ShaWrite` for the proof of a verify circuit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TranscriptHash {
+    Sha256,
+    Keccak256,
+}
+
+impl Default for TranscriptHash {
+    fn default() -> Self {
+        TranscriptHash::Sha256
+    }
+}
+
+/// `ShaRead` with keccak-256, for verify circuit proofs checked by the solidity
+/// verifier: the `ShaRead` framing, with the digest of the EVM `keccak256` opcode. Halo2
+/// proofs with a keccak transcript are read by `keccak::Keccak256Read` instead.
+pub type ShaKeccakRead<R, C, E> = ShaRead<R, C, E, Keccak256>;
+
+/// `ShaWrite` with keccak-256, the writer of `ShaKeccakRead`.
+pub type ShaKeccakWrite<W, C, E> = ShaWrite<W, C, E, Keccak256>;
+
 #[derive(Debug, Clone)]
 pub struct ShaRead<R: Read, C: CurveAffine, E: EncodedChallenge<C>, D: Digest> {
     state: D,
@@ -168,11 +168,13 @@ fn setup_sample_circuit() -> (
 #[cfg(test)]
 mod evm_circ_benches {
     use crate::verify_circuit::{
-        calc_verify_circuit_instances, Halo2VerifierCircuit, SingleProofWitness,
+        calc_verify_circuit_instances, Halo2VerifierCircuit, SingleProofWitness, TranscriptKind,
     };
 
     use super::*;
     use halo2_proofs::dev::MockProver;
+    use halo2_snark_aggregator_api::hash::poseidon::HALO2_TRANSCRIPT_SPEC;
+    use halo2_snark_aggregator_api::systems::halo2::multiopen::MultiOpenKind;
 
     #[cfg_attr(not(feature = "benches"), ignore)]
     #[test]
@@ -199,7 +201,13 @@ mod evm_circ_benches {
             proofs: vec![SingleProofWitness {
                 instances: &target_circuit_instance,
                 transcript: &target_circuit_proof,
+                enabled: true,
             }],
+            has_accumulator: false,
+            kind: MultiOpenKind::GWC,
+            padding: false,
+            transcript_kind: TranscriptKind::Poseidon,
+            poseidon_spec: HALO2_TRANSCRIPT_SPEC,
         };
 
         let instances = calc_verify_circuit_instances(
@@ -208,7 +216,10 @@ mod evm_circ_benches {
             target_circuit_pk.get_vk(),
             &vec![instances1],
             &vec![proof1],
-        );
+            false,
+            MultiOpenKind::GWC,
+        )
+        .unwrap();
 
         let prover = match MockProver::run(K, &verify_circuit, vec![instances]) {
             Ok(prover) => prover,
@@ -110,7 +110,7 @@ impl<'a, 'b, C: CurveAffine> ArithEccChip for EccChip<'a, 'b, C> {
         lhs: &Self::AssignedScalar,
         rhs: &Self::AssignedPoint,
     ) -> Result<Self::AssignedPoint, Self::Error> {
-        self.chip.mul(ctx, &mut rhs.clone(), lhs)
+        self.chip.glv_mul(ctx, &mut rhs.clone(), lhs)
     }
 
     fn scalar_mul_constant(
@@ -128,6 +128,6 @@ impl<'a, 'b, C: CurveAffine> ArithEccChip for EccChip<'a, 'b, C> {
         mut points: Vec<Self::AssignedPoint>,
         scalars: Vec<Self::AssignedScalar>,
     ) -> Result<Self::AssignedPoint, Self::Error> {
-        self.chip.shamir(ctx, &mut points, &scalars)
+        self.chip.glv_msm(ctx, &mut points, &scalars)
     }
 }
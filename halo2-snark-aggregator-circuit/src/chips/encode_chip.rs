@@ -1,9 +1,21 @@
 use super::ecc_chip::EccChip;
-use halo2_ecc_circuit_lib::chips::ecc_chip::EccChipOps;
+use halo2curves::group::ff::{Field, PrimeField};
+use halo2_ecc_circuit_lib::{
+    chips::{
+        ecc_chip::EccChipOps,
+        integer_chip::{AssignedInteger, IntegerChipOps},
+    },
+    five::integer_chip::LIMB_COMMON_WIDTH,
+    gates::{
+        base_gate::{AssignedValue, Context},
+        bits_gate::{BitsGate, BitsGateOps},
+    },
+    utils::field_to_bn,
+};
 use halo2_proofs::{arithmetic::CurveAffine, plonk::Error};
 use halo2_snark_aggregator_api::{
-    arith::{common::ArithCommonChip, ecc::ArithEccChip},
-    transcript::encode::Encode,
+    arith::{bits::ArithBitsChip, common::ArithCommonChip, ecc::ArithEccChip},
+    transcript::encode::{Encode, EncodeBits},
 };
 use std::marker::PhantomData;
 
@@ -49,3 +61,82 @@ impl<'a, 'b, C: CurveAffine> Encode<EccChip<'a, 'b, C>> for PoseidonEncodeChip<E
         Ok(v[0])
     }
 }
+
+fn repr_bits<F: PrimeField>() -> usize {
+    F::Repr::default().as_ref().len() * 8
+}
+
+pub struct BitsEncodeChip<A: ArithEccChip> {
+    _phantom: PhantomData<A>,
+}
+
+impl<'a, 'b, C: CurveAffine> BitsEncodeChip<EccChip<'a, 'b, C>> {
+    // The canonical bits of `v` are recomposed into limbs, which must equal `v` modulo
+    // the base field.
+    fn integer_bits(
+        ctx: &mut Context<'b, C::ScalarExt>,
+        pchip: &EccChip<'a, 'b, C>,
+        v: &AssignedInteger<C::Base, C::ScalarExt>,
+    ) -> Result<Vec<AssignedValue<C::ScalarExt>>, Error> {
+        let integer_chip = pchip.chip.integer_chip();
+        let bits_gate = BitsGate::new(pchip.chip.base_gate());
+        let w_modulus = field_to_bn(&-C::Base::one()) + 1u64;
+
+        let w = integer_chip.get_w(v)?;
+        let bits = bits_gate.assign_bits(ctx, &field_to_bn(&w), repr_bits::<C::Base>())?;
+        bits_gate.assert_bits_less_than(ctx, &bits, &w_modulus)?;
+
+        let limbs = bits
+            .chunks(LIMB_COMMON_WIDTH)
+            .map(|limb_bits| bits_gate.bits_to_num(ctx, limb_bits))
+            .collect::<Result<Vec<_>, _>>()?;
+        integer_chip.assert_equal(ctx, v, &AssignedInteger::new(limbs, 0))?;
+
+        Ok(bits.into_iter().map(|bit| bit.into()).collect())
+    }
+}
+
+impl<'a, 'b, C: CurveAffine> EncodeBits<EccChip<'a, 'b, C>> for BitsEncodeChip<EccChip<'a, 'b, C>> {
+    fn encode_point_bits(
+        ctx: &mut <EccChip<'a, 'b, C> as ArithCommonChip>::Context,
+        _: &<EccChip<'a, 'b, C> as ArithEccChip>::NativeChip,
+        _: &<EccChip<'a, 'b, C> as ArithEccChip>::ScalarChip,
+        pchip: &EccChip<'a, 'b, C>,
+        v: &<EccChip<'a, 'b, C> as ArithEccChip>::AssignedPoint,
+    ) -> Result<
+        (
+            Vec<<EccChip<'a, 'b, C> as ArithEccChip>::AssignedNative>,
+            Vec<<EccChip<'a, 'b, C> as ArithEccChip>::AssignedNative>,
+        ),
+        Error,
+    > {
+        let x = Self::integer_bits(ctx, pchip, &v.x)?;
+        let y = Self::integer_bits(ctx, pchip, &v.y)?;
+
+        Ok((x, y))
+    }
+
+    fn encode_scalar_bits(
+        ctx: &mut <EccChip<'a, 'b, C> as ArithCommonChip>::Context,
+        nchip: &<EccChip<'a, 'b, C> as ArithEccChip>::NativeChip,
+        _: &<EccChip<'a, 'b, C> as ArithEccChip>::ScalarChip,
+        v: &<EccChip<'a, 'b, C> as ArithEccChip>::AssignedScalar,
+    ) -> Result<Vec<<EccChip<'a, 'b, C> as ArithEccChip>::AssignedNative>, Error> {
+        let mut bits = nchip.to_bits_canonical(ctx, v)?;
+        let zero = nchip.assign_zero(ctx)?;
+        bits.resize(repr_bits::<C::ScalarExt>(), zero);
+
+        Ok(bits)
+    }
+
+    fn decode_scalar_bits(
+        ctx: &mut <EccChip<'a, 'b, C> as ArithCommonChip>::Context,
+        nchip: &<EccChip<'a, 'b, C> as ArithEccChip>::NativeChip,
+        _: &<EccChip<'a, 'b, C> as ArithEccChip>::ScalarChip,
+        v: &[<EccChip<'a, 'b, C> as ArithEccChip>::AssignedNative],
+    ) -> Result<<EccChip<'a, 'b, C> as ArithEccChip>::AssignedScalar, Error> {
+        // Native and scalar fields are the same, so the recomposition is reduced
+        // the same way as `from_bytes_wide`.
+        nchip.from_bits(ctx, v)
+    }
+}
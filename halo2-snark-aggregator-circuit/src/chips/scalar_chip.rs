@@ -2,10 +2,15 @@ use std::marker::PhantomData;
 
 use halo2_ecc_circuit_lib::{
     five::base_gate::FiveColumnBaseGate,
-    gates::base_gate::{AssignedValue, BaseGateOps, Context},
+    gates::{
+        base_gate::{AssignedCondition, AssignedValue, BaseGateOps, Context},
+        bits_gate::{BitsGate, BitsGateOps},
+    },
 };
 use halo2_proofs::{arithmetic::FieldExt, plonk::Error};
-use halo2_snark_aggregator_api::arith::{common::ArithCommonChip, field::ArithFieldChip};
+use halo2_snark_aggregator_api::arith::{
+    bits::ArithBitsChip, common::ArithCommonChip, field::ArithFieldChip,
+};
 
 pub struct ScalarChip<'a, 'b, N: FieldExt>(&'a FiveColumnBaseGate<N>, PhantomData<&'b N>);
 
@@ -125,3 +130,59 @@ impl<'a, 'b, N: FieldExt> ArithFieldChip for ScalarChip<'a, 'b, N> {
         self.0.mul_add_constant(ctx, a, b, c)
     }
 }
+
+impl<'a, 'b, N: FieldExt> ArithBitsChip for ScalarChip<'a, 'b, N> {
+    fn to_bits(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedField,
+        bits: usize,
+    ) -> Result<Vec<Self::AssignedField>, Self::Error> {
+        let res = BitsGate::new(self.0).num_to_bits(ctx, a, bits)?;
+        Ok(res.into_iter().map(|bit| bit.into()).collect())
+    }
+
+    fn to_bits_canonical(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedField,
+    ) -> Result<Vec<Self::AssignedField>, Self::Error> {
+        let res = BitsGate::new(self.0).num_to_bits_canonical(ctx, a)?;
+        Ok(res.into_iter().map(|bit| bit.into()).collect())
+    }
+
+    fn from_bits(
+        &self,
+        ctx: &mut Self::Context,
+        bits: &[Self::AssignedField],
+    ) -> Result<Self::AssignedField, Self::Error> {
+        let bits: Vec<AssignedCondition<N>> = bits.iter().map(|bit| bit.into()).collect();
+        BitsGate::new(self.0).bits_to_num(ctx, &bits)
+    }
+
+    fn and(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedField,
+        b: &Self::AssignedField,
+    ) -> Result<Self::AssignedField, Self::Error> {
+        Ok(self.0.and(ctx, &a.into(), &b.into())?.into())
+    }
+
+    fn not(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedField,
+    ) -> Result<Self::AssignedField, Self::Error> {
+        Ok(self.0.not(ctx, &a.into())?.into())
+    }
+
+    fn xor(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedField,
+        b: &Self::AssignedField,
+    ) -> Result<Self::AssignedField, Self::Error> {
+        Ok(self.0.xor(ctx, &a.into(), &b.into())?.into())
+    }
+}
@@ -0,0 +1,77 @@
+use halo2_proofs::plonk::Error;
+use std::{fmt, io};
+
+#[derive(Debug)]
+pub enum AggregatorError {
+    // Reading or writing a file of the folder.
+    Io(io::Error),
+    // A file was read but its content is not a valid params, vkey or instance encoding.
+    Deserialize(String),
+    // A target proof ends early or does not decode with the expected transcript.
+    Transcript(io::Error),
+    // Instances of a target proof do not match the columns of its circuit.
+    InstanceShape(String),
+    // The final pair or the verify circuit proof does not pass the pairing check.
+    Pairing(String),
+    // Errors of keygen and proving.
+    Synthesis(Error),
+    // Params do not fit the circuit or do not come from the expected SRS.
+    Srs(String),
+    // A target proof failed its pre-verification, before the verify circuit is proved.
+    InvalidTargetProof {
+        name: String,
+        index: usize,
+        err: Box<AggregatorError>,
+    },
+    // The cli manifest does not parse or names circuits the cli was not built with.
+    Manifest(String),
+}
+
+impl fmt::Display for AggregatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregatorError::Io(err) => write!(f, "io error: {}", err),
+            AggregatorError::Deserialize(msg) => write!(f, "deserialization error: {}", msg),
+            AggregatorError::Transcript(err) => write!(f, "transcript error: {}", err),
+            AggregatorError::InstanceShape(msg) => write!(f, "instance shape mismatch: {}", msg),
+            AggregatorError::Pairing(msg) => write!(f, "pairing check failed: {}", msg),
+            AggregatorError::Synthesis(err) => write!(f, "synthesis error: {:?}", err),
+            AggregatorError::Srs(msg) => write!(f, "srs error: {}", msg),
+            AggregatorError::InvalidTargetProof { name, index, err } => {
+                write!(f, "proof {} of {} does not verify: {}", index, name, err)
+            }
+            AggregatorError::Manifest(msg) => write!(f, "manifest error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AggregatorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AggregatorError::Io(err) | AggregatorError::Transcript(err) => Some(err),
+            AggregatorError::InvalidTargetProof { err, .. } => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AggregatorError {
+    fn from(err: io::Error) -> Self {
+        AggregatorError::Io(err)
+    }
+}
+
+impl From<Error> for AggregatorError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Transcript(err) => AggregatorError::Transcript(err),
+            Error::InvalidInstances => {
+                AggregatorError::InstanceShape("instances do not match the circuit".to_owned())
+            }
+            Error::Opening => {
+                AggregatorError::Pairing("the proof does not open the commitments".to_owned())
+            }
+            err => AggregatorError::Synthesis(err),
+        }
+    }
+}
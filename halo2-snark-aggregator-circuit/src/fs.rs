@@ -1,7 +1,12 @@
-use crate::{sample_circuit::TargetCircuit, verify_circuit::Halo2VerifierCircuit};
+use crate::{
+    error::AggregatorError,
+    sample_circuit::TargetCircuit,
+    srs::load_params_srs,
+    verify_circuit::{Halo2VerifierCircuit, ProofTimings},
+};
 use halo2_proofs::{
     arithmetic::CurveAffine,
-    plonk::{keygen_vk, VerifyingKey},
+    plonk::{keygen_vk, ProvingKey, VerifyingKey},
     poly::{
         commitment::{CommitmentScheme, Params},
         kzg::commitment::{KZGCommitmentScheme, ParamsKZG},
@@ -12,34 +17,49 @@ use halo2curves::{
     pairing::MultiMillerLoop,
 };
 use halo2curves::{group::ff::PrimeField, pairing::Engine};
+use log::info;
+use sha2::{Digest, Sha256};
 use std::{
     fmt::Debug,
-    io::{Cursor, Read, Write},
+    io::{Cursor, ErrorKind, Read, Write},
     path::PathBuf,
 };
 
-pub fn read_file(folder: &mut PathBuf, filename: &str) -> Vec<u8> {
+pub fn read_file(folder: &mut PathBuf, filename: &str) -> Result<Vec<u8>, AggregatorError> {
     let mut buf = vec![];
 
     folder.push(filename);
-    let mut fd = std::fs::File::open(folder.as_path()).unwrap();
+    let fd = std::fs::File::open(folder.as_path());
+    folder.pop();
+
+    fd?.read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+pub fn write_file(
+    folder: &mut PathBuf,
+    filename: &str,
+    buf: &Vec<u8>,
+) -> Result<(), AggregatorError> {
+    folder.push(filename);
+    let fd = std::fs::File::create(folder.as_path());
     folder.pop();
 
-    fd.read_to_end(&mut buf).unwrap();
-    buf
+    fd?.write_all(buf)?;
+    Ok(())
 }
 
-pub fn write_file(folder: &mut PathBuf, filename: &str, buf: &Vec<u8>) {
+fn create_file(folder: &mut PathBuf, filename: &str) -> Result<std::fs::File, AggregatorError> {
     folder.push(filename);
-    let mut fd = std::fs::File::create(folder.as_path()).unwrap();
+    let fd = std::fs::File::create(folder.as_path());
     folder.pop();
 
-    fd.write_all(buf).unwrap();
+    Ok(fd?)
 }
 
 pub fn read_target_circuit_params<E: MultiMillerLoop, Circuit: TargetCircuit<E>>(
     folder: &mut PathBuf,
-) -> Vec<u8> {
+) -> Result<Vec<u8>, AggregatorError> {
     read_file(
         folder,
         &format!("sample_circuit_{}.params", Circuit::PARAMS_NAME),
@@ -48,16 +68,16 @@ pub fn read_target_circuit_params<E: MultiMillerLoop, Circuit: TargetCircuit<E>>
 
 pub fn load_target_circuit_params<E: MultiMillerLoop + Debug, Circuit: TargetCircuit<E>>(
     folder: &mut PathBuf,
-) -> ParamsKZG<E> {
-    KZGCommitmentScheme::<E>::read_params(&mut Cursor::new(
-        &read_target_circuit_params::<E, Circuit>(&mut folder.clone()),
-    ))
-    .unwrap()
+) -> Result<ParamsKZG<E>, AggregatorError> {
+    let buf = read_target_circuit_params::<E, Circuit>(&mut folder.clone())?;
+    KZGCommitmentScheme::<E>::read_params(&mut Cursor::new(&buf)).map_err(|err| {
+        AggregatorError::Deserialize(format!("params of {}: {}", Circuit::PARAMS_NAME, err))
+    })
 }
 
 pub fn read_target_circuit_vk<E: MultiMillerLoop, Circuit: TargetCircuit<E>>(
     folder: &mut PathBuf,
-) -> Vec<u8> {
+) -> Result<Vec<u8>, AggregatorError> {
     read_file(
         folder,
         &format!("sample_circuit_{}.vkey", Circuit::PARAMS_NAME),
@@ -67,25 +87,27 @@ pub fn read_target_circuit_vk<E: MultiMillerLoop, Circuit: TargetCircuit<E>>(
 pub fn load_target_circuit_vk<E: MultiMillerLoop + Debug, Circuit: TargetCircuit<E>>(
     folder: &mut PathBuf,
     params: &ParamsKZG<E>,
-) -> VerifyingKey<E::G1Affine> {
+) -> Result<VerifyingKey<E::G1Affine>, AggregatorError> {
     if Circuit::READABLE_VKEY {
         VerifyingKey::<E::G1Affine>::read::<_, Circuit::Circuit, E, _>(
-            &mut Cursor::new(&read_target_circuit_vk::<E, Circuit>(&mut folder.clone())),
-            &load_target_circuit_params::<E, Circuit>(&mut folder.clone()),
+            &mut Cursor::new(&read_target_circuit_vk::<E, Circuit>(&mut folder.clone())?),
+            &load_target_circuit_params::<E, Circuit>(&mut folder.clone())?,
         )
-        .unwrap()
+        .map_err(|err| {
+            AggregatorError::Deserialize(format!("vkey of {}: {}", Circuit::PARAMS_NAME, err))
+        })
     } else {
         let circuit = Circuit::Circuit::default();
 
-        keygen_vk::<E::G1Affine, _, Circuit::Circuit>(params, &circuit)
-            .expect("keygen_vk should not fail")
+        let vk = keygen_vk::<E::G1Affine, _, Circuit::Circuit>(params, &circuit)?;
+        Ok(vk)
     }
 }
 
 pub fn load_target_circuit_instance<Circuit: TargetCircuit<Bn256>>(
     folder: &mut PathBuf,
     index: usize,
-) -> Vec<u8> {
+) -> Result<Vec<u8>, AggregatorError> {
     read_file(
         folder,
         &format!("sample_circuit_instance_{}{}.data", Circuit::NAME, index),
@@ -95,109 +117,239 @@ pub fn load_target_circuit_instance<Circuit: TargetCircuit<Bn256>>(
 pub fn load_target_circuit_proof<Circuit: TargetCircuit<Bn256>>(
     folder: &mut PathBuf,
     index: usize,
-) -> Vec<u8> {
+) -> Result<Vec<u8>, AggregatorError> {
     read_file(
         folder,
         &format!("sample_circuit_proof_{}{}.data", Circuit::NAME, index),
     )
 }
 
-pub fn read_verify_circuit_params(folder: &mut PathBuf) -> Vec<u8> {
+// Proofs are numbered from 0, the count stops at the first missing index.
+pub fn count_target_circuit_proofs<Circuit: TargetCircuit<Bn256>>(folder: &mut PathBuf) -> usize {
+    let mut n = 0;
+    loop {
+        folder.push(format!("sample_circuit_proof_{}{}.data", Circuit::NAME, n));
+        let exists = folder.exists();
+        folder.pop();
+
+        if !exists {
+            return n;
+        }
+        n += 1;
+    }
+}
+
+// SRS imported into the folder, shared by the target circuits and the verify circuit.
+pub fn srs_params_path(folder: &PathBuf) -> Option<PathBuf> {
+    let path = folder.join("srs.params");
+    if path.exists() {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+pub fn load_srs_params<E: MultiMillerLoop + Debug>(
+    folder: &mut PathBuf,
+    k: u32,
+) -> Result<Option<ParamsKZG<E>>, AggregatorError> {
+    match srs_params_path(folder) {
+        Some(path) => Ok(Some(load_params_srs(&mut std::fs::File::open(path)?, k)?)),
+        None => Ok(None),
+    }
+}
+
+pub fn write_srs_params(
+    folder: &mut PathBuf,
+    params: &ParamsKZG<Bn256>,
+) -> Result<(), AggregatorError> {
+    let mut fd = create_file(folder, "srs.params")?;
+    params.write(&mut fd)?;
+    Ok(())
+}
+
+pub fn read_verify_circuit_params(folder: &mut PathBuf) -> Result<Vec<u8>, AggregatorError> {
     read_file(folder, "verify_circuit.params")
 }
 
-pub fn load_verify_circuit_params(folder: &mut PathBuf) -> ParamsKZG<Bn256> {
+pub fn load_verify_circuit_params(
+    folder: &mut PathBuf,
+) -> Result<ParamsKZG<Bn256>, AggregatorError> {
     KZGCommitmentScheme::<Bn256>::read_params(&mut Cursor::new(&read_verify_circuit_params(
         &mut folder.clone(),
-    )))
-    .unwrap()
+    )?))
+    .map_err(|err| AggregatorError::Deserialize(format!("verify circuit params: {}", err)))
 }
 
-pub fn read_verify_circuit_vk(folder: &mut PathBuf) -> Vec<u8> {
+pub fn read_verify_circuit_vk(folder: &mut PathBuf) -> Result<Vec<u8>, AggregatorError> {
     read_file(folder, "verify_circuit.vkey")
 }
 
-pub fn load_verify_circuit_vk(folder: &mut PathBuf) -> VerifyingKey<G1Affine> {
+pub fn load_verify_circuit_vk(
+    folder: &mut PathBuf,
+) -> Result<VerifyingKey<G1Affine>, AggregatorError> {
     VerifyingKey::<G1Affine>::read::<_, Halo2VerifierCircuit<'_, Bn256>, Bn256, _>(
-        &mut Cursor::new(&read_verify_circuit_vk(&mut folder.clone())),
-        &load_verify_circuit_params(&mut folder.clone()),
+        &mut Cursor::new(&read_verify_circuit_vk(&mut folder.clone())?),
+        &load_verify_circuit_params(&mut folder.clone())?,
     )
-    .unwrap()
+    .map_err(|err| AggregatorError::Deserialize(format!("verify circuit vkey: {}", err)))
 }
 
-pub fn read_verify_circuit_instance(folder: &mut PathBuf) -> Vec<u8> {
+// Sha256 of the encoding of `vk`, it keys the cached proving key of the verify circuit.
+fn vk_hash(vk: &VerifyingKey<G1Affine>) -> Result<Vec<u8>, AggregatorError> {
+    let mut buf = vec![];
+    vk.write(&mut buf)?;
+    Ok(Sha256::digest(&buf).to_vec())
+}
+
+// The cached proving key of the verify circuit, `None` when there is no cache or when
+// it was written for another vkey than `vk`, e.g. after a new verify setup.
+pub fn load_verify_circuit_pk(
+    folder: &mut PathBuf,
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+) -> Result<Option<ProvingKey<G1Affine>>, AggregatorError> {
+    let buf = match read_file(folder, "verify_circuit.pk") {
+        Ok(buf) => buf,
+        Err(AggregatorError::Io(err)) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    let hash = vk_hash(vk)?;
+    if buf.len() < hash.len() || buf[..hash.len()] != hash[..] {
+        info!("cached verify circuit pk does not match the vkey, it is regenerated");
+        return Ok(None);
+    }
+
+    ProvingKey::<G1Affine>::read::<_, Halo2VerifierCircuit<'_, Bn256>, Bn256, _>(
+        &mut Cursor::new(&buf[hash.len()..]),
+        params,
+    )
+    .map(Some)
+    .map_err(|err| AggregatorError::Deserialize(format!("verify circuit pk: {}", err)))
+}
+
+pub fn read_verify_circuit_instance(folder: &mut PathBuf) -> Result<Vec<u8>, AggregatorError> {
     read_file(folder, "verify_circuit_instance.data")
 }
 
-fn load_instances<E: MultiMillerLoop>(buf: &[u8]) -> Vec<Vec<Vec<E::Scalar>>> {
+// Instances of a single column, as written by `sample_circuit_random_run`.
+pub fn load_instances<E: MultiMillerLoop>(
+    buf: &[u8],
+) -> Result<Vec<Vec<Vec<E::Scalar>>>, AggregatorError> {
     let mut ret = vec![];
     let cursor = &mut std::io::Cursor::new(buf);
     let mut scalar_bytes = <<E as Engine>::Scalar as PrimeField>::Repr::default();
+    let repr_len = scalar_bytes.as_ref().len();
+    if buf.len() % repr_len != 0 {
+        return Err(AggregatorError::Deserialize(format!(
+            "{} bytes of instances, not a multiple of the {} bytes of a scalar",
+            buf.len(),
+            repr_len
+        )));
+    }
 
     while cursor.read_exact(scalar_bytes.as_mut()).is_ok() {
-        ret.push(<E::Scalar as PrimeField>::from_repr(scalar_bytes).unwrap())
+        let scalar =
+            Option::from(<E::Scalar as PrimeField>::from_repr(scalar_bytes)).ok_or_else(|| {
+                AggregatorError::Deserialize(format!("invalid scalar at instance {}", ret.len()))
+            })?;
+        ret.push(scalar)
     }
 
-    vec![vec![ret]]
+    Ok(vec![vec![ret]])
 }
 
-pub fn load_verify_circuit_instance(folder: &mut PathBuf) -> Vec<Vec<Vec<Fr>>> {
-    let instances = read_verify_circuit_instance(&mut folder.clone());
+pub fn load_verify_circuit_instance(
+    folder: &mut PathBuf,
+) -> Result<Vec<Vec<Vec<Fr>>>, AggregatorError> {
+    let instances = read_verify_circuit_instance(&mut folder.clone())?;
     load_instances::<Bn256>(&instances)
 }
 
-pub fn load_verify_circuit_proof(folder: &mut PathBuf) -> Vec<u8> {
+pub fn load_verify_circuit_proof(folder: &mut PathBuf) -> Result<Vec<u8>, AggregatorError> {
     read_file(folder, "verify_circuit_proof.data")
 }
 
-pub fn write_verify_circuit_params(folder: &mut PathBuf, verify_circuit_params: &ParamsKZG<Bn256>) {
-    folder.push("verify_circuit.params");
-    let mut fd = std::fs::File::create(folder.as_path()).unwrap();
-    folder.pop();
-
-    verify_circuit_params.write(&mut fd).unwrap();
+pub fn write_verify_circuit_params(
+    folder: &mut PathBuf,
+    verify_circuit_params: &ParamsKZG<Bn256>,
+) -> Result<(), AggregatorError> {
+    let mut fd = create_file(folder, "verify_circuit.params")?;
+    verify_circuit_params.write(&mut fd)?;
+    Ok(())
 }
 
-pub fn write_verify_circuit_vk(folder: &mut PathBuf, verify_circuit_vk: &VerifyingKey<G1Affine>) {
-    folder.push("verify_circuit.vkey");
-    let mut fd = std::fs::File::create(folder.as_path()).unwrap();
-    folder.pop();
+pub fn write_verify_circuit_vk(
+    folder: &mut PathBuf,
+    verify_circuit_vk: &VerifyingKey<G1Affine>,
+) -> Result<(), AggregatorError> {
+    let mut fd = create_file(folder, "verify_circuit.vkey")?;
+    verify_circuit_vk.write(&mut fd)?;
+    Ok(())
+}
 
-    verify_circuit_vk.write(&mut fd).unwrap();
+// Writes the sha256 of the vkey of `pk`, then `pk`, see `load_verify_circuit_pk`.
+pub fn write_verify_circuit_pk(
+    folder: &mut PathBuf,
+    verify_circuit_pk: &ProvingKey<G1Affine>,
+) -> Result<(), AggregatorError> {
+    let mut fd = create_file(folder, "verify_circuit.pk")?;
+    fd.write_all(&vk_hash(verify_circuit_pk.get_vk())?)?;
+    verify_circuit_pk.write(&mut fd)?;
+    Ok(())
 }
 
 pub fn write_verify_circuit_instance(
     folder: &mut PathBuf,
     buf: &[<G1Affine as CurveAffine>::ScalarExt],
-) {
-    folder.push("verify_circuit_instance.data");
-    let mut fd = std::fs::File::create(folder.as_path()).unwrap();
-    folder.pop();
+) -> Result<(), AggregatorError> {
+    let mut fd = create_file(folder, "verify_circuit_instance.data")?;
 
-    buf.iter().for_each(|x| {
-        fd.write_all(x.to_repr().as_ref()).unwrap();
-    });
+    for x in buf.iter() {
+        fd.write_all(x.to_repr().as_ref())?;
+    }
+    Ok(())
 }
 
-pub fn write_verify_circuit_final_pair(folder: &mut PathBuf, pair: &(G1Affine, G1Affine, Vec<Fr>)) {
-    folder.push("verify_circuit_final_pair.data");
-    let mut fd = std::fs::File::create(folder.as_path()).unwrap();
-    folder.pop();
+pub fn write_verify_circuit_final_pair(
+    folder: &mut PathBuf,
+    pair: &(G1Affine, G1Affine, Vec<Fr>),
+) -> Result<(), AggregatorError> {
+    let mut fd = create_file(folder, "verify_circuit_final_pair.data")?;
 
-    fd.write_all(pair.0.x.to_repr().as_ref()).unwrap();
-    fd.write_all(pair.0.y.to_repr().as_ref()).unwrap();
-    fd.write_all(pair.1.x.to_repr().as_ref()).unwrap();
-    fd.write_all(pair.1.y.to_repr().as_ref()).unwrap();
+    fd.write_all(pair.0.x.to_repr().as_ref())?;
+    fd.write_all(pair.0.y.to_repr().as_ref())?;
+    fd.write_all(pair.1.x.to_repr().as_ref())?;
+    fd.write_all(pair.1.y.to_repr().as_ref())?;
 
-    pair.2.iter().for_each(|scalar| {
-        fd.write_all(scalar.to_repr().as_ref()).unwrap();
-    })
+    for scalar in pair.2.iter() {
+        fd.write_all(scalar.to_repr().as_ref())?;
+    }
+    Ok(())
 }
 
-pub fn write_verify_circuit_proof(folder: &mut PathBuf, buf: &Vec<u8>) {
+pub fn write_verify_circuit_proof(
+    folder: &mut PathBuf,
+    buf: &Vec<u8>,
+) -> Result<(), AggregatorError> {
     write_file(folder, "verify_circuit_proof.data", buf)
 }
 
-pub fn write_verify_circuit_solidity(folder: &mut PathBuf, buf: &Vec<u8>) {
+pub fn write_verify_circuit_timings(
+    folder: &mut PathBuf,
+    timings: &ProofTimings,
+) -> Result<(), AggregatorError> {
+    write_file(
+        folder,
+        "verify_circuit_timings.json",
+        &timings.to_json().into_bytes(),
+    )
+}
+
+pub fn write_verify_circuit_solidity(
+    folder: &mut PathBuf,
+    buf: &Vec<u8>,
+) -> Result<(), AggregatorError> {
     write_file(folder, "verifier.sol", buf)
 }
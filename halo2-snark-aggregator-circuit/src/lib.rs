@@ -1,6 +1,8 @@
 pub mod chips;
+pub mod error;
 pub mod fs;
 pub mod sample_circuit;
+pub mod srs;
 pub mod verify_circuit;
 
 #[cfg(test)]
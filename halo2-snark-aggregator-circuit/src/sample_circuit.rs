@@ -1,56 +1,85 @@
-use halo2_proofs::plonk::keygen_vk;
 use halo2_proofs::plonk::{create_proof, keygen_pk};
+use halo2_proofs::plonk::{keygen_vk, Error, ProvingKey, VerifyingKey};
 use halo2_proofs::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG};
-use halo2_proofs::poly::kzg::multiopen::{ProverGWC, VerifierGWC};
+use halo2_proofs::poly::kzg::multiopen::{ProverGWC, ProverSHPLONK, VerifierGWC, VerifierSHPLONK};
 use halo2_proofs::poly::kzg::strategy::SingleStrategy;
-use halo2_proofs::transcript::PoseidonRead;
+use halo2_proofs::transcript::{Blake2bRead, Blake2bWrite, PoseidonRead};
 use halo2_proofs::transcript::{Challenge255, PoseidonWrite};
+use halo2_proofs::transcript::{
+    TranscriptRead, TranscriptReadBuffer, TranscriptWrite, TranscriptWriterBuffer,
+};
 use halo2_proofs::{plonk::Circuit, poly::commitment::Params};
+use halo2_snark_aggregator_api::hash::poseidon::{TranscriptSpec, HALO2_TRANSCRIPT_SPEC};
+use halo2_snark_aggregator_api::systems::halo2::multiopen::MultiOpenKind;
+use halo2_snark_aggregator_api::systems::halo2::transcript::TranscriptKind;
+use halo2_snark_aggregator_api::transcript::keccak::{Keccak256Read, Keccak256Write};
 use halo2curves::group::ff::PrimeField;
 use halo2curves::pairing::{Engine, MultiMillerLoop};
 use rand_core::OsRng;
 use std::fmt::Debug;
 use std::io::Write;
 
+use crate::error::AggregatorError;
+use crate::fs::load_srs_params;
 use crate::fs::load_target_circuit_params;
 use crate::fs::load_target_circuit_vk;
+use crate::srs::unsafe_srs;
 
 pub trait TargetCircuit<E: MultiMillerLoop> {
     const TARGET_CIRCUIT_K: u32;
     const PUBLIC_INPUT_SIZE: usize;
+    // Number of proofs generated by sample run. Setup and proving aggregate
+    // whatever proofs are found in the folder.
     const N_PROOFS: usize;
     const NAME: &'static str;
     const PARAMS_NAME: &'static str;
     const READABLE_VKEY: bool;
+    // Set when the circuit is a verify circuit, whose first instances are the limbs
+    // of its final pair. The pair is then folded into the new one.
+    const HAS_ACCUMULATOR: bool = false;
+    // Multiopen argument used by the prover of the target circuit.
+    const MULTIOPEN: MultiOpenKind = MultiOpenKind::GWC;
+    // Reserve N_PROOFS slots, so that the verify circuit accepts from 1 to N_PROOFS
+    // proofs. Empty slots get a disabled dummy proof.
+    const PADDING: bool = false;
+    // Transcript of the target circuit proofs, blake2b and keccak need the feature of
+    // the same name.
+    const TRANSCRIPT: TranscriptKind = TranscriptKind::Poseidon;
+    // Poseidon spec of the transcript of the target circuit proofs.
+    const POSEIDON_SPEC: TranscriptSpec = HALO2_TRANSCRIPT_SPEC;
 
     type Circuit: Circuit<<E as Engine>::Scalar> + Default;
 
     fn instance_builder() -> (Self::Circuit, Vec<Vec<<E as Engine>::Scalar>>);
-    fn load_instances(buf: &[u8]) -> Vec<Vec<Vec<<E as Engine>::Scalar>>>;
+    fn load_instances(buf: &[u8]) -> Result<Vec<Vec<Vec<<E as Engine>::Scalar>>>, AggregatorError>;
 }
 
 pub fn sample_circuit_setup<E: MultiMillerLoop + Debug, CIRCUIT: TargetCircuit<E>>(
     mut folder: std::path::PathBuf,
-) {
-    // TODO: Do not use setup in production
-    let params = ParamsKZG::<E>::unsafe_setup(CIRCUIT::TARGET_CIRCUIT_K);
+) -> Result<(), AggregatorError> {
+    let params = match load_srs_params::<E>(&mut folder.clone(), CIRCUIT::TARGET_CIRCUIT_K)? {
+        Some(params) => params,
+        None => unsafe_srs::<E>(CIRCUIT::TARGET_CIRCUIT_K),
+    };
 
     let circuit = CIRCUIT::Circuit::default();
-    let vk = keygen_vk(&params, &circuit).expect("keygen_vk should not fail");
+    let vk = keygen_vk(&params, &circuit)?;
 
     {
         folder.push(format!("sample_circuit_{}.params", CIRCUIT::PARAMS_NAME));
-        let mut fd = std::fs::File::create(folder.as_path()).unwrap();
+        let fd = std::fs::File::create(folder.as_path());
         folder.pop();
-        params.write(&mut fd).unwrap();
+        params.write(&mut fd?)?;
     }
 
     {
         folder.push(format!("sample_circuit_{}.vkey", CIRCUIT::PARAMS_NAME));
-        let mut fd = std::fs::File::create(folder.as_path()).unwrap();
+        let fd = std::fs::File::create(folder.as_path());
         folder.pop();
-        vk.write(&mut fd).unwrap();
+        vk.write(&mut fd?)?;
     }
+
+    Ok(())
 }
 
 pub fn sample_circuit_random_run<E: MultiMillerLoop + Debug, CIRCUIT: TargetCircuit<E>>(
@@ -58,30 +87,54 @@ pub fn sample_circuit_random_run<E: MultiMillerLoop + Debug, CIRCUIT: TargetCirc
     circuit: CIRCUIT::Circuit,
     instances: &[&[E::Scalar]],
     index: usize,
-) {
-    let params = load_target_circuit_params::<E, CIRCUIT>(&mut folder);
+) -> Result<(), AggregatorError> {
+    let params = load_target_circuit_params::<E, CIRCUIT>(&mut folder)?;
 
     let vk = if CIRCUIT::READABLE_VKEY {
-      load_target_circuit_vk::<E, CIRCUIT>(&mut folder, &params)
+      load_target_circuit_vk::<E, CIRCUIT>(&mut folder, &params)?
     } else {
-      keygen_vk(&params, &circuit).expect("keygen_vk should not fail")
+      keygen_vk(&params, &circuit)?
     };
 
-    let pk = keygen_pk(&params, vk, &circuit).expect("keygen_pk should not fail");
+    let pk = keygen_pk(&params, vk, &circuit)?;
 
     // let instances: &[&[&[C::Scalar]]] = &[&[&[constant * a.square() * b.square()]]];
     let instances: &[&[&[_]]] = &[instances];
-    let mut transcript = PoseidonWrite::<_, E::G1Affine, Challenge255<_>>::init(vec![]);
-    create_proof::<KZGCommitmentScheme<_>, ProverGWC<_>, _, _, _, _>(
-        &params,
-        &pk,
-        &[circuit],
-        instances,
-        OsRng,
-        &mut transcript,
-    )
-    .expect("proof generation should not fail");
-    let proof = transcript.finalize();
+    let proof = match CIRCUIT::TRANSCRIPT {
+        TranscriptKind::Poseidon => {
+            let mut transcript = PoseidonWrite::<_, E::G1Affine, Challenge255<_>>::init(vec![]);
+            create_sample_proof::<E, CIRCUIT, _>(
+                &params,
+                &pk,
+                circuit,
+                instances,
+                &mut transcript,
+            )?;
+            transcript.finalize()
+        }
+        TranscriptKind::Blake2b => {
+            let mut transcript = Blake2bWrite::<_, E::G1Affine, Challenge255<_>>::init(vec![]);
+            create_sample_proof::<E, CIRCUIT, _>(
+                &params,
+                &pk,
+                circuit,
+                instances,
+                &mut transcript,
+            )?;
+            transcript.finalize()
+        }
+        TranscriptKind::Keccak256 => {
+            let mut transcript = Keccak256Write::<_, E::G1Affine, Challenge255<_>>::init(vec![]);
+            create_sample_proof::<E, CIRCUIT, _>(
+                &params,
+                &pk,
+                circuit,
+                instances,
+                &mut transcript,
+            )?;
+            transcript.finalize()
+        }
+    };
 
     {
         folder.push(format!(
@@ -89,9 +142,9 @@ pub fn sample_circuit_random_run<E: MultiMillerLoop + Debug, CIRCUIT: TargetCirc
             CIRCUIT::NAME,
             index
         ));
-        let mut fd = std::fs::File::create(folder.as_path()).unwrap();
+        let fd = std::fs::File::create(folder.as_path());
         folder.pop();
-        fd.write_all(&proof).unwrap();
+        fd?.write_all(&proof)?;
     }
 
     {
@@ -100,25 +153,88 @@ pub fn sample_circuit_random_run<E: MultiMillerLoop + Debug, CIRCUIT: TargetCirc
             CIRCUIT::NAME,
             index
         ));
-        let mut fd = std::fs::File::create(folder.as_path()).unwrap();
+        let fd = std::fs::File::create(folder.as_path());
         folder.pop();
-        instances.iter().for_each(|l1| {
-            l1.iter().for_each(|l2| {
-                l2.iter().for_each(|c: &E::Scalar| {
-                    fd.write_all(c.to_repr().as_ref()).unwrap();
-                })
-            })
-        });
+        let mut fd = fd?;
+        for c in instances.iter().flat_map(|l1| l1.iter()).flat_map(|l2| l2.iter()) {
+            fd.write_all(c.to_repr().as_ref())?;
+        }
     }
 
-    let strategy = SingleStrategy::new(&params);
-    let mut transcript = PoseidonRead::<_, _, Challenge255<_>>::init(&proof[..]);
-    halo2_proofs::plonk::verify_proof::<_, VerifierGWC<_>, _, _, _>(
-        &params,
-        pk.get_vk(),
-        strategy,
-        instances,
-        &mut transcript,
-    )
-    .unwrap();
+    match CIRCUIT::TRANSCRIPT {
+        TranscriptKind::Poseidon => verify_sample_proof::<E, CIRCUIT, _>(
+            &params,
+            pk.get_vk(),
+            instances,
+            &mut PoseidonRead::<_, _, Challenge255<_>>::init(&proof[..]),
+        ),
+        TranscriptKind::Blake2b => verify_sample_proof::<E, CIRCUIT, _>(
+            &params,
+            pk.get_vk(),
+            instances,
+            &mut Blake2bRead::<_, _, Challenge255<_>>::init(&proof[..]),
+        ),
+        TranscriptKind::Keccak256 => verify_sample_proof::<E, CIRCUIT, _>(
+            &params,
+            pk.get_vk(),
+            instances,
+            &mut Keccak256Read::<_, _, Challenge255<_>>::init(&proof[..]),
+        ),
+    }?;
+
+    Ok(())
+}
+
+fn create_sample_proof<
+    E: MultiMillerLoop + Debug,
+    CIRCUIT: TargetCircuit<E>,
+    T: TranscriptWrite<E::G1Affine, Challenge255<E::G1Affine>>,
+>(
+    params: &ParamsKZG<E>,
+    pk: &ProvingKey<E::G1Affine>,
+    circuit: CIRCUIT::Circuit,
+    instances: &[&[&[E::Scalar]]],
+    transcript: &mut T,
+) -> Result<(), Error> {
+    match CIRCUIT::MULTIOPEN {
+        MultiOpenKind::GWC => create_proof::<KZGCommitmentScheme<_>, ProverGWC<_>, _, _, _, _>(
+            params,
+            pk,
+            &[circuit],
+            instances,
+            OsRng,
+            transcript,
+        ),
+        MultiOpenKind::SHPLONK => create_proof::<
+            KZGCommitmentScheme<_>,
+            ProverSHPLONK<_>,
+            _,
+            _,
+            _,
+            _,
+        >(params, pk, &[circuit], instances, OsRng, transcript),
+    }
+}
+
+fn verify_sample_proof<
+    E: MultiMillerLoop + Debug,
+    CIRCUIT: TargetCircuit<E>,
+    T: TranscriptRead<E::G1Affine, Challenge255<E::G1Affine>>,
+>(
+    params: &ParamsKZG<E>,
+    vk: &VerifyingKey<E::G1Affine>,
+    instances: &[&[&[E::Scalar]]],
+    transcript: &mut T,
+) -> Result<(), Error> {
+    let strategy = SingleStrategy::new(params);
+    match CIRCUIT::MULTIOPEN {
+        MultiOpenKind::GWC => halo2_proofs::plonk::verify_proof::<_, VerifierGWC<_>, _, _, _>(
+            params, vk, strategy, instances, transcript,
+        ),
+        MultiOpenKind::SHPLONK => {
+            halo2_proofs::plonk::verify_proof::<_, VerifierSHPLONK<_>, _, _, _>(
+                params, vk, strategy, instances, transcript,
+            )
+        }
+    }
 }
@@ -0,0 +1,246 @@
+use crate::error::AggregatorError;
+use halo2_proofs::{
+    arithmetic::{g_to_lagrange, CurveAffine, Field},
+    poly::{commitment::Params, kzg::commitment::ParamsKZG},
+};
+use halo2_snark_aggregator_api::systems::halo2::verify::same_srs;
+use halo2curves::{
+    bn256::{Bn256, Fq, Fq2, G1Affine, G2Affine},
+    group::{ff::PrimeField, prime::PrimeCurveAffine, GroupEncoding},
+    pairing::{Engine, MultiMillerLoop},
+};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{
+    fmt::Debug,
+    io::{self, Cursor, Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+// Section types of a snarkjs powers of tau file.
+const PTAU_HEADER: u32 = 1;
+const PTAU_TAU_G1: u32 = 2;
+const PTAU_TAU_G2: u32 = 3;
+
+// Development SRS, the toxic waste comes from a fixed seed so that every circuit of
+// a run shares the same g2 and s_g2.
+// TODO: Do not use this setup in production
+pub fn unsafe_srs<E: MultiMillerLoop + Debug>(k: u32) -> ParamsKZG<E> {
+    ParamsKZG::<E>::setup(k, StdRng::seed_from_u64(0))
+}
+
+fn ptau_error(msg: String) -> AggregatorError {
+    AggregatorError::Deserialize(format!("ptau: {}", msg))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, AggregatorError> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, AggregatorError> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+// snarkjs writes field elements little endian in montgomery form.
+fn read_fq<R: Read>(reader: &mut R) -> Result<Fq, AggregatorError> {
+    let mut repr = <Fq as PrimeField>::Repr::default();
+    reader.read_exact(repr.as_mut())?;
+    let x = Option::<Fq>::from(Fq::from_repr(repr))
+        .ok_or_else(|| ptau_error("field element out of range".to_owned()))?;
+    let r_inv = Fq::from(2).pow_vartime(&[256]).invert().unwrap();
+    Ok(x * r_inv)
+}
+
+fn read_g1<R: Read>(reader: &mut R) -> Result<G1Affine, AggregatorError> {
+    let x = read_fq(reader)?;
+    let y = read_fq(reader)?;
+    Option::from(G1Affine::from_xy(x, y))
+        .ok_or_else(|| ptau_error("G1 point not on curve".to_owned()))
+}
+
+fn read_g2<R: Read>(reader: &mut R) -> Result<G2Affine, AggregatorError> {
+    let x = Fq2 {
+        c0: read_fq(reader)?,
+        c1: read_fq(reader)?,
+    };
+    let y = Fq2 {
+        c0: read_fq(reader)?,
+        c1: read_fq(reader)?,
+    };
+    Ok(G2Affine { x, y })
+}
+
+// Offset of the data of each section, by section type.
+fn ptau_sections<R: Read + Seek>(reader: &mut R) -> Result<Vec<(u32, u64)>, AggregatorError> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != b"ptau" {
+        return Err(ptau_error("bad magic".to_owned()));
+    }
+    let _version = read_u32(reader)?;
+    let nsections = read_u32(reader)?;
+
+    let mut sections = vec![];
+    for _ in 0..nsections {
+        let section_type = read_u32(reader)?;
+        let size = read_u64(reader)?;
+        let offset = reader.stream_position()?;
+        sections.push((section_type, offset));
+        reader.seek(SeekFrom::Current(size as i64))?;
+    }
+    Ok(sections)
+}
+
+fn seek_section<R: Read + Seek>(
+    reader: &mut R,
+    sections: &[(u32, u64)],
+    section_type: u32,
+) -> Result<(), AggregatorError> {
+    let (_, offset) = sections
+        .iter()
+        .find(|(t, _)| *t == section_type)
+        .ok_or_else(|| ptau_error(format!("missing section {}", section_type)))?;
+    reader.seek(SeekFrom::Start(*offset))?;
+    Ok(())
+}
+
+// Reads the first 2^k powers of tau of a snarkjs ptau file into halo2 params. The
+// ptau must come from a ceremony of power at least k.
+pub fn read_ptau<R: Read + Seek>(
+    reader: &mut R,
+    k: u32,
+) -> Result<ParamsKZG<Bn256>, AggregatorError> {
+    let sections = ptau_sections(reader)?;
+
+    seek_section(reader, &sections, PTAU_HEADER)?;
+    let n8 = read_u32(reader)?;
+    if n8 as usize != <Fq as PrimeField>::Repr::default().as_ref().len() {
+        return Err(ptau_error(format!(
+            "{} bytes field elements, not bn256",
+            n8
+        )));
+    }
+    let mut q = <Fq as PrimeField>::Repr::default();
+    reader.read_exact(q.as_mut())?;
+    if q.as_ref() != fq_modulus().as_slice() {
+        return Err(ptau_error("base field is not the one of bn256".to_owned()));
+    }
+    let power = read_u32(reader)?;
+    if power < k {
+        return Err(ptau_error(format!(
+            "power {} is lower than k = {}",
+            power, k
+        )));
+    }
+
+    seek_section(reader, &sections, PTAU_TAU_G1)?;
+    let g = (0..1usize << k)
+        .map(|_| read_g1(reader))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    seek_section(reader, &sections, PTAU_TAU_G2)?;
+    let g2 = read_g2(reader)?;
+    let s_g2 = read_g2(reader)?;
+
+    if g[0] != G1Affine::generator() || g2 != G2Affine::generator() {
+        return Err(ptau_error(
+            "powers do not start from the generators".to_owned(),
+        ));
+    }
+    if Bn256::pairing(&g[1], &g2) != Bn256::pairing(&g[0], &s_g2) {
+        return Err(ptau_error("tau of G1 and G2 do not match".to_owned()));
+    }
+
+    params_from_powers(k, g, g2, s_g2)
+}
+
+fn fq_modulus() -> Vec<u8> {
+    // q = (q - 1) + 1, little endian
+    let mut q = (-Fq::one()).to_repr().as_ref().to_vec();
+    for byte in q.iter_mut() {
+        let (b, carry) = byte.overflowing_add(1);
+        *byte = b;
+        if !carry {
+            break;
+        }
+    }
+    q
+}
+
+// Params have no public constructor from the powers, they are built through their
+// serialized form.
+fn params_from_powers(
+    k: u32,
+    g: Vec<G1Affine>,
+    g2: G2Affine,
+    s_g2: G2Affine,
+) -> Result<ParamsKZG<Bn256>, AggregatorError> {
+    let g_lagrange = g_to_lagrange::<G1Affine>(g.iter().map(|p| p.to_curve()).collect(), k);
+
+    let mut buf = vec![];
+    buf.write_all(&k.to_le_bytes())?;
+    for p in g.iter().chain(g_lagrange.iter()) {
+        buf.write_all(p.to_bytes().as_ref())?;
+    }
+    buf.write_all(g2.to_bytes().as_ref())?;
+    buf.write_all(s_g2.to_bytes().as_ref())?;
+
+    ParamsKZG::<Bn256>::read(&mut Cursor::new(buf))
+        .map_err(|err| AggregatorError::Deserialize(format!("params from ptau: {}", err)))
+}
+
+// Cuts halo2 params down to 2^k points.
+pub fn downsize_params<E: MultiMillerLoop + Debug>(
+    mut params: ParamsKZG<E>,
+    k: u32,
+) -> Result<ParamsKZG<E>, AggregatorError> {
+    if params.k() < k {
+        return Err(AggregatorError::Srs(format!(
+            "params of k = {} cannot serve k = {}",
+            params.k(),
+            k
+        )));
+    }
+    if params.k() > k {
+        params.downsize(k);
+    }
+    Ok(params)
+}
+
+// Loads a ptau file, by its extension, or halo2 params and downsizes them to k.
+pub fn load_srs(path: &Path, k: u32) -> Result<ParamsKZG<Bn256>, AggregatorError> {
+    let mut fd = std::fs::File::open(path)?;
+    if path.extension().map_or(false, |ext| ext == "ptau") {
+        read_ptau(&mut io::BufReader::new(fd), k)
+    } else {
+        load_params_srs(&mut fd, k)
+    }
+}
+
+pub fn load_params_srs<E: MultiMillerLoop + Debug, R: Read>(
+    reader: &mut R,
+    k: u32,
+) -> Result<ParamsKZG<E>, AggregatorError> {
+    let params = ParamsKZG::<E>::read(reader)
+        .map_err(|err| AggregatorError::Deserialize(format!("srs params: {}", err)))?;
+    downsize_params(params, k)
+}
+
+// Params of circuits whose proofs end in the same pairing must come from the same
+// SRS, see `same_srs`.
+pub fn check_same_srs<E: MultiMillerLoop + Debug>(
+    name: &str,
+    params: &ParamsKZG<E>,
+    reference: &ParamsKZG<E>,
+) -> Result<(), AggregatorError> {
+    if !same_srs(params, reference) {
+        return Err(AggregatorError::Srs(format!(
+            "params of {} come from another SRS",
+            name
+        )));
+    }
+    Ok(())
+}
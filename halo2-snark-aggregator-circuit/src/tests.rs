@@ -3,3 +3,18 @@ mod mul_add;
 
 #[cfg(test)]
 mod lookup;
+
+#[cfg(test)]
+mod accumulator;
+
+#[cfg(test)]
+mod multi_phase;
+
+#[cfg(test)]
+mod srs;
+
+#[cfg(test)]
+mod final_pair;
+
+#[cfg(all(test, any(feature = "blake2b", feature = "keccak")))]
+mod bits_transcript;
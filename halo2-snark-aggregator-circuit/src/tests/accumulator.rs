@@ -0,0 +1,46 @@
+use crate::fs::load_instances;
+use crate::verify_circuit::{final_pair_to_instances, instances_to_accumulator};
+use halo2_proofs::arithmetic::Field;
+use halo2curves::bn256::{Bn256, Fr, G1Affine, G1};
+use halo2curves::group::{ff::PrimeField, Curve, Group};
+use rand_core::OsRng;
+
+#[test]
+fn test_accumulator_instances_round_trip() {
+    for _ in 0..16 {
+        let w_x = (G1::generator() * Fr::random(OsRng)).to_affine();
+        let w_g = (G1::generator() * Fr::random(OsRng)).to_affine();
+
+        let instances = final_pair_to_instances::<G1Affine, Bn256>(&(w_x, w_g, vec![]));
+        let (w_x_, w_g_) = instances_to_accumulator::<G1Affine>(&instances[..]).unwrap();
+
+        assert_eq!(w_x, w_x_);
+        assert_eq!(w_g, w_g_);
+    }
+}
+
+#[test]
+fn test_accumulator_invalid_instances() {
+    let w_x = (G1::generator() * Fr::random(OsRng)).to_affine();
+    let w_g = (G1::generator() * Fr::random(OsRng)).to_affine();
+    let mut instances = final_pair_to_instances::<G1Affine, Bn256>(&(w_x, w_g, vec![]));
+
+    assert!(instances_to_accumulator::<G1Affine>(&instances[..3]).is_err());
+
+    // x = 0 is not on bn256, 3 is not a square
+    instances[0] = Fr::zero();
+    instances[1] = Fr::zero();
+    assert!(instances_to_accumulator::<G1Affine>(&instances[..]).is_err());
+}
+
+#[test]
+fn test_load_instances_truncated() {
+    let buf = [Fr::one(), Fr::from(2)]
+        .iter()
+        .flat_map(|scalar| scalar.to_repr().as_ref().to_vec())
+        .collect::<Vec<_>>();
+
+    let instances = load_instances::<Bn256>(&buf).unwrap();
+    assert_eq!(instances, vec![vec![vec![Fr::one(), Fr::from(2)]]]);
+    assert!(load_instances::<Bn256>(&buf[..buf.len() - 1]).is_err());
+}
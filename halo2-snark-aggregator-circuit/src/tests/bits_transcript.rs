@@ -0,0 +1,228 @@
+use super::super::chips::{
+    ecc_chip::EccChip,
+    encode_chip::{BitsEncodeChip, PoseidonEncodeChip},
+    scalar_chip::ScalarChip,
+};
+use halo2_ecc_circuit_lib::{
+    chips::native_ecc_chip::NativeEccChip,
+    five::{
+        base_gate::{FiveColumnBaseGate, FiveColumnBaseGateConfig},
+        integer_chip::FiveColumnIntegerChip,
+        range_gate::FiveColumnRangeGate,
+    },
+    gates::{
+        base_gate::{BaseGateOps, Context},
+        range_gate::RangeGateConfig,
+    },
+};
+use halo2_proofs::{
+    arithmetic::Field,
+    circuit::{Layouter, SimpleFloorPlanner},
+    dev::MockProver,
+    plonk::{Circuit, ConstraintSystem, Error},
+    transcript::{Challenge255, Transcript, TranscriptWrite},
+};
+use halo2_snark_aggregator_api::{
+    hash::poseidon::HALO2_TRANSCRIPT_SPEC,
+    systems::halo2::transcript::{TargetTranscriptRead, TranscriptKind},
+    transcript::read::TranscriptRead,
+};
+use halo2curves::bn256::{Fq, Fr, G1Affine};
+use halo2curves::group::{Curve, Group};
+
+const COMMON_RANGE_BITS: usize = 17usize;
+
+#[derive(Clone)]
+struct TestBitsTranscriptConfig {
+    base_gate_config: FiveColumnBaseGateConfig,
+    range_gate_config: RangeGateConfig,
+}
+
+// Reads a point and two scalars of `proof` with the in-circuit reader of `kind`, and
+// constrains the challenges squeezed after the first and the second scalar.
+struct TestBitsTranscriptCircuit {
+    kind: TranscriptKind,
+    proof: Vec<u8>,
+    challenges: Vec<Fr>,
+}
+
+impl Circuit<Fr> for TestBitsTranscriptCircuit {
+    type Config = TestBitsTranscriptConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            kind: self.kind,
+            proof: self.proof.clone(),
+            challenges: self.challenges.clone(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let base_gate_config = FiveColumnBaseGate::<Fr>::configure(meta);
+        let range_gate_config = FiveColumnRangeGate::<'_, Fq, Fr, COMMON_RANGE_BITS>::configure(
+            meta,
+            &base_gate_config,
+        );
+        TestBitsTranscriptConfig {
+            base_gate_config,
+            range_gate_config,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let base_gate = FiveColumnBaseGate::new(config.base_gate_config);
+        let range_gate = FiveColumnRangeGate::<'_, Fq, Fr, COMMON_RANGE_BITS>::new(
+            config.range_gate_config,
+            &base_gate,
+        );
+        let integer_gate = FiveColumnIntegerChip::new(&range_gate);
+        let ecc_gate = NativeEccChip::new(&integer_gate);
+
+        range_gate
+            .init_table(&mut layouter, &integer_gate.helper.integer_modulus)
+            .unwrap();
+
+        let nchip = &ScalarChip::new(&base_gate);
+        let schip = nchip;
+        let pchip = &EccChip::new(&ecc_gate);
+
+        layouter.assign_region(
+            || "base",
+            |region| {
+                let base_offset = 0usize;
+                let mut aux = Context::new(region, base_offset);
+                let ctx = &mut aux;
+                ctx.in_shape_mode = base_gate.in_shape_mode(ctx)?;
+
+                let mut transcript = TargetTranscriptRead::<
+                    _,
+                    G1Affine,
+                    _,
+                    PoseidonEncodeChip<_>,
+                    BitsEncodeChip<_>,
+                >::new(
+                    self.kind,
+                    &self.proof[..],
+                    ctx,
+                    nchip,
+                    &HALO2_TRANSCRIPT_SPEC,
+                )?;
+
+                transcript.read_point(ctx, nchip, schip, pchip)?;
+                for challenge in self.challenges.iter() {
+                    transcript.read_scalar(ctx, nchip, schip)?;
+                    let assigned = transcript.squeeze_challenge_scalar(ctx, nchip, schip)?;
+                    base_gate.assert_constant(ctx, &assigned, *challenge)?;
+                }
+
+                Ok(())
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+// Proof bytes and challenges of the transcript read by `TestBitsTranscriptCircuit`.
+fn write_transcript<W: TranscriptWrite<G1Affine, Challenge255<G1Affine>>>(
+    mut writer: W,
+    finalize: impl FnOnce(W) -> Vec<u8>,
+) -> (Vec<u8>, Vec<Fr>) {
+    let point = (G1Affine::generator() * Fr::from(3)).to_affine();
+    writer.write_point(point).unwrap();
+
+    let challenges = [Fr::from(5), -Fr::from(7)]
+        .iter()
+        .map(|scalar| {
+            writer.write_scalar(*scalar).unwrap();
+            *writer.squeeze_challenge_scalar::<()>()
+        })
+        .collect();
+
+    (finalize(writer), challenges)
+}
+
+fn run_transcript(k: u32, kind: TranscriptKind, proof: Vec<u8>, challenges: Vec<Fr>) {
+    let circuit = TestBitsTranscriptCircuit {
+        kind,
+        proof,
+        challenges,
+    };
+    let prover = match MockProver::run(k, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+fn run_transcript_wrong_challenge(
+    k: u32,
+    kind: TranscriptKind,
+    proof: Vec<u8>,
+    mut challenges: Vec<Fr>,
+) {
+    challenges[1] += Fr::one();
+    let circuit = TestBitsTranscriptCircuit {
+        kind,
+        proof,
+        challenges,
+    };
+    let prover = match MockProver::run(k, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{:#?}", e),
+    };
+    assert!(prover.verify().is_err());
+}
+
+#[cfg(feature = "blake2b")]
+fn blake2b_transcript() -> (Vec<u8>, Vec<Fr>) {
+    use halo2_proofs::transcript::{Blake2bWrite, TranscriptWriterBuffer};
+
+    write_transcript(
+        Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]),
+        |writer| writer.finalize(),
+    )
+}
+
+#[cfg(feature = "blake2b")]
+#[test]
+fn test_bits_transcript_blake2b() {
+    let (proof, challenges) = blake2b_transcript();
+    run_transcript(20, TranscriptKind::Blake2b, proof, challenges);
+}
+
+#[cfg(feature = "blake2b")]
+#[test]
+fn test_bits_transcript_blake2b_wrong_challenge() {
+    let (proof, challenges) = blake2b_transcript();
+    run_transcript_wrong_challenge(20, TranscriptKind::Blake2b, proof, challenges);
+}
+
+#[cfg(feature = "keccak")]
+fn keccak_transcript() -> (Vec<u8>, Vec<Fr>) {
+    use halo2_snark_aggregator_api::transcript::keccak::Keccak256Write;
+
+    write_transcript(
+        Keccak256Write::<_, G1Affine, Challenge255<_>>::init(vec![]),
+        |writer| writer.finalize(),
+    )
+}
+
+#[cfg(feature = "keccak")]
+#[test]
+fn test_bits_transcript_keccak() {
+    let (proof, challenges) = keccak_transcript();
+    run_transcript(22, TranscriptKind::Keccak256, proof, challenges);
+}
+
+#[cfg(feature = "keccak")]
+#[test]
+fn test_bits_transcript_keccak_wrong_challenge() {
+    let (proof, challenges) = keccak_transcript();
+    run_transcript_wrong_challenge(22, TranscriptKind::Keccak256, proof, challenges);
+}
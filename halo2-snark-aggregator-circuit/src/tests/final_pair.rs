@@ -0,0 +1,254 @@
+use crate::verify_circuit::{
+    final_pair_to_instances, Halo2CircuitInstance, Halo2CircuitInstances, TranscriptKind,
+};
+use halo2_proofs::{
+    arithmetic::Field,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{
+        create_proof, keygen_pk, keygen_vk, Advice, Circuit, Column, ConstraintSystem, Error,
+        Instance, Selector, VerifyingKey,
+    },
+    poly::{
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::{ProverGWC, ProverSHPLONK},
+        },
+        Rotation,
+    },
+    transcript::{Challenge255, PoseidonWrite},
+};
+use halo2_snark_aggregator_api::hash::poseidon::HALO2_TRANSCRIPT_SPEC;
+use halo2_snark_aggregator_api::systems::halo2::multiopen::MultiOpenKind;
+use halo2curves::bn256::{Bn256, Fr, G1Affine, G1};
+use halo2curves::group::{Curve, Group};
+use rand_core::OsRng;
+
+const K: u32 = 8;
+
+#[derive(Clone)]
+struct InstanceCircuitConfig {
+    a: Column<Advice>,
+    b: Column<Advice>,
+    s: Selector,
+    instance: Column<Instance>,
+}
+
+// Exposes its values on the instance column, a copy of them is constrained equal
+// in the advice columns.
+#[derive(Clone, Default)]
+struct InstanceCircuit {
+    values: Vec<Value<Fr>>,
+}
+
+impl Circuit<Fr> for InstanceCircuit {
+    type Config = InstanceCircuitConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            values: vec![Value::unknown(); self.values.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        let s = meta.selector();
+        let instance = meta.instance_column();
+        meta.enable_equality(a);
+        meta.enable_equality(instance);
+
+        meta.create_gate("a = b", |meta| {
+            let s = meta.query_selector(s);
+            let a = meta.query_advice(a, Rotation::cur());
+            let b = meta.query_advice(b, Rotation::cur());
+            vec![s * (a - b)]
+        });
+
+        InstanceCircuitConfig { a, b, s, instance }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let cells = layouter.assign_region(
+            || "values",
+            |mut region| {
+                self.values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, value)| {
+                        config.s.enable(&mut region, i)?;
+                        region.assign_advice(|| "b", config.b, i, || *value)?;
+                        region.assign_advice(|| "a", config.a, i, || *value)
+                    })
+                    .collect::<Result<Vec<_>, Error>>()
+            },
+        )?;
+
+        for (i, cell) in cells.iter().enumerate() {
+            layouter.constrain_instance(cell.cell(), config.instance, i)?;
+        }
+        Ok(())
+    }
+}
+
+struct TargetProofs {
+    name: String,
+    kind: MultiOpenKind,
+    vk: VerifyingKey<G1Affine>,
+    n_instances: Vec<Vec<Vec<Vec<Fr>>>>,
+    n_transcript: Vec<Vec<u8>>,
+    n_enabled: Vec<bool>,
+    has_accumulator: bool,
+    padding: bool,
+}
+
+fn target_proofs(
+    params: &ParamsKZG<Bn256>,
+    name: &str,
+    kind: MultiOpenKind,
+    n_values: Vec<Vec<Fr>>,
+) -> TargetProofs {
+    let circuits = n_values
+        .iter()
+        .map(|values| InstanceCircuit {
+            values: values.iter().cloned().map(Value::known).collect(),
+        })
+        .collect::<Vec<_>>();
+    let vk = keygen_vk(params, &circuits[0]).expect("keygen_vk should not fail");
+    let pk = keygen_pk(params, vk.clone(), &circuits[0]).expect("keygen_pk should not fail");
+
+    let n_transcript = circuits
+        .into_iter()
+        .zip(n_values.iter())
+        .map(|(circuit, values)| {
+            let instances: &[&[&[Fr]]] = &[&[&values[..]]];
+            let mut transcript =
+                PoseidonWrite::<Vec<u8>, G1Affine, Challenge255<G1Affine>>::init(vec![]);
+            match kind {
+                MultiOpenKind::GWC => {
+                    create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<Bn256>, _, _, _, _>(
+                        params,
+                        &pk,
+                        &[circuit],
+                        instances,
+                        OsRng,
+                        &mut transcript,
+                    )
+                }
+                MultiOpenKind::SHPLONK => {
+                    create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<Bn256>, _, _, _, _>(
+                        params,
+                        &pk,
+                        &[circuit],
+                        instances,
+                        OsRng,
+                        &mut transcript,
+                    )
+                }
+            }
+            .expect("proof generation should not fail");
+            transcript.finalize()
+        })
+        .collect::<Vec<_>>();
+
+    TargetProofs {
+        name: name.to_owned(),
+        kind,
+        vk,
+        n_enabled: vec![true; n_values.len()],
+        n_instances: n_values
+            .into_iter()
+            .map(|values| vec![vec![values]])
+            .collect(),
+        n_transcript,
+        has_accumulator: false,
+        padding: false,
+    }
+}
+
+fn circuit_instance<'a>(
+    params: &'a ParamsKZG<Bn256>,
+    target: &'a TargetProofs,
+) -> Halo2CircuitInstance<'a, Bn256> {
+    Halo2CircuitInstance {
+        name: target.name.clone(),
+        params,
+        vk: &target.vk,
+        n_instances: &target.n_instances,
+        n_transcript: &target.n_transcript,
+        n_enabled: &target.n_enabled,
+        has_accumulator: target.has_accumulator,
+        kind: target.kind,
+        padding: target.padding,
+        transcript_kind: TranscriptKind::Poseidon,
+        poseidon_spec: HALO2_TRANSCRIPT_SPEC,
+    }
+}
+
+fn random_values(n: usize) -> Vec<Fr> {
+    (0..n).map(|_| Fr::random(OsRng)).collect()
+}
+
+// Accumulator limbs of a random pair, followed by a plain instance.
+fn accumulator_values() -> Vec<Fr> {
+    let w_x = (G1::generator() * Fr::random(OsRng)).to_affine();
+    let w_g = (G1::generator() * Fr::random(OsRng)).to_affine();
+    let mut values = final_pair_to_instances::<G1Affine, Bn256>(&(w_x, w_g, vec![]));
+    values.extend(random_values(1));
+    values
+}
+
+// The parallel verifier must give the final pair and the instances of the verifier
+// on the mock chips, with SHPLONK proofs, padding slots and accumulators.
+#[test]
+fn test_final_pair_parallel() {
+    let params = ParamsKZG::<Bn256>::setup(K, OsRng);
+
+    let shplonk = target_proofs(
+        &params,
+        "shplonk",
+        MultiOpenKind::SHPLONK,
+        vec![random_values(2), random_values(2)],
+    );
+    let padding = TargetProofs {
+        n_enabled: vec![true, false],
+        padding: true,
+        ..target_proofs(
+            &params,
+            "padding",
+            MultiOpenKind::GWC,
+            vec![random_values(2), random_values(2)],
+        )
+    };
+    let accumulator = TargetProofs {
+        has_accumulator: true,
+        ..target_proofs(
+            &params,
+            "accumulator",
+            MultiOpenKind::GWC,
+            vec![accumulator_values(), accumulator_values()],
+        )
+    };
+
+    for targets in [
+        vec![&shplonk],
+        vec![&padding],
+        vec![&accumulator],
+        vec![&shplonk, &padding, &accumulator],
+    ] {
+        let instances = Halo2CircuitInstances(
+            targets
+                .into_iter()
+                .map(|target| circuit_instance(&params, target))
+                .collect(),
+        );
+        assert_eq!(
+            instances.calc_verify_circuit_final_pair_parallel().unwrap(),
+            instances.calc_verify_circuit_final_pair().unwrap()
+        );
+    }
+}
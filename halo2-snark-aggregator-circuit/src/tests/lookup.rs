@@ -19,6 +19,7 @@ use halo2_proofs::{
     circuit::{Layouter, SimpleFloorPlanner},
     plonk::{Circuit, ConstraintSystem, Error},
 };
+use halo2_snark_aggregator_api::systems::halo2::multiopen::MultiOpenKind;
 use halo2_snark_aggregator_api::tests::systems::halo2::lookup_test::{
     verify_aggregation::test_verify_aggregation_proof_in_chip,
     verify_single::test_verify_single_proof_in_chip,
@@ -29,6 +30,8 @@ use std::marker::PhantomData;
 enum TestCase {
     Single,
     Aggregation,
+    SingleShplonk,
+    AggregationShplonk,
 }
 
 impl Default for TestCase {
@@ -58,6 +61,7 @@ impl TestFiveColumnNativeEccChipCircuit<G1Affine> {
         base_gate_config: BaseGateConfig<VAR_COLUMNS, MUL_COLUMNS>,
         ecc_chip: &NativeEccChip<G1Affine>,
         ctx: &mut Context<'a, Fr>,
+        kind: MultiOpenKind,
     ) -> Result<(), Error> {
         let native_base_gate = FiveColumnBaseGate::new(base_gate_config.clone());
         let scalar_base_gate = FiveColumnBaseGate::new(base_gate_config);
@@ -72,6 +76,7 @@ impl TestFiveColumnNativeEccChipCircuit<G1Affine> {
             &ScalarChip::new(&scalar_base_gate),
             &EccChip::new(ecc_chip),
             ctx,
+            kind,
         );
 
         Ok(())
@@ -82,6 +87,7 @@ impl TestFiveColumnNativeEccChipCircuit<G1Affine> {
         base_gate_config: BaseGateConfig<VAR_COLUMNS, MUL_COLUMNS>,
         ecc_chip: &NativeEccChip<G1Affine>,
         ctx: &mut Context<'a, Fr>,
+        kind: MultiOpenKind,
     ) -> Result<(), Error> {
         let native_base_gate = FiveColumnBaseGate::new(base_gate_config.clone());
         let scalar_base_gate = FiveColumnBaseGate::new(base_gate_config);
@@ -96,6 +102,7 @@ impl TestFiveColumnNativeEccChipCircuit<G1Affine> {
             &ScalarChip::new(&scalar_base_gate),
             &EccChip::new(ecc_chip),
             ctx,
+            kind,
         );
 
         Ok(())
@@ -152,11 +159,25 @@ impl Circuit<Fr> for TestFiveColumnNativeEccChipCircuit<G1Affine> {
                             config.base_gate_config.clone(),
                             &ecc_gate,
                             r,
+                            MultiOpenKind::GWC,
                         ),
                         TestCase::Aggregation => self.setup_aggregation_proof_verify_test(
                             config.base_gate_config.clone(),
                             &ecc_gate,
                             r,
+                            MultiOpenKind::GWC,
+                        ),
+                        TestCase::SingleShplonk => self.setup_single_proof_verify_test(
+                            config.base_gate_config.clone(),
+                            &ecc_gate,
+                            r,
+                            MultiOpenKind::SHPLONK,
+                        ),
+                        TestCase::AggregationShplonk => self.setup_aggregation_proof_verify_test(
+                            config.base_gate_config.clone(),
+                            &ecc_gate,
+                            r,
+                            MultiOpenKind::SHPLONK,
                         ),
                     }?;
                 }
@@ -206,4 +227,36 @@ mod tests {
         };
         assert_eq!(prover.verify(), Ok(()));
     }
+
+    #[test]
+    fn test_five_column_single_proof_verify_shplonk() {
+        //const K: u32 = (COMMON_RANGE_BITS + 2) as u32;
+        const K: u32 = 22;
+        let chip = TestFiveColumnNativeEccChipCircuit::<G1Affine> {
+            test_case: TestCase::SingleShplonk,
+            _phantom_w: PhantomData,
+            _phantom_n: PhantomData,
+        };
+        let prover = match MockProver::run(K, &chip, vec![]) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_five_column_aggregation_proof_verify_shplonk() {
+        //const K: u32 = (COMMON_RANGE_BITS + 2) as u32;
+        const K: u32 = 22;
+        let chip = TestFiveColumnNativeEccChipCircuit::<G1Affine> {
+            test_case: TestCase::AggregationShplonk,
+            _phantom_w: PhantomData,
+            _phantom_n: PhantomData,
+        };
+        let prover = match MockProver::run(K, &chip, vec![]) {
+            Ok(prover) => prover,
+            Err(e) => panic!("{:#?}", e),
+        };
+        assert_eq!(prover.verify(), Ok(()));
+    }
 }
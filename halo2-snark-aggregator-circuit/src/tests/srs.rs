@@ -0,0 +1,85 @@
+use crate::srs::{check_same_srs, downsize_params, read_ptau, unsafe_srs};
+use halo2_proofs::arithmetic::{CurveAffine, Field};
+use halo2_proofs::poly::commitment::Params;
+use halo2curves::bn256::{Bn256, Fq, Fr, G2Affine, G1, G2};
+use halo2curves::group::{ff::PrimeField, Curve, Group};
+use rand_core::OsRng;
+use std::io::Cursor;
+
+const POWER: u32 = 5;
+
+// Field elements are written little endian in montgomery form, as snarkjs does.
+fn write_fq(buf: &mut Vec<u8>, x: &Fq) {
+    let r = Fq::from(2).pow_vartime(&[256]);
+    buf.extend_from_slice((*x * r).to_repr().as_ref());
+}
+
+fn write_section(buf: &mut Vec<u8>, section_type: u32, data: Vec<u8>) {
+    buf.extend_from_slice(&section_type.to_le_bytes());
+    buf.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    buf.extend(data);
+}
+
+fn ptau(tau: Fr) -> Vec<u8> {
+    let mut header = vec![];
+    header.extend_from_slice(&32u32.to_le_bytes());
+    let mut q = (-Fq::one()).to_repr().as_ref().to_vec();
+    q[0] += 1;
+    header.extend(q);
+    header.extend_from_slice(&POWER.to_le_bytes());
+    header.extend_from_slice(&POWER.to_le_bytes());
+
+    let mut tau_g1 = vec![];
+    let mut s = Fr::one();
+    for _ in 0..(2 << POWER) - 1 {
+        let p = (G1::generator() * s).to_affine();
+        let coordinates = p.coordinates().unwrap();
+        write_fq(&mut tau_g1, coordinates.x());
+        write_fq(&mut tau_g1, coordinates.y());
+        s *= tau;
+    }
+
+    let mut tau_g2 = vec![];
+    let mut s = Fr::one();
+    for _ in 0..1 << POWER {
+        let p = (G2::generator() * s).to_affine();
+        for x in [p.x.c0, p.x.c1, p.y.c0, p.y.c1] {
+            write_fq(&mut tau_g2, &x);
+        }
+        s *= tau;
+    }
+
+    let mut buf = b"ptau".to_vec();
+    buf.extend_from_slice(&1u32.to_le_bytes());
+    buf.extend_from_slice(&3u32.to_le_bytes());
+    write_section(&mut buf, 1, header);
+    write_section(&mut buf, 2, tau_g1);
+    write_section(&mut buf, 3, tau_g2);
+    buf
+}
+
+#[test]
+fn test_read_ptau() {
+    let tau = Fr::random(OsRng);
+    let buf = ptau(tau);
+
+    let params = read_ptau(&mut Cursor::new(&buf), 4).unwrap();
+    assert_eq!(params.k(), 4);
+    assert_eq!(params.g2(), G2Affine::from(G2::generator()));
+    assert_eq!(params.s_g2(), (G2::generator() * tau).to_affine());
+
+    let larger = read_ptau(&mut Cursor::new(&buf), POWER).unwrap();
+    let downsized = downsize_params(larger, 3).unwrap();
+    assert_eq!(downsized.k(), 3);
+    check_same_srs("ptau", &params, &downsized).unwrap();
+
+    assert!(read_ptau(&mut Cursor::new(&buf), POWER + 1).is_err());
+    assert!(downsize_params(downsized, 4).is_err());
+    assert!(check_same_srs("ptau", &params, &unsafe_srs::<Bn256>(4)).is_err());
+}
+
+#[test]
+fn test_unsafe_srs_is_shared() {
+    let params = unsafe_srs::<Bn256>(3);
+    check_same_srs("unsafe", &params, &unsafe_srs::<Bn256>(4)).unwrap();
+}
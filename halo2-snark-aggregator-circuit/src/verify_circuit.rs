@@ -1,10 +1,16 @@
-use super::chips::{ecc_chip::EccChip, encode_chip::PoseidonEncodeChip, scalar_chip::ScalarChip};
+use super::chips::{
+    ecc_chip::EccChip,
+    encode_chip::{BitsEncodeChip, PoseidonEncodeChip},
+    scalar_chip::ScalarChip,
+};
+use crate::error::AggregatorError;
 use crate::fs::{
-    load_target_circuit_instance, load_target_circuit_params, load_target_circuit_proof,
-    load_target_circuit_vk, load_verify_circuit_instance, load_verify_circuit_params,
-    load_verify_circuit_proof, load_verify_circuit_vk,
+    count_target_circuit_proofs, load_target_circuit_instance, load_target_circuit_params,
+    load_target_circuit_proof, load_target_circuit_vk, load_verify_circuit_instance,
+    load_verify_circuit_params, load_verify_circuit_proof, load_verify_circuit_vk,
 };
 use crate::sample_circuit::TargetCircuit;
+use crate::srs::{check_same_srs, load_params_srs, unsafe_srs};
 use halo2_ecc_circuit_lib::chips::integer_chip::IntegerChipOps;
 use halo2_ecc_circuit_lib::chips::{
     ecc_chip::{AssignedPoint, EccChipOps},
@@ -12,7 +18,7 @@ use halo2_ecc_circuit_lib::chips::{
 };
 use halo2_ecc_circuit_lib::five::integer_chip::FiveColumnIntegerChipHelper;
 use halo2_ecc_circuit_lib::gates::base_gate::{AssignedValue, BaseGateOps};
-use halo2_ecc_circuit_lib::utils::field_to_bn;
+use halo2_ecc_circuit_lib::utils::{bn_to_field, field_to_bn};
 use halo2_ecc_circuit_lib::{
     five::{
         base_gate::{FiveColumnBaseGate, FiveColumnBaseGateConfig},
@@ -21,6 +27,7 @@ use halo2_ecc_circuit_lib::{
     },
     gates::{base_gate::Context, range_gate::RangeGateConfig},
 };
+use halo2_proofs::arithmetic::Field;
 use halo2_proofs::circuit::floor_planner::V1;
 use halo2_proofs::plonk::{create_proof, keygen_vk, ProvingKey};
 use halo2_proofs::plonk::{Column, Instance};
@@ -36,20 +43,38 @@ use halo2_proofs::{
 };
 use halo2_proofs::{
     plonk::{keygen_pk, verify_proof},
-    transcript::Challenge255,
+    transcript::{Challenge255, TranscriptRead, TranscriptWrite},
 };
+use halo2_snark_aggregator_api::arith::{common::ArithCommonChip, ecc::ArithEccChip};
+use halo2_snark_aggregator_api::hash::poseidon::PoseidonChip;
+pub use halo2_snark_aggregator_api::hash::poseidon::{TranscriptSpec, HALO2_TRANSCRIPT_SPEC};
 use halo2_snark_aggregator_api::mock::arith::{
     ecc::MockEccChip,
     field::{MockChipCtx, MockFieldChip},
 };
-use halo2_snark_aggregator_api::mock::transcript_encode::PoseidonEncode;
+use halo2_snark_aggregator_api::mock::transcript_encode::{BitsEncode, PoseidonEncode};
+pub use halo2_snark_aggregator_api::systems::halo2::coherent::{
+    column_count, ColumnKind, CommitmentRef,
+};
+use halo2_snark_aggregator_api::systems::halo2::native::{
+    verify_aggregation_proofs_parallel, NativeCircuitProof, NativeCtx, NativeEccChip,
+    NativeFieldChip, NativeProofData,
+};
+pub use halo2_snark_aggregator_api::systems::halo2::transcript::TranscriptKind;
 use halo2_snark_aggregator_api::systems::halo2::verify::{
+    diagnose_aggregation_proofs_in_chip, fold_accumulators_in_chip,
     verify_aggregation_proofs_in_chip, CircuitProof,
 };
 use halo2_snark_aggregator_api::systems::halo2::{
-    transcript::PoseidonTranscriptRead, verify::ProofData,
+    coherent::find_commitment,
+    multiopen::MultiOpenKind,
+    transcript::{check_transcript_spec, TargetTranscriptRead},
+    verify::ProofData,
+};
+pub use halo2_snark_aggregator_api::transcript::sha::TranscriptHash;
+use halo2_snark_aggregator_api::transcript::sha::{
+    ShaKeccakRead, ShaKeccakWrite, ShaRead, ShaWrite,
 };
-use halo2_snark_aggregator_api::transcript::sha::{ShaRead, ShaWrite};
 use halo2curves::bn256::{Bn256, Fr, G1Affine};
 use halo2curves::group::{Curve, Group};
 use halo2curves::pairing::{Engine, MillerLoopResult, MultiMillerLoop};
@@ -59,10 +84,17 @@ use std::env::var;
 use std::fmt::Debug;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::{io::Read, marker::PhantomData};
+use std::time::{Duration, Instant};
+use std::{
+    io::{self, Read},
+    marker::PhantomData,
+};
 
 const COMMON_RANGE_BITS: usize = 17usize;
 
+// Number of instances used to expose the final pair (w_x, w_g) of a verify circuit.
+pub const ACCUMULATOR_INSTANCES: usize = 4usize;
+
 #[derive(Clone)]
 pub struct Halo2VerifierCircuitConfig {
     base_gate_config: FiveColumnBaseGateConfig,
@@ -74,12 +106,14 @@ pub struct Halo2VerifierCircuitConfig {
 pub struct SingleProofPair<E: MultiMillerLoop> {
     pub instances: Vec<Vec<Vec<E::Scalar>>>,
     pub transcript: Vec<u8>,
+    pub enabled: bool,
 }
 
 #[derive(Clone, Debug)]
 pub struct SingleProofWitness<'a, E: MultiMillerLoop> {
     pub instances: &'a Vec<Vec<Vec<E::Scalar>>>,
     pub transcript: &'a Vec<u8>,
+    pub enabled: bool,
 }
 
 #[derive(Clone)]
@@ -89,6 +123,13 @@ pub struct Halo2VerifierCircuit<'a, E: MultiMillerLoop> {
     pub vk: &'a VerifyingKey<E::G1Affine>,
     pub proofs: Vec<SingleProofWitness<'a, E>>,
     pub nproofs: usize,
+    pub has_accumulator: bool,
+    pub kind: MultiOpenKind,
+    // Set when disabled proof slots are allowed, a selector bit is then exposed
+    // for each slot after the instances.
+    pub padding: bool,
+    pub transcript_kind: TranscriptKind,
+    pub poseidon_spec: TranscriptSpec,
 }
 
 #[derive(Clone)]
@@ -98,69 +139,121 @@ pub struct Halo2CircuitInstance<'a, E: MultiMillerLoop> {
     pub vk: &'a VerifyingKey<E::G1Affine>,
     pub n_instances: &'a Vec<Vec<Vec<Vec<E::Scalar>>>>,
     pub n_transcript: &'a Vec<Vec<u8>>,
+    pub n_enabled: &'a Vec<bool>,
+    pub has_accumulator: bool,
+    pub kind: MultiOpenKind,
+    pub padding: bool,
+    pub transcript_kind: TranscriptKind,
+    pub poseidon_spec: TranscriptSpec,
+}
+
+fn instances_len<F>(instances: &[Vec<Vec<F>>]) -> usize {
+    instances
+        .iter()
+        .map(|instance| instance.iter().map(|column| column.len()).sum::<usize>())
+        .sum()
 }
 
-pub struct Halo2CircuitInstances<'a, E: MultiMillerLoop, const N: usize>(
-    pub [Halo2CircuitInstance<'a, E>; N],
-);
+pub struct Halo2CircuitInstances<'a, E: MultiMillerLoop>(pub Vec<Halo2CircuitInstance<'a, E>>);
+
+// Reader of the target proofs, and of the poseidon aggregation transcript, on the mock
+// chips.
+type MockTargetTranscriptRead<'a, C> =
+    TargetTranscriptRead<&'a [u8], C, MockEccChip<C, Error>, PoseidonEncode, BitsEncode>;
+
+// Poseidon aggregation transcript of the native verifier.
+type NativeTargetTranscriptRead<'a, C> =
+    TargetTranscriptRead<&'a [u8], C, NativeEccChip<C>, PoseidonEncode, BitsEncode>;
 
-impl<
-        'a,
-        C: CurveAffine,
-        E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug,
-        const N: usize,
-    > Halo2CircuitInstances<'a, E, N>
+impl<'a, C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug>
+    Halo2CircuitInstances<'a, E>
 {
-    pub fn calc_verify_circuit_final_pair(&self) -> (C, C, Vec<<C as CurveAffine>::ScalarExt>) {
-        let nchip = MockFieldChip::<C::ScalarExt, Error>::default();
+    fn check_padding(&self) -> Result<(), AggregatorError> {
+        for instance in self.0.iter() {
+            check_padding_support(&instance.name, instance.padding, instance.has_accumulator)?;
+        }
+        Ok(())
+    }
+
+    // The final pair is checked on the g2 and s_g2 of the first circuit.
+    fn check_srs(&self) -> Result<(), AggregatorError> {
+        for instance in self.0.iter() {
+            check_same_srs(&instance.name, instance.params, self.0[0].params)?;
+        }
+        Ok(())
+    }
+
+    fn mock_circuit_proofs(
+        &self,
+        ctx: &mut MockChipCtx,
+    ) -> Result<
+        Vec<CircuitProof<'a, E, MockEccChip<C, Error>, MockTargetTranscriptRead<'a, C>>>,
+        Error,
+    > {
         let schip = MockFieldChip::<C::ScalarExt, Error>::default();
-        let pchip = MockEccChip::<C, Error>::default();
-        let ctx = &mut MockChipCtx::default();
 
-        let circuit_proofs = self
-            .0
+        self.0
             .iter()
             .enumerate()
             .map(|(ci, instance)| {
                 let mut proof_data_list = vec![];
                 for (i, instances) in instance.n_instances.iter().enumerate() {
-                    let transcript =
-                        PoseidonTranscriptRead::<_, C, _, PoseidonEncode, 9usize, 8usize>::new(
-                            &instance.n_transcript[i][..],
-                            ctx,
-                            &schip,
-                            8usize,
-                            63usize,
-                        )
-                        .unwrap();
+                    let transcript = MockTargetTranscriptRead::new(
+                        instance.transcript_kind,
+                        &instance.n_transcript[i][..],
+                        ctx,
+                        &schip,
+                        &instance.poseidon_spec,
+                    )?;
 
                     proof_data_list.push(ProofData {
                         instances,
                         transcript,
                         key: format!("{}_p{}", self.0[ci].name, i),
+                        enabled: if instance.padding {
+                            Some(schip.assign_var(
+                                ctx,
+                                C::ScalarExt::from(instance.n_enabled[i] as u64),
+                            )?)
+                        } else {
+                            None
+                        },
                         _phantom: PhantomData,
                     })
                 }
 
-                CircuitProof {
+                Ok(CircuitProof {
                     name: instance.name.clone(),
                     vk: instance.vk,
                     params: instance.params,
                     proofs: proof_data_list,
-                }
+                    scheme: instance.kind,
+                })
             })
-            .collect();
+            .collect()
+    }
+
+    pub fn calc_verify_circuit_final_pair(
+        &self,
+    ) -> Result<(C, C, Vec<<C as CurveAffine>::ScalarExt>), AggregatorError> {
+        self.check_padding()?;
+        self.check_srs()?;
+
+        let nchip = MockFieldChip::<C::ScalarExt, Error>::default();
+        let schip = MockFieldChip::<C::ScalarExt, Error>::default();
+        let pchip = MockEccChip::<C, Error>::default();
+        let ctx = &mut MockChipCtx::default();
+
+        let circuit_proofs = self.mock_circuit_proofs(ctx)?;
 
         let empty_vec = vec![];
-        let mut transcript =
-            PoseidonTranscriptRead::<_, C, _, PoseidonEncode, 9usize, 8usize>::new(
-                &empty_vec[..],
-                ctx,
-                &nchip,
-                8usize,
-                63usize,
-            )
-            .unwrap();
+        let mut transcript = MockTargetTranscriptRead::new(
+            TranscriptKind::Poseidon,
+            &empty_vec[..],
+            ctx,
+            &nchip,
+            &HALO2_TRANSCRIPT_SPEC,
+        )?;
 
         let (w_x, w_g, instances, _) = verify_aggregation_proofs_in_chip(
             ctx,
@@ -169,10 +262,188 @@ impl<
             &pchip,
             circuit_proofs,
             &mut transcript,
-        )
-        .unwrap();
+        )?;
+
+        self.fold_final_pair::<MockEccChip<C, Error>, _>(ctx, &mut transcript, w_x, w_g, instances)
+    }
+
+    // Same result as `calc_verify_circuit_final_pair`, but each proof is read on its
+    // own thread, on the native chips, and the commitments are summed by one multiexp.
+    pub fn calc_verify_circuit_final_pair_parallel(
+        &self,
+    ) -> Result<(C, C, Vec<<C as CurveAffine>::ScalarExt>), AggregatorError> {
+        self.check_padding()?;
+        self.check_srs()?;
+
+        let ctx = &mut NativeCtx::default();
+
+        let circuit_proofs = self
+            .0
+            .iter()
+            .map(|instance| {
+                let proofs = instance
+                    .n_instances
+                    .iter()
+                    .enumerate()
+                    .map(|(i, instances)| NativeProofData {
+                        instances,
+                        transcript: &instance.n_transcript[i][..],
+                        key: format!("{}_p{}", instance.name, i),
+                        enabled: if instance.padding {
+                            Some(instance.n_enabled[i])
+                        } else {
+                            None
+                        },
+                    })
+                    .collect();
+
+                NativeCircuitProof {
+                    name: instance.name.clone(),
+                    vk: instance.vk,
+                    params: instance.params,
+                    proofs,
+                    scheme: instance.kind,
+                    transcript_kind: instance.transcript_kind,
+                    poseidon_spec: instance.poseidon_spec,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let nchip = NativeFieldChip::<C::ScalarExt>::default();
+        let empty_vec = vec![];
+        let mut transcript = NativeTargetTranscriptRead::new(
+            TranscriptKind::Poseidon,
+            &empty_vec[..],
+            ctx,
+            &nchip,
+            &HALO2_TRANSCRIPT_SPEC,
+        )?;
+
+        let (w_x, w_g, instances) =
+            verify_aggregation_proofs_parallel(ctx, &circuit_proofs, &mut transcript)?;
+
+        self.fold_final_pair::<NativeEccChip<C>, _>(ctx, &mut transcript, w_x, w_g, instances)
+    }
+
+    // Pairing result of every target proof, keyed as "{name}_p{i}", to find the proofs
+    // that break the final pair.
+    pub fn diagnose(&self) -> Result<Vec<(String, bool)>, AggregatorError> {
+        let nchip = MockFieldChip::<C::ScalarExt, Error>::default();
+        let schip = MockFieldChip::<C::ScalarExt, Error>::default();
+        let pchip = MockEccChip::<C, Error>::default();
+        let ctx = &mut MockChipCtx::default();
+
+        let circuit_proofs = self.mock_circuit_proofs(ctx)?;
+        Ok(diagnose_aggregation_proofs_in_chip(
+            ctx,
+            &nchip,
+            &schip,
+            &pchip,
+            circuit_proofs,
+        )?)
+    }
+
+    // Checks every enabled target proof on its own with the parallel verifier, so a bad
+    // proof is reported by circuit and index instead of as an unsatisfied verify circuit.
+    pub fn pre_verify(&self) -> Result<(), AggregatorError> {
+        for instance in self.0.iter() {
+            for (index, instances) in instance.n_instances.iter().enumerate() {
+                if instance.padding && !instance.n_enabled[index] {
+                    continue;
+                }
+
+                let n_instances = vec![instances.clone()];
+                let n_transcript = vec![instance.n_transcript[index].clone()];
+                Halo2CircuitInstances(vec![Halo2CircuitInstance {
+                    name: instance.name.clone(),
+                    params: instance.params,
+                    vk: instance.vk,
+                    n_instances: &n_instances,
+                    n_transcript: &n_transcript,
+                    n_enabled: &vec![true],
+                    has_accumulator: instance.has_accumulator,
+                    kind: instance.kind,
+                    padding: false,
+                    transcript_kind: instance.transcript_kind,
+                    poseidon_spec: instance.poseidon_spec,
+                }])
+                .calc_verify_circuit_final_pair_parallel()
+                .map_err(|err| AggregatorError::InvalidTargetProof {
+                    name: instance.name.clone(),
+                    index,
+                    err: Box::new(err),
+                })?;
+            }
+        }
+        info!("pre-verification of the target proofs done");
+        Ok(())
+    }
+
+    // Folds the accumulators carried in the instances into (w_x, w_g) and appends the
+    // padding selectors to the instances.
+    fn fold_final_pair<A, T>(
+        &self,
+        ctx: &mut A::Context,
+        transcript: &mut T,
+        w_x: C::CurveExt,
+        w_g: C::CurveExt,
+        mut instances: Vec<C::ScalarExt>,
+    ) -> Result<(C, C, Vec<<C as CurveAffine>::ScalarExt>), AggregatorError>
+    where
+        A: ArithEccChip<
+                Point = C,
+                AssignedPoint = C::CurveExt,
+                Scalar = C::ScalarExt,
+                Native = C::ScalarExt,
+                Error = Error,
+            > + Default,
+        A::ScalarChip: Default,
+        A::NativeChip: Default,
+        T: halo2_snark_aggregator_api::transcript::read::TranscriptRead<A>,
+    {
+        let nchip = A::NativeChip::default();
+        let schip = A::ScalarChip::default();
+        let pchip = A::default();
+
+        let mut accumulators = vec![];
+        let mut offset = 0;
+        for instance in self.0.iter() {
+            for proof_instances in instance.n_instances.iter() {
+                if instance.has_accumulator {
+                    let (acc_x, acc_g) = accumulator_from_instances::<C>(
+                        &instances[offset..offset + ACCUMULATOR_INSTANCES],
+                    )
+                    .ok_or_else(|| {
+                        AggregatorError::InstanceShape(format!(
+                            "{}: no accumulator in the instances at {}",
+                            instance.name, offset
+                        ))
+                    })?;
+                    accumulators
+                        .push((pchip.assign_var(ctx, acc_x)?, pchip.assign_var(ctx, acc_g)?));
+                }
+                offset += instances_len(proof_instances);
+            }
+        }
+
+        let (w_x, w_g) = fold_accumulators_in_chip::<E, A, T>(
+            ctx,
+            &nchip,
+            &schip,
+            &pchip,
+            w_x,
+            w_g,
+            accumulators,
+            transcript,
+        )?;
 
-        if true {
+        for instance in self.0.iter().filter(|instance| instance.padding) {
+            for enabled in instance.n_enabled.iter() {
+                instances.push(C::ScalarExt::from(*enabled as u64));
+            }
+        }
+
+        {
             // check final pair
             let s_g2_prepared = <E as MultiMillerLoop>::G2Prepared::from(self.0[0].params.s_g2());
             let n_g2_prepared = <E as MultiMillerLoop>::G2Prepared::from(-self.0[0].params.g2());
@@ -195,31 +466,38 @@ impl<
                     -self.0[0].params.g2()
                 )
             );
-            debug_assert!(success);
+            if !success {
+                return Err(AggregatorError::Pairing(
+                    "final pair of the target proofs".to_owned(),
+                ));
+            }
         }
-        (w_x.to_affine(), w_g.to_affine(), instances)
+        Ok((w_x.to_affine(), w_g.to_affine(), instances))
     }
 }
 
-pub struct Halo2VerifierCircuits<'a, E: MultiMillerLoop, const N: usize> {
-    pub circuits: [Halo2VerifierCircuit<'a, E>; N],
-    pub coherent: Vec<[(usize, usize); 2]>,
+pub struct Halo2VerifierCircuits<'a, E: MultiMillerLoop> {
+    pub circuits: Vec<Halo2VerifierCircuit<'a, E>>,
+    pub coherent: Vec<[CommitmentRef; 2]>,
+    // Expose a poseidon digest of the aggregated instances instead of the instances.
+    pub hash_instances: bool,
 }
 
-impl<
-        'a,
-        C: CurveAffine,
-        E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug,
-        const N: usize,
-    > Circuit<C::ScalarExt> for Halo2VerifierCircuits<'a, E, N>
+impl<'a, C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug>
+    Circuit<C::ScalarExt> for Halo2VerifierCircuits<'a, E>
 {
     type Config = Halo2VerifierCircuitConfig;
     type FloorPlanner = V1;
 
     fn without_witnesses(&self) -> Self {
         Halo2VerifierCircuits {
-            circuits: self.circuits.clone().map(|c| c.without_witnesses()),
+            circuits: self
+                .circuits
+                .iter()
+                .map(|c| c.without_witnesses())
+                .collect(),
             coherent: self.coherent.clone(),
+            hash_instances: self.hash_instances,
         }
     }
     fn configure(meta: &mut ConstraintSystem<C::ScalarExt>) -> Self::Config {
@@ -370,12 +648,8 @@ impl<
     }
 }
 
-impl<
-        'a,
-        C: CurveAffine,
-        E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug,
-        const N: usize,
-    > Halo2VerifierCircuits<'a, E, N>
+impl<'a, C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug>
+    Halo2VerifierCircuits<'a, E>
 {
     fn synthesize_proof(
         &self,
@@ -412,6 +686,7 @@ impl<
                 // Check context is used in shape layout or not
                 ctx.in_shape_mode = base_gate.in_shape_mode(ctx)?;
 
+                let mut selectors = vec![];
                 let circuit_proofs = self
                     .circuits
                     .iter()
@@ -421,37 +696,48 @@ impl<
                             ProofData<
                                 E,
                                 _,
-                                PoseidonTranscriptRead<
+                                TargetTranscriptRead<
                                     _,
                                     C,
                                     _,
                                     PoseidonEncodeChip<_>,
-                                    9usize,
-                                    8usize,
+                                    BitsEncodeChip<_>,
                                 >,
                             >,
                         > = vec![];
 
                         for i in 0..instance.nproofs {
-                            let transcript = PoseidonTranscriptRead::<
+                            let enabled = if instance.padding {
+                                let enabled = base_gate.assign(
+                                    ctx,
+                                    C::ScalarExt::from(instance.proofs[i].enabled as u64),
+                                )?;
+                                base_gate.assert_bit(ctx, &enabled)?;
+                                selectors.push(enabled);
+                                Some(enabled)
+                            } else {
+                                None
+                            };
+
+                            let transcript = TargetTranscriptRead::<
                                 _,
                                 C,
                                 _,
                                 PoseidonEncodeChip<_>,
-                                9usize,
-                                8usize,
+                                BitsEncodeChip<_>,
                             >::new(
+                                instance.transcript_kind,
                                 &instance.proofs[i].transcript[..],
                                 ctx,
                                 schip,
-                                8usize,
-                                63usize,
+                                &instance.poseidon_spec,
                             )?;
 
                             proof_data_list.push(ProofData {
                                 instances: instance.proofs[i].instances,
                                 transcript,
                                 key: format!("{}_p{}", self.circuits[ci].name, i),
+                                enabled,
                                 _phantom: PhantomData,
                             })
                         }
@@ -461,6 +747,7 @@ impl<
                             vk: instance.vk,
                             params: instance.params,
                             proofs: proof_data_list,
+                            scheme: instance.kind,
                         })
                     })
                     .into_iter()
@@ -468,14 +755,14 @@ impl<
 
                 let empty_vec = vec![];
                 let mut transcript =
-                    PoseidonTranscriptRead::<_, C, _, PoseidonEncodeChip<_>, 9usize, 8usize>::new(
+                    TargetTranscriptRead::<_, C, _, PoseidonEncodeChip<_>, BitsEncodeChip<_>>::new(
+                        TranscriptKind::Poseidon,
                         &empty_vec[..],
                         ctx,
                         schip,
-                        8usize,
-                        63usize,
+                        &HALO2_TRANSCRIPT_SPEC,
                     )?;
-                let (p1, p2, v, mut commits) = verify_aggregation_proofs_in_chip(
+                let (p1, p2, mut v, commits) = verify_aggregation_proofs_in_chip(
                     ctx,
                     nchip,
                     schip,
@@ -484,16 +771,56 @@ impl<
                     &mut transcript,
                 )?;
 
-                for coherent in &self.coherent {
-                    ecc_chip.assert_equal(
-                        ctx,
-                        &mut commits[coherent[0].0][coherent[0].1].clone(),
-                        &mut commits[coherent[1].0][coherent[1].1],
-                    )?;
+                let mut accumulators = vec![];
+                let mut offset = 0;
+                for circuit in self.circuits.iter() {
+                    for proof in circuit.proofs.iter().take(circuit.nproofs) {
+                        if circuit.has_accumulator {
+                            accumulators.push(Self::decode_accumulator(
+                                base_gate,
+                                &ecc_chip,
+                                ctx,
+                                &v[offset..offset + ACCUMULATOR_INSTANCES],
+                            )?);
+                        }
+                        offset += instances_len(proof.instances);
+                    }
+                }
+
+                let (p1, p2) = fold_accumulators_in_chip(
+                    ctx,
+                    nchip,
+                    schip,
+                    pchip,
+                    p1,
+                    p2,
+                    accumulators,
+                    &mut transcript,
+                )?;
+
+                v.extend(selectors);
+
+                for [a, b] in &self.coherent {
+                    // The refs are checked against the circuits by `check_coherent`
+                    let mut a = find_commitment(&commits, a)
+                        .ok_or(Error::Synthesis)?
+                        .clone();
+                    let mut b = find_commitment(&commits, b)
+                        .ok_or(Error::Synthesis)?
+                        .clone();
+                    ecc_chip.assert_equal(ctx, &mut a, &mut b)?;
                 }
 
                 base_gate.assert_false(ctx, &p1.z)?;
                 base_gate.assert_false(ctx, &p2.z)?;
+
+                let v = if self.hash_instances {
+                    let mut hasher = PoseidonChip::from_spec(ctx, schip, &HALO2_TRANSCRIPT_SPEC)?;
+                    hasher.update(&v);
+                    vec![hasher.squeeze(ctx, schip)?]
+                } else {
+                    v
+                };
                 r = Some((p1, p2, v));
                 Ok(())
             },
@@ -501,6 +828,65 @@ impl<
 
         Ok(r.unwrap())
     }
+
+    // Decode the instances exposed by `synthesize` back into the final pair.
+    fn decode_accumulator(
+        base_gate: &FiveColumnBaseGate<C::ScalarExt>,
+        ecc_chip: &NativeEccChip<'_, C>,
+        ctx: &mut Context<'_, C::ScalarExt>,
+        instances: &[AssignedValue<C::ScalarExt>],
+    ) -> Result<
+        (
+            AssignedPoint<C, <C as CurveAffine>::ScalarExt>,
+            AssignedPoint<C, <C as CurveAffine>::ScalarExt>,
+        ),
+        Error,
+    > {
+        let integer_chip = ecc_chip.integer_chip();
+        let helper = FiveColumnIntegerChipHelper::<C::Base, C::ScalarExt>::new();
+        let zero = C::ScalarExt::from(0);
+
+        let mut points = vec![];
+        for limbs in instances.chunks(2) {
+            let (low, high) = (&limbs[0], &limbs[1]);
+            let p =
+                limbs_to_point::<C>(&helper, &low.value, &high.value).unwrap_or_else(C::generator);
+
+            // assign_point constrains the point to be on curve.
+            let mut p = ecc_chip.assign_point(ctx, p.to_curve())?;
+            base_gate.assert_false(ctx, &p.z)?;
+            integer_chip.reduce(ctx, &mut p.x)?;
+            integer_chip.reduce(ctx, &mut p.y)?;
+
+            let y_bit = integer_chip.get_last_bit(ctx, &p.y)?;
+
+            let low_ = base_gate.sum_with_constant(
+                ctx,
+                vec![
+                    (&p.x.limbs_le[0], helper.limb_modulus_exps[0]),
+                    (&p.x.limbs_le[1], helper.limb_modulus_exps[1]),
+                ],
+                zero,
+            )?;
+            let high_ = base_gate.sum_with_constant(
+                ctx,
+                vec![
+                    (&p.x.limbs_le[2], helper.limb_modulus_exps[0]),
+                    (&p.x.limbs_le[3], helper.limb_modulus_exps[1]),
+                    (&y_bit, helper.limb_modulus_exps[2]),
+                ],
+                zero,
+            )?;
+            base_gate.assert_equal(ctx, &low_, low)?;
+            base_gate.assert_equal(ctx, &high_, high)?;
+
+            points.push(p);
+        }
+
+        let w_g = points.pop().unwrap();
+        let w_x = points.pop().unwrap();
+        Ok((w_x, w_g))
+    }
 }
 
 impl<'a, C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug>
@@ -516,6 +902,11 @@ impl<'a, C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt>
             vk: self.vk,
             proofs: (0..self.nproofs).map(|_| self.proofs[0].clone()).collect(),
             nproofs: self.nproofs,
+            has_accumulator: self.has_accumulator,
+            kind: self.kind,
+            padding: self.padding,
+            transcript_kind: self.transcript_kind,
+            poseidon_spec: self.poseidon_spec,
         }
     }
 
@@ -543,41 +934,236 @@ impl<'a, C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt>
         layouter: impl Layouter<C::ScalarExt>,
     ) -> Result<(), Error> {
         Halo2VerifierCircuits {
-            circuits: [self.clone()],
+            circuits: vec![self.clone()],
             coherent: vec![],
+            hash_instances: false,
         }
         .synthesize(config, layouter)
     }
 }
 
-fn verify_circuit_builder<'a, C: CurveAffine, E: MultiMillerLoop<G1Affine = C>, const N: usize>(
-    circuits: [Halo2VerifierCircuit<'a, E>; N],
-    coherent: Vec<[(usize, usize); 2]>,
-) -> Halo2VerifierCircuits<'a, E, N> {
-    Halo2VerifierCircuits { circuits, coherent }
+fn verify_circuit_builder<'a, C: CurveAffine, E: MultiMillerLoop<G1Affine = C>>(
+    circuits: Vec<Halo2VerifierCircuit<'a, E>>,
+    coherent: Vec<[CommitmentRef; 2]>,
+    hash_instances: bool,
+) -> Halo2VerifierCircuits<'a, E> {
+    Halo2VerifierCircuits {
+        circuits,
+        coherent,
+        hash_instances,
+    }
 }
 
 pub fn load_params<E: Engine + Debug>(
     folder: &mut std::path::PathBuf,
     file_name: &str,
-) -> ParamsKZG<E> {
+) -> Result<ParamsKZG<E>, AggregatorError> {
     folder.push(file_name);
-    let mut fd = std::fs::File::open(folder.as_path()).unwrap();
+    let fd = std::fs::File::open(folder.as_path());
     folder.pop();
-    KZGCommitmentScheme::<E>::read_params(&mut fd).unwrap()
+    KZGCommitmentScheme::<E>::read_params(&mut fd?)
+        .map_err(|err| AggregatorError::Deserialize(format!("{}: {}", file_name, err)))
 }
 
 pub fn load_transcript<C: CurveAffine>(
     folder: &mut std::path::PathBuf,
     file_name: &str,
-) -> Vec<u8> {
+) -> Result<Vec<u8>, AggregatorError> {
     folder.push(file_name);
-    let mut fd = std::fs::File::open(folder.as_path()).unwrap();
+    let fd = std::fs::File::open(folder.as_path());
     folder.pop();
 
     let mut buf = vec![];
-    fd.read_to_end(&mut buf).unwrap();
-    buf
+    fd?.read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+// Load the proofs of a target circuit found in the folder. With padding, the
+// N_PROOFS slots are filled up with disabled copies of the first proof.
+fn load_proof_slots<SingleCircuit: TargetCircuit<Bn256>, L>(
+    folder: &PathBuf,
+    load_instances: L,
+) -> Result<Vec<SingleProofPair<Bn256>>, AggregatorError>
+where
+    L: Fn(&[u8]) -> Result<Vec<Vec<Vec<Fr>>>, AggregatorError>,
+{
+    let nproofs = count_target_circuit_proofs::<SingleCircuit>(&mut folder.clone());
+    if nproofs == 0 {
+        return Err(AggregatorError::Io(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no proof of {} found in {:?}", SingleCircuit::NAME, folder),
+        )));
+    }
+
+    let mut proofs = (0..nproofs)
+        .map(|index| {
+            Ok(SingleProofPair::<Bn256> {
+                instances: load_instances(&load_target_circuit_instance::<SingleCircuit>(
+                    &mut folder.clone(),
+                    index,
+                )?)?,
+                transcript: load_target_circuit_proof::<SingleCircuit>(&mut folder.clone(), index)?,
+                enabled: true,
+            })
+        })
+        .collect::<Result<Vec<_>, AggregatorError>>()?;
+
+    if SingleCircuit::PADDING && nproofs > SingleCircuit::N_PROOFS {
+        return Err(AggregatorError::InstanceShape(format!(
+            "too many proofs of {} found in {:?}",
+            SingleCircuit::NAME,
+            folder
+        )));
+    }
+
+    // A disabled slot still has its transcript read by the verify circuit, so it needs a
+    // proof of the same circuit: points that decode and the same number of reads, which
+    // keeps the layout and the vkey independent of the enabled slots. Its content does
+    // not matter, the constrained `enabled` bit, a public instance, zeroes its w_x, w_g
+    // and instances in the final pair. Any valid proof works, the first one is reused
+    // rather than shipping a canonical dummy proof per target circuit.
+    if SingleCircuit::PADDING {
+        let dummy = SingleProofPair {
+            enabled: false,
+            ..proofs[0].clone()
+        };
+        proofs.resize(SingleCircuit::N_PROOFS, dummy);
+    }
+
+    Ok(proofs)
+}
+
+// A target proof created with another poseidon spec would silently make the verify
+// circuit unsatisfiable, so the challenge it derives is checked first. Other
+// transcripts only need their reader to be built.
+fn check_transcript<SingleCircuit: TargetCircuit<Bn256>>(
+    proofs: &[SingleProofPair<Bn256>],
+) -> Result<(), AggregatorError> {
+    if !SingleCircuit::TRANSCRIPT.is_supported() {
+        return Err(AggregatorError::Transcript(io::Error::new(
+            io::ErrorKind::Other,
+            format!(
+                "{}: the {:?} transcript is not enabled in this build",
+                SingleCircuit::NAME,
+                SingleCircuit::TRANSCRIPT
+            ),
+        )));
+    }
+    if SingleCircuit::TRANSCRIPT != TranscriptKind::Poseidon {
+        return Ok(());
+    }
+
+    check_transcript_spec::<G1Affine>(&proofs[0].transcript, &SingleCircuit::POSEIDON_SPEC).map_err(
+        |err| match AggregatorError::from(err) {
+            AggregatorError::Transcript(err) => AggregatorError::Transcript(io::Error::new(
+                err.kind(),
+                format!("{}: {}", SingleCircuit::NAME, err),
+            )),
+            err => err,
+        },
+    )
+}
+
+// A disabled slot has no accumulator to fold, so padding is only for circuits
+// without one.
+fn check_padding_support(
+    name: &str,
+    padding: bool,
+    has_accumulator: bool,
+) -> Result<(), AggregatorError> {
+    if padding && has_accumulator {
+        return Err(AggregatorError::InstanceShape(format!(
+            "{}: padding is not supported on circuits with accumulator",
+            name
+        )));
+    }
+    Ok(())
+}
+
+// Each coherent commitment must name a proof and a column of one of the circuits.
+fn check_coherent<C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt>>(
+    setups: &[Setup<C, E>],
+    coherent: &[[CommitmentRef; 2]],
+) -> Result<(), AggregatorError> {
+    for commitment in coherent.iter().flatten() {
+        let setup = setups.get(commitment.circuit).ok_or_else(|| {
+            AggregatorError::Manifest(format!(
+                "coherent commitment {:?}: {} circuits in the batch",
+                commitment,
+                setups.len()
+            ))
+        })?;
+        if commitment.proof >= setup.nproofs {
+            return Err(AggregatorError::Manifest(format!(
+                "coherent commitment {:?}: {} has {} proofs",
+                commitment, setup.name, setup.nproofs
+            )));
+        }
+        match column_count(
+            setup.target_circuit_vk.cs(),
+            commitment.phase,
+            commitment.kind,
+        ) {
+            Some(count) if commitment.index < count => {}
+            Some(count) => {
+                return Err(AggregatorError::Manifest(format!(
+                    "coherent commitment {:?}: {} has {} {:?} columns in phase {}",
+                    commitment, setup.name, count, commitment.kind, commitment.phase
+                )))
+            }
+            None => {
+                return Err(AggregatorError::Manifest(format!(
+                    "coherent commitment {:?}: {} has no phase {} of {:?} columns",
+                    commitment, setup.name, commitment.phase, commitment.kind
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
+// The verifier commits to the instances with the lagrange basis of the params, so
+// the params must have the k of the circuit, and each proof must have one instance
+// column per circuit column, none longer than the usable rows.
+fn check_instances_shape<C: CurveAffine, E: MultiMillerLoop<G1Affine = C>>(
+    name: &str,
+    params: &ParamsKZG<E>,
+    vk: &VerifyingKey<C>,
+    proofs: &[SingleProofPair<E>],
+) -> Result<(), AggregatorError> {
+    if params.k() != vk.get_domain().k() {
+        return Err(AggregatorError::Srs(format!(
+            "{}: params of k = {} for a circuit of k = {}",
+            name,
+            params.k(),
+            vk.get_domain().k()
+        )));
+    }
+
+    let max_len = params.n() as usize - (vk.cs().blinding_factors() + 1);
+    for (i, proof) in proofs.iter().enumerate() {
+        for instances in proof.instances.iter() {
+            if instances.len() != vk.cs().num_instance_columns {
+                return Err(AggregatorError::InstanceShape(format!(
+                    "{} proof {}: {} instance columns, the circuit has {}",
+                    name,
+                    i,
+                    instances.len(),
+                    vk.cs().num_instance_columns
+                )));
+            }
+            if let Some(column) = instances.iter().find(|column| column.len() > max_len) {
+                return Err(AggregatorError::InstanceShape(format!(
+                    "{} proof {}: {} instances in a column, at most {} fit",
+                    name,
+                    i,
+                    column.len(),
+                    max_len
+                )));
+            }
+        }
+    }
+    Ok(())
 }
 
 pub struct Setup<C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt>> {
@@ -586,48 +1172,55 @@ pub struct Setup<C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C::Sc
     pub target_circuit_vk: Rc<VerifyingKey<C>>,
     pub proofs: Vec<SingleProofPair<E>>,
     pub nproofs: usize,
+    pub has_accumulator: bool,
+    pub kind: MultiOpenKind,
+    pub padding: bool,
+    pub transcript_kind: TranscriptKind,
+    pub poseidon_spec: TranscriptSpec,
 }
 
 impl Setup<G1Affine, Bn256> {
     pub fn new<SingleCircuit: TargetCircuit<Bn256>, L>(
         folder: &PathBuf,
         load_instances: L,
-    ) -> Setup<G1Affine, Bn256>
+    ) -> Result<Setup<G1Affine, Bn256>, AggregatorError>
     where
-        L: Fn(&[u8]) -> Vec<Vec<Vec<Fr>>>,
+        L: Fn(&[u8]) -> Result<Vec<Vec<Vec<Fr>>>, AggregatorError>,
     {
-        let target_circuit_instances = (0..SingleCircuit::N_PROOFS).map(|index| {
-            load_instances(&load_target_circuit_instance::<SingleCircuit>(
-                &mut folder.clone(),
-                index,
-            ))
-        });
-
-        let proofs = (0..SingleCircuit::N_PROOFS)
-            .map(|index| load_target_circuit_proof::<SingleCircuit>(&mut folder.clone(), index));
-
-        let single_proof_witness = target_circuit_instances
-            .zip(proofs)
-            .map(|(instances, transcript)| SingleProofPair::<Bn256> {
-                instances,
-                transcript,
-            })
-            .collect::<Vec<_>>();
+        check_padding_support(
+            SingleCircuit::NAME,
+            SingleCircuit::PADDING,
+            SingleCircuit::HAS_ACCUMULATOR,
+        )?;
+        let single_proof_witness = load_proof_slots::<SingleCircuit, _>(folder, load_instances)?;
+        let nproofs = single_proof_witness.len();
+        check_transcript::<SingleCircuit>(&single_proof_witness)?;
 
         let target_circuit_params =
-            load_target_circuit_params::<Bn256, SingleCircuit>(&mut folder.clone());
+            load_target_circuit_params::<Bn256, SingleCircuit>(&mut folder.clone())?;
         let target_circuit_vk = load_target_circuit_vk::<Bn256, SingleCircuit>(
             &mut folder.clone(),
             &target_circuit_params,
-        );
+        )?;
+        check_instances_shape(
+            SingleCircuit::NAME,
+            &target_circuit_params,
+            &target_circuit_vk,
+            &single_proof_witness,
+        )?;
 
-        Setup {
-            name: format!("{:?}", folder),
+        Ok(Setup {
+            name: format!("{:?}_{}", folder, SingleCircuit::NAME),
             target_circuit_params: Rc::new(target_circuit_params),
             target_circuit_vk: Rc::new(target_circuit_vk),
             proofs: single_proof_witness,
-            nproofs: SingleCircuit::N_PROOFS,
-        }
+            nproofs,
+            has_accumulator: SingleCircuit::HAS_ACCUMULATOR,
+            kind: SingleCircuit::MULTIOPEN,
+            padding: SingleCircuit::PADDING,
+            transcript_kind: SingleCircuit::TRANSCRIPT,
+            poseidon_spec: SingleCircuit::POSEIDON_SPEC,
+        })
     }
 }
 
@@ -638,67 +1231,121 @@ struct SetupOutcome<C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C:
     vk: Rc<VerifyingKey<C>>,
     instances: Vec<Vec<Vec<Vec<C::ScalarExt>>>>,
     proofs: Vec<Vec<u8>>,
+    enabled: Vec<bool>,
     nproofs: usize,
+    has_accumulator: bool,
+    kind: MultiOpenKind,
+    padding: bool,
+    transcript_kind: TranscriptKind,
+    poseidon_spec: TranscriptSpec,
+}
+
+impl<C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt>> SetupOutcome<C, E> {
+    fn verifier_circuit(&self) -> Halo2VerifierCircuit<'_, E> {
+        Halo2VerifierCircuit {
+            name: self.name.clone(),
+            params: &self.params_verifier,
+            vk: &self.vk,
+            proofs: self
+                .instances
+                .iter()
+                .zip(self.proofs.iter())
+                .zip(self.enabled.iter())
+                .map(|((instances, transcript), enabled)| SingleProofWitness {
+                    instances,
+                    transcript,
+                    enabled: *enabled,
+                })
+                .collect(),
+            nproofs: self.nproofs,
+            has_accumulator: self.has_accumulator,
+            kind: self.kind,
+            padding: self.padding,
+            transcript_kind: self.transcript_kind,
+            poseidon_spec: self.poseidon_spec,
+        }
+    }
 }
 
 pub struct MultiCircuitsSetup<
     C: CurveAffine,
     E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug,
-    const N: usize,
 > {
-    pub setups: [Setup<C, E>; N],
-    pub coherent: Vec<[(usize, usize); 2]>,
+    pub setups: Vec<Setup<C, E>>,
+    pub coherent: Vec<[CommitmentRef; 2]>,
+    pub hash_instances: bool,
+    // Halo2 params of an imported SRS, downsized to the verify circuit. The target
+    // circuits must have been set up from the same SRS.
+    pub srs: Option<PathBuf>,
 }
 
-fn from_0_to_n<const N: usize>() -> [usize; N] {
-    let mut arr = [0; N];
-    for (i, e) in arr.iter_mut().enumerate() {
-        *e = i;
+impl<C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug>
+    MultiCircuitsSetup<C, E>
+{
+    // The setups and coherent commitments are public fields, so they are checked
+    // before the circuit is built.
+    fn check(&self) -> Result<(), AggregatorError> {
+        for setup in self.setups.iter() {
+            check_padding_support(&setup.name, setup.padding, setup.has_accumulator)?;
+            check_same_srs(
+                &setup.name,
+                &setup.target_circuit_params,
+                &self.setups[0].target_circuit_params,
+            )?;
+        }
+        check_coherent(&self.setups, &self.coherent)
     }
-    arr
-}
 
-impl<
-        C: CurveAffine,
-        E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug,
-        const N: usize,
-    > MultiCircuitsSetup<C, E, N>
-{
-    fn new_verify_circuit_info(&self, setup: bool) -> [SetupOutcome<C, E>; N] {
-        from_0_to_n::<N>().map(|circuit_index| {
-            // let target_circuit_verifier_params = self.setups[circuit_index]
-            //     .target_circuit_params
-            //     .verifier::<E>(
-            //         self.setups[circuit_index]
-            //             .target_circuit_vk
-            //             .cs()
-            //             .num_instance_columns,
-            //     )
-            //     .unwrap();
-
-            let mut target_circuit_transcripts = vec![];
-            let mut target_circuit_instances = vec![];
-
-            for i in 0..self.setups[circuit_index].nproofs {
-                let index = if setup { 0 } else { i };
-                target_circuit_transcripts
-                    .push(self.setups[circuit_index].proofs[index].transcript.clone());
-                target_circuit_instances
-                    .push(self.setups[circuit_index].proofs[index].instances.clone());
-            }
+    fn new_verify_circuit_info(&self, setup: bool) -> Vec<SetupOutcome<C, E>> {
+        (0..self.setups.len())
+            .map(|circuit_index| {
+                // let target_circuit_verifier_params = self.setups[circuit_index]
+                //     .target_circuit_params
+                //     .verifier::<E>(
+                //         self.setups[circuit_index]
+                //             .target_circuit_vk
+                //             .cs()
+                //             .num_instance_columns,
+                //     )
+                //     .unwrap();
+
+                let mut target_circuit_transcripts = vec![];
+                let mut target_circuit_instances = vec![];
+                let mut target_circuit_enabled = vec![];
+
+                for i in 0..self.setups[circuit_index].nproofs {
+                    let index = if setup { 0 } else { i };
+                    target_circuit_transcripts
+                        .push(self.setups[circuit_index].proofs[index].transcript.clone());
+                    target_circuit_instances
+                        .push(self.setups[circuit_index].proofs[index].instances.clone());
+                    target_circuit_enabled.push(self.setups[circuit_index].proofs[index].enabled);
+                }
 
-            SetupOutcome::<C, E> {
-                name: self.setups[circuit_index].name.clone(),
-                params_verifier: self.setups[circuit_index].target_circuit_params.clone(),
-                vk: self.setups[circuit_index].target_circuit_vk.clone(),
-                instances: target_circuit_instances,
-                proofs: target_circuit_transcripts,
-                nproofs: self.setups[circuit_index].nproofs,
-            }
-        })
+                SetupOutcome::<C, E> {
+                    name: self.setups[circuit_index].name.clone(),
+                    params_verifier: self.setups[circuit_index].target_circuit_params.clone(),
+                    vk: self.setups[circuit_index].target_circuit_vk.clone(),
+                    instances: target_circuit_instances,
+                    proofs: target_circuit_transcripts,
+                    enabled: target_circuit_enabled,
+                    nproofs: self.setups[circuit_index].nproofs,
+                    has_accumulator: self.setups[circuit_index].has_accumulator,
+                    kind: self.setups[circuit_index].kind,
+                    padding: self.setups[circuit_index].padding,
+                    transcript_kind: self.setups[circuit_index].transcript_kind,
+                    poseidon_spec: self.setups[circuit_index].poseidon_spec,
+                }
+            })
+            .collect()
     }
 
-    fn get_params_cached(k: u32) -> ParamsKZG<E> {
+    fn get_params_cached(&self, k: u32) -> Result<ParamsKZG<E>, AggregatorError> {
+        if let Some(srs) = &self.srs {
+            info!("read params from srs {:?}", srs);
+            return load_params_srs(&mut std::fs::File::open(srs)?, k);
+        }
+
         let params_path = format!("HALO2_PARAMS_{}", k);
 
         let path = var(params_path);
@@ -710,58 +1357,58 @@ impl<
             _ => None,
         };
 
-        println!("params path: {:?}", path);
+        log::debug!("params path: {:?}", path);
         if path.is_some() && Path::exists(path.unwrap()) {
-            println!("read params from {:?}", path.unwrap());
-            let mut fd = std::fs::File::open(path.unwrap()).unwrap();
-            ParamsKZG::<E>::read(&mut fd).unwrap()
+            info!("read params from {:?}", path.unwrap());
+            let mut fd = std::fs::File::open(path.unwrap())?;
+            ParamsKZG::<E>::read(&mut fd)
+                .map_err(|err| AggregatorError::Deserialize(format!("{:?}: {}", path, err)))
         } else {
-            let params = ParamsKZG::<E>::unsafe_setup(k);
+            let params = unsafe_srs::<E>(k);
 
             if let Some(path) = path {
-                println!("write params to {:?}", path);
+                info!("write params to {:?}", path);
 
-                let mut fd = std::fs::File::create(path).unwrap();
+                let mut fd = std::fs::File::create(path)?;
 
-                params.write(&mut fd).unwrap();
+                params.write(&mut fd)?;
             };
 
-            params
+            Ok(params)
         }
     }
 
-    pub fn call(&self, verify_circuit_k: u32) -> (ParamsKZG<E>, VerifyingKey<C>) {
+    pub fn call(
+        &self,
+        verify_circuit_k: u32,
+    ) -> Result<(ParamsKZG<E>, VerifyingKey<C>), AggregatorError> {
+        self.check()?;
         let setup_outcome = self.new_verify_circuit_info(true);
 
         let verify_circuit = verify_circuit_builder(
-            from_0_to_n::<N>().map(|i| Halo2VerifierCircuit {
-                name: setup_outcome[i].name.clone(),
-                params: &setup_outcome[i].params_verifier,
-                vk: &setup_outcome[i].vk,
-                proofs: setup_outcome[i]
-                    .instances
-                    .iter()
-                    .zip(setup_outcome[i].proofs.iter())
-                    .map(|(instances, transcript)| SingleProofWitness {
-                        instances,
-                        transcript,
-                    })
-                    .collect(),
-                nproofs: setup_outcome[i].nproofs,
-            }),
+            setup_outcome
+                .iter()
+                .map(|outcome| outcome.verifier_circuit())
+                .collect(),
             self.coherent.clone(),
+            self.hash_instances,
         );
         info!("circuit build done");
 
-        // TODO: Do not use this setup in production
-        let verify_circuit_params = Self::get_params_cached(verify_circuit_k);
+        let verify_circuit_params = self.get_params_cached(verify_circuit_k)?;
+        for setup in self.setups.iter() {
+            check_same_srs(
+                &setup.name,
+                &setup.target_circuit_params,
+                &verify_circuit_params,
+            )?;
+        }
         info!("setup params done");
 
-        let verify_circuit_vk =
-            keygen_vk(&verify_circuit_params, &verify_circuit).expect("keygen_vk should not fail");
+        let verify_circuit_vk = keygen_vk(&verify_circuit_params, &verify_circuit)?;
         info!("setup vkey done");
 
-        (verify_circuit_params, verify_circuit_vk)
+        Ok((verify_circuit_params, verify_circuit_vk))
     }
 }
 
@@ -803,6 +1450,60 @@ pub fn final_pair_to_instances<
     verify_circuit_instances
 }
 
+fn limbs_to_point<C: CurveAffine>(
+    helper: &FiveColumnIntegerChipHelper<C::Base, C::ScalarExt>,
+    low: &C::ScalarExt,
+    high: &C::ScalarExt,
+) -> Option<C> {
+    let shift = &helper.limb_modulus * &helper.limb_modulus;
+    let high = field_to_bn(high);
+    let y_bit = (&high / &shift).bit(0);
+    let x = field_to_bn(low) + (&high % &shift) * &shift;
+    let x: C::Base = bn_to_field(&x);
+    let y: Option<C::Base> = (x.square() * x + C::b()).sqrt().into();
+    let y = y.map(|y| {
+        if field_to_bn(&y).bit(0) == y_bit {
+            y
+        } else {
+            -y
+        }
+    })?;
+    C::from_xy(x, y).into()
+}
+
+fn accumulator_from_instances<C: CurveAffine>(instances: &[C::ScalarExt]) -> Option<(C, C)> {
+    let helper = FiveColumnIntegerChipHelper::<C::Base, C::ScalarExt>::new();
+    let w_x = limbs_to_point::<C>(&helper, &instances[0], &instances[1])?;
+    let w_g = limbs_to_point::<C>(&helper, &instances[2], &instances[3])?;
+    Some((w_x, w_g))
+}
+
+// Inverse of final_pair_to_instances on the first `ACCUMULATOR_INSTANCES` instances.
+pub fn instances_to_accumulator<C: CurveAffine>(
+    instances: &[C::ScalarExt],
+) -> Result<(C, C), AggregatorError> {
+    if instances.len() < ACCUMULATOR_INSTANCES {
+        return Err(AggregatorError::Deserialize(format!(
+            "{} instances, the accumulator takes {}",
+            instances.len(),
+            ACCUMULATOR_INSTANCES
+        )));
+    }
+    accumulator_from_instances::<C>(instances).ok_or_else(|| {
+        AggregatorError::Deserialize("accumulator in instances is not a valid point".to_owned())
+    })
+}
+
+// Native counterpart of the digest exposed by a verify circuit with `hash_instances`.
+pub fn instances_digest<C: CurveAffine>(instances: &[C::ScalarExt]) -> C::ScalarExt {
+    let chip = MockFieldChip::<C::ScalarExt, Error>::default();
+    let ctx = &mut MockChipCtx::default();
+
+    let mut hasher = PoseidonChip::from_spec(ctx, &chip, &HALO2_TRANSCRIPT_SPEC).unwrap();
+    hasher.update(instances);
+    hasher.squeeze(ctx, &chip).unwrap()
+}
+
 pub fn calc_verify_circuit_instances<
     C: CurveAffine,
     E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug,
@@ -812,16 +1513,24 @@ pub fn calc_verify_circuit_instances<
     vk: &VerifyingKey<C>,
     n_instances: &Vec<Vec<Vec<Vec<E::Scalar>>>>,
     n_transcript: &Vec<Vec<u8>>,
-) -> Vec<C::ScalarExt> {
-    let pair = Halo2CircuitInstances([Halo2CircuitInstance {
+    has_accumulator: bool,
+    kind: MultiOpenKind,
+) -> Result<Vec<C::ScalarExt>, AggregatorError> {
+    let pair = Halo2CircuitInstances(vec![Halo2CircuitInstance {
         name,
         params,
         vk,
         n_instances,
         n_transcript,
+        n_enabled: &vec![true; n_instances.len()],
+        has_accumulator,
+        kind,
+        padding: false,
+        transcript_kind: TranscriptKind::Poseidon,
+        poseidon_spec: HALO2_TRANSCRIPT_SPEC,
     }])
-    .calc_verify_circuit_final_pair();
-    final_pair_to_instances::<C, E>(&pair)
+    .calc_verify_circuit_final_pair()?;
+    Ok(final_pair_to_instances::<C, E>(&pair))
 }
 
 pub struct CreateProof<C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt>> {
@@ -831,49 +1540,56 @@ pub struct CreateProof<C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar =
     pub template_proofs: Vec<SingleProofPair<E>>,
     pub proofs: Vec<SingleProofPair<E>>,
     pub nproofs: usize,
+    pub has_accumulator: bool,
+    pub kind: MultiOpenKind,
+    pub padding: bool,
+    pub transcript_kind: TranscriptKind,
+    pub poseidon_spec: TranscriptSpec,
 }
 
 impl CreateProof<G1Affine, Bn256> {
     pub fn new<SingleCircuit: TargetCircuit<Bn256>, L>(
         folder: &PathBuf,
         load_instances: L,
-    ) -> CreateProof<G1Affine, Bn256>
+    ) -> Result<CreateProof<G1Affine, Bn256>, AggregatorError>
     where
-        L: Fn(&[u8]) -> Vec<Vec<Vec<Fr>>>,
+        L: Fn(&[u8]) -> Result<Vec<Vec<Vec<Fr>>>, AggregatorError>,
     {
-        let instances = (0..SingleCircuit::N_PROOFS).map(|index| {
-            load_instances(&load_target_circuit_instance::<SingleCircuit>(
-                &mut folder.clone(),
-                index,
-            ))
-        });
-
-        let proofs = (0..SingleCircuit::N_PROOFS)
-            .map(|index| load_target_circuit_proof::<SingleCircuit>(&mut folder.clone(), index));
-
-        let single_proof_witness = instances
-            .zip(proofs)
-            .map(|(instances, transcript)| SingleProofPair::<Bn256> {
-                instances,
-                transcript,
-            })
-            .collect::<Vec<_>>();
+        check_padding_support(
+            SingleCircuit::NAME,
+            SingleCircuit::PADDING,
+            SingleCircuit::HAS_ACCUMULATOR,
+        )?;
+        let single_proof_witness = load_proof_slots::<SingleCircuit, _>(folder, load_instances)?;
+        let nproofs = single_proof_witness.len();
+        check_transcript::<SingleCircuit>(&single_proof_witness)?;
 
         let target_circuit_params =
-            load_target_circuit_params::<Bn256, SingleCircuit>(&mut folder.clone());
+            load_target_circuit_params::<Bn256, SingleCircuit>(&mut folder.clone())?;
         let target_circuit_vk = load_target_circuit_vk::<Bn256, SingleCircuit>(
             &mut folder.clone(),
             &target_circuit_params,
-        );
+        )?;
+        check_instances_shape(
+            SingleCircuit::NAME,
+            &target_circuit_params,
+            &target_circuit_vk,
+            &single_proof_witness,
+        )?;
 
-        CreateProof {
-            name: format!("{:?}", folder),
+        Ok(CreateProof {
+            name: format!("{:?}_{}", folder, SingleCircuit::NAME),
             target_circuit_params: Rc::new(target_circuit_params),
             target_circuit_vk: Rc::new(target_circuit_vk),
             template_proofs: single_proof_witness.clone(),
             proofs: single_proof_witness,
-            nproofs: SingleCircuit::N_PROOFS,
-        }
+            nproofs,
+            has_accumulator: SingleCircuit::HAS_ACCUMULATOR,
+            kind: SingleCircuit::MULTIOPEN,
+            padding: SingleCircuit::PADDING,
+            transcript_kind: SingleCircuit::TRANSCRIPT,
+            poseidon_spec: SingleCircuit::POSEIDON_SPEC,
+        })
     }
 }
 
@@ -881,60 +1597,126 @@ pub struct MultiCircuitsCreateProof<
     'a,
     C: CurveAffine,
     E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt>,
-    const N: usize,
 > {
-    pub target_circuit_proofs: [CreateProof<C, E>; N],
+    pub target_circuit_proofs: Vec<CreateProof<C, E>>,
     pub verify_circuit_params: &'a ParamsKZG<E>,
     pub verify_circuit_vk: VerifyingKey<C>,
-    pub coherent: Vec<[(usize, usize); 2]>,
+    // Proving key of a previous run, see `load_verify_circuit_pk`, `keygen_pk` runs
+    // when it is `None`.
+    pub verify_circuit_pk: Option<ProvingKey<C>>,
+    pub coherent: Vec<[CommitmentRef; 2]>,
+    pub hash_instances: bool,
+    pub transcript_hash: TranscriptHash,
+}
+
+// Wall clock time of the phases of `MultiCircuitsCreateProof`. `synthesize` covers
+// the build of the verify circuit and the native computation of its instances.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProofTimings {
+    pub synthesize: Duration,
+    pub keygen_pk: Duration,
+    pub create_proof: Duration,
+}
+
+impl ProofTimings {
+    pub fn to_json(&self) -> String {
+        serde_json::json!({
+            "synthesize_ms": self.synthesize.as_millis() as u64,
+            "keygen_pk_ms": self.keygen_pk.as_millis() as u64,
+            "create_proof_ms": self.create_proof.as_millis() as u64,
+        })
+        .to_string()
+    }
+}
+
+fn create_verify_circuit_proof<
+    C: CurveAffine,
+    E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug,
+    T: TranscriptWrite<C, Challenge255<C>>,
+>(
+    params: &ParamsKZG<E>,
+    pk: &ProvingKey<C>,
+    circuit: Halo2VerifierCircuits<'_, E>,
+    instances: &[&[&[C::ScalarExt]]],
+    transcript: &mut T,
+) -> Result<(), AggregatorError> {
+    create_proof::<KZGCommitmentScheme<_>, ProverGWC<_>, _, _, _, _>(
+        params,
+        pk,
+        &[circuit],
+        instances,
+        OsRng,
+        transcript,
+    )?;
+    Ok(())
 }
 
-impl<
-        C: CurveAffine,
-        E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug,
-        const N: usize,
-    > MultiCircuitsCreateProof<'_, C, E, N>
+impl<C: CurveAffine, E: MultiMillerLoop<G1Affine = C, Scalar = C::ScalarExt> + Debug>
+    MultiCircuitsCreateProof<'_, C, E>
 {
     pub fn call(
         self,
-    ) -> (
-        ProvingKey<C>,
-        (C, C, Vec<C::ScalarExt>),
-        Vec<C::ScalarExt>,
-        Vec<u8>,
-    ) {
+    ) -> Result<
+        (
+            ProvingKey<C>,
+            (C, C, Vec<C::ScalarExt>),
+            Vec<C::ScalarExt>,
+            Vec<u8>,
+        ),
+        AggregatorError,
+    > {
+        let (pk, final_pair, instances, proof, _) = self.call_with_timings()?;
+        Ok((pk, final_pair, instances, proof))
+    }
+
+    pub fn call_with_timings(
+        self,
+    ) -> Result<
+        (
+            ProvingKey<C>,
+            (C, C, Vec<C::ScalarExt>),
+            Vec<C::ScalarExt>,
+            Vec<u8>,
+            ProofTimings,
+        ),
+        AggregatorError,
+    > {
+        let mut timings = ProofTimings::default();
         let setup = MultiCircuitsSetup {
-            setups: self.target_circuit_proofs.map(|target_circuit| Setup {
-                name: target_circuit.name,
-                target_circuit_params: target_circuit.target_circuit_params,
-                target_circuit_vk: target_circuit.target_circuit_vk,
-                proofs: target_circuit.template_proofs, // template_proofs?
-                nproofs: target_circuit.nproofs,
-            }),
+            setups: self
+                .target_circuit_proofs
+                .into_iter()
+                .map(|target_circuit| Setup {
+                    name: target_circuit.name,
+                    target_circuit_params: target_circuit.target_circuit_params,
+                    target_circuit_vk: target_circuit.target_circuit_vk,
+                    proofs: target_circuit.template_proofs, // template_proofs?
+                    nproofs: target_circuit.nproofs,
+                    has_accumulator: target_circuit.has_accumulator,
+                    kind: target_circuit.kind,
+                    padding: target_circuit.padding,
+                    transcript_kind: target_circuit.transcript_kind,
+                    poseidon_spec: target_circuit.poseidon_spec,
+                })
+                .collect(),
             coherent: self.coherent.clone(),
+            hash_instances: self.hash_instances,
+            srs: None,
         };
 
-        let now = std::time::Instant::now();
+        setup.check()?;
+
+        let now = Instant::now();
 
         let setup_outcome = setup.new_verify_circuit_info(false);
         let verify_circuit = {
             verify_circuit_builder(
-                from_0_to_n::<N>().map(|i| Halo2VerifierCircuit {
-                    name: setup_outcome[i].name.clone(),
-                    params: &setup_outcome[i].params_verifier,
-                    vk: &setup_outcome[i].vk,
-                    proofs: setup_outcome[i]
-                        .instances
-                        .iter()
-                        .zip(setup_outcome[i].proofs.iter())
-                        .map(|(instances, transcript)| SingleProofWitness {
-                            instances,
-                            transcript,
-                        })
-                        .collect(),
-                    nproofs: setup_outcome[i].nproofs,
-                }),
+                setup_outcome
+                    .iter()
+                    .map(|outcome| outcome.verifier_circuit())
+                    .collect(),
                 self.coherent,
+                self.hash_instances,
             )
         };
 
@@ -943,7 +1725,7 @@ impl<
             let mut verify_circuit_transcripts = vec![];
             let mut verify_circuit_instances = vec![];
 
-            for i in 0..N {
+            for i in 0..self.target_circuit_proofs.len() {
                 for j in 0..self.target_circuit_proofs[i].nproofs {
                     verify_circuit_transcripts
                         .push(self.target_circuit_proofs[i].proofs[j].transcript.clone());
@@ -958,54 +1740,92 @@ impl<
 
         let setup_outcome = setup.new_verify_circuit_info(false);
 
-        let verify_circuit_final_pair = {
-            Halo2CircuitInstances(from_0_to_n::<N>().map(|i| Halo2CircuitInstance {
-                name: setup_outcome[i].name.clone(),
-                params: &setup_outcome[i].params_verifier,
-                vk: &setup_outcome[i].vk,
-                n_instances: &setup_outcome[i].instances,
-                n_transcript: &setup_outcome[i].proofs,
-            }))
-            .calc_verify_circuit_final_pair()
+        let target_circuit_instances = Halo2CircuitInstances(
+            setup_outcome
+                .iter()
+                .map(|outcome| Halo2CircuitInstance {
+                    name: outcome.name.clone(),
+                    params: &outcome.params_verifier,
+                    vk: &outcome.vk,
+                    n_instances: &outcome.instances,
+                    n_transcript: &outcome.proofs,
+                    n_enabled: &outcome.enabled,
+                    has_accumulator: outcome.has_accumulator,
+                    kind: outcome.kind,
+                    padding: outcome.padding,
+                    transcript_kind: outcome.transcript_kind,
+                    poseidon_spec: outcome.poseidon_spec,
+                })
+                .collect(),
+        );
+        target_circuit_instances.pre_verify()?;
+
+        let verify_circuit_final_pair =
+            target_circuit_instances.calc_verify_circuit_final_pair()?;
+
+        let verify_circuit_instances = if self.hash_instances {
+            let (w_x, w_g, instances) = &verify_circuit_final_pair;
+            final_pair_to_instances::<C, E>(&(*w_x, *w_g, vec![instances_digest::<C>(instances)]))
+        } else {
+            final_pair_to_instances::<C, E>(&verify_circuit_final_pair)
         };
 
-        let verify_circuit_instances = final_pair_to_instances::<C, E>(&verify_circuit_final_pair);
+        timings.synthesize = now.elapsed();
+        info!("synthesize took {:?}", timings.synthesize);
 
-        let verify_circuit_pk = keygen_pk(
-            self.verify_circuit_params,
-            self.verify_circuit_vk,
-            &verify_circuit,
-        )
-        .expect("keygen_pk should not fail");
+        let now = Instant::now();
+        let verify_circuit_pk = match self.verify_circuit_pk {
+            Some(pk) => {
+                info!("use the cached verify circuit pk");
+                pk
+            }
+            None => keygen_pk(
+                self.verify_circuit_params,
+                self.verify_circuit_vk,
+                &verify_circuit,
+            )?,
+        };
 
-        let elapsed_time = now.elapsed();
-        info!("Running keygen_pk took {} seconds.", elapsed_time.as_secs());
+        timings.keygen_pk = now.elapsed();
+        info!("keygen_pk took {:?}", timings.keygen_pk);
 
+        let now = Instant::now();
         let instances: &[&[&[C::ScalarExt]]] = &[&[&verify_circuit_instances[..]]];
-        let mut transcript = ShaWrite::<_, C, Challenge255<_>, sha2::Sha256>::init(vec![]);
-        create_proof::<KZGCommitmentScheme<_>, ProverGWC<_>, _, _, _, _>(
-            self.verify_circuit_params,
-            &verify_circuit_pk,
-            &[verify_circuit],
-            instances,
-            OsRng,
-            &mut transcript,
-        )
-        .expect("proof generation should not fail");
-        let proof = transcript.finalize();
+        let proof = match self.transcript_hash {
+            TranscriptHash::Sha256 => {
+                let mut transcript = ShaWrite::<_, C, Challenge255<_>, sha2::Sha256>::init(vec![]);
+                create_verify_circuit_proof(
+                    self.verify_circuit_params,
+                    &verify_circuit_pk,
+                    verify_circuit,
+                    instances,
+                    &mut transcript,
+                )?;
+                transcript.finalize()
+            }
+            TranscriptHash::Keccak256 => {
+                let mut transcript = ShaKeccakWrite::<_, C, Challenge255<_>>::init(vec![]);
+                create_verify_circuit_proof(
+                    self.verify_circuit_params,
+                    &verify_circuit_pk,
+                    verify_circuit,
+                    instances,
+                    &mut transcript,
+                )?;
+                transcript.finalize()
+            }
+        };
 
-        let elapsed_time = now.elapsed();
-        println!(
-            "Running create proof took {} seconds.",
-            elapsed_time.as_secs()
-        );
+        timings.create_proof = now.elapsed();
+        info!("create_proof took {:?}", timings.create_proof);
 
-        (
+        Ok((
             verify_circuit_pk,
             verify_circuit_final_pair,
             verify_circuit_instances,
             proof,
-        )
+            timings,
+        ))
     }
 }
 
@@ -1015,22 +1835,49 @@ pub struct VerifyCheck<E: Engine> {
     pub verify_instance: Vec<Vec<Vec<E::Scalar>>>,
     pub verify_public_inputs_size: usize,
     pub verify_proof: Vec<u8>,
+    pub transcript_hash: TranscriptHash,
 }
 
 impl VerifyCheck<Bn256> {
-    pub fn new(folder: &PathBuf, verify_public_inputs_size: usize) -> VerifyCheck<Bn256> {
-        VerifyCheck::<Bn256> {
-            verify_params: Rc::new(load_verify_circuit_params(&mut folder.clone())),
-            verify_vk: Rc::new(load_verify_circuit_vk(&mut folder.clone())),
-            verify_instance: load_verify_circuit_instance(&mut folder.clone()),
-            verify_proof: load_verify_circuit_proof(&mut folder.clone()),
+    pub fn new(
+        folder: &PathBuf,
+        verify_public_inputs_size: usize,
+        transcript_hash: TranscriptHash,
+    ) -> Result<VerifyCheck<Bn256>, AggregatorError> {
+        Ok(VerifyCheck::<Bn256> {
+            verify_params: Rc::new(load_verify_circuit_params(&mut folder.clone())?),
+            verify_vk: Rc::new(load_verify_circuit_vk(&mut folder.clone())?),
+            verify_instance: load_verify_circuit_instance(&mut folder.clone())?,
+            verify_proof: load_verify_circuit_proof(&mut folder.clone())?,
             verify_public_inputs_size,
-        }
+            transcript_hash,
+        })
     }
 }
 
 impl<E: Engine + MultiMillerLoop + Debug> VerifyCheck<E> {
-    pub fn call(&self) -> Result<(), Error> {
+    pub fn call(&self) -> Result<(), AggregatorError> {
+        let res = match self.transcript_hash {
+            TranscriptHash::Sha256 => {
+                self.verify_with_transcript(
+                    &mut ShaRead::<_, _, Challenge255<_>, sha2::Sha256>::init(
+                        &self.verify_proof[..],
+                    ),
+                )
+            }
+            TranscriptHash::Keccak256 => {
+                self.verify_with_transcript(&mut ShaKeccakRead::<_, _, Challenge255<_>>::init(
+                    &self.verify_proof[..],
+                ))
+            }
+        };
+        Ok(res?)
+    }
+
+    fn verify_with_transcript<T: TranscriptRead<E::G1Affine, Challenge255<E::G1Affine>>>(
+        &self,
+        transcript: &mut T,
+    ) -> Result<(), Error> {
         let strategy = SingleStrategy::new(&self.verify_params);
 
         let verify_circuit_instance1: Vec<Vec<&[E::Scalar]>> = self
@@ -1041,15 +1888,12 @@ impl<E: Engine + MultiMillerLoop + Debug> VerifyCheck<E> {
         let verify_circuit_instance2: Vec<&[&[E::Scalar]]> =
             verify_circuit_instance1.iter().map(|x| &x[..]).collect();
 
-        let mut transcript =
-            ShaRead::<_, _, Challenge255<_>, sha2::Sha256>::init(&self.verify_proof[..]);
-
         verify_proof::<_, VerifierGWC<_>, _, _, _>(
             &self.verify_params,
             &self.verify_vk,
             strategy,
             &verify_circuit_instance2[..],
-            &mut transcript,
+            transcript,
         )
     }
 }
@@ -4,12 +4,13 @@ use halo2_proofs::{
     plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Fixed, Instance},
     poly::Rotation,
 };
+use halo2_snark_aggregator_circuit::error::AggregatorError;
+use halo2_snark_aggregator_circuit::fs::load_instances;
 use halo2_snark_aggregator_circuit::sample_circuit::TargetCircuit;
 use halo2_snark_aggregator_sdk::zkaggregate;
 use halo2curves::pairing::MultiMillerLoop;
 use rand_core::OsRng;
-use std::{io::Read, marker::PhantomData};
-use zkevm_circuits::tx_circuit::PrimeField;
+use std::marker::PhantomData;
 
 // ANCHOR: instructions
 trait NumericInstructions<F: FieldExt>: Chip<F> {
@@ -342,16 +343,8 @@ impl<E: MultiMillerLoop> TargetCircuit<E> for TestCircuit {
         (circuit, instances)
     }
 
-    fn load_instances(buf: &[u8]) -> Vec<Vec<Vec<E::Scalar>>> {
-        let mut ret = vec![];
-        let cursor = &mut std::io::Cursor::new(buf);
-        let mut compressed = <E::Scalar as PrimeField>::Repr::default();
-
-        while cursor.read_exact(compressed.as_mut()).is_ok() {
-            ret.push(<E::Scalar as PrimeField>::from_repr(compressed).unwrap());
-        }
-
-        vec![vec![ret]]
+    fn load_instances(buf: &[u8]) -> Result<Vec<Vec<Vec<E::Scalar>>>, AggregatorError> {
+        load_instances::<E>(buf)
     }
 }
 
@@ -380,22 +373,14 @@ impl<E: MultiMillerLoop> TargetCircuit<E> for TestCircuit2 {
         (circuit, instances)
     }
 
-    fn load_instances(buf: &[u8]) -> Vec<Vec<Vec<E::Scalar>>> {
-        let mut ret = vec![];
-        let cursor = &mut std::io::Cursor::new(buf);
-        let mut compressed = <E::Scalar as PrimeField>::Repr::default();
-
-        while cursor.read_exact(compressed.as_mut()).is_ok() {
-            ret.push(<E::Scalar as PrimeField>::from_repr(compressed).unwrap());
-        }
-
-        vec![vec![ret]]
+    fn load_instances(buf: &[u8]) -> Result<Vec<Vec<Vec<E::Scalar>>>, AggregatorError> {
+        load_instances::<E>(buf)
     }
 }
 
-zkaggregate! {2, vec![], TestCircuit, TestCircuit2}
+zkaggregate! {vec![], TestCircuit, TestCircuit2}
 
-pub fn main() {
-    let builder = zkcli::builder(23);
+pub fn main() -> Result<(), zkcli::AggregatorError> {
+    let builder = zkcli::builder(23)?;
     builder.run()
 }
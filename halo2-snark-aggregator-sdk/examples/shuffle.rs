@@ -7,6 +7,7 @@ use halo2_proofs::{
 };
 use rand_core::{OsRng, RngCore};
 use std::iter;
+use halo2_snark_aggregator_circuit::error::AggregatorError;
 use halo2_snark_aggregator_circuit::sample_circuit::TargetCircuit;
 use halo2_snark_aggregator_sdk::zkaggregate;
 use halo2curves::pairing::MultiMillerLoop;
@@ -265,15 +266,15 @@ impl<E: MultiMillerLoop> TargetCircuit<E> for TestCircuit {
         (circuit, instances)
     }
 
-    fn load_instances(_buf: &[u8]) -> Vec<Vec<Vec<E::Scalar>>> {
-        vec![vec![]]
+    fn load_instances(_buf: &[u8]) -> Result<Vec<Vec<Vec<E::Scalar>>>, AggregatorError> {
+        Ok(vec![vec![]])
     }
 }
 
 
-zkaggregate! {1, vec![], TestCircuit}
+zkaggregate! {vec![], TestCircuit}
 
-fn main() {
-    let builder = zkcli::builder(22);
+fn main() -> Result<(), zkcli::AggregatorError> {
+    let builder = zkcli::builder(22)?;
     builder.run()
 }
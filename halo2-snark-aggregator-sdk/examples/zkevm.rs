@@ -3,6 +3,7 @@ use halo2_proofs::{
     circuit::{Layouter, SimpleFloorPlanner},
     plonk::{Circuit, ConstraintSystem, Error, Expression},
 };
+use halo2_snark_aggregator_circuit::error::AggregatorError;
 use halo2_snark_aggregator_circuit::sample_circuit::TargetCircuit;
 use halo2_snark_aggregator_sdk::zkaggregate;
 use halo2curves::bn256::{Bn256, Fr};
@@ -69,15 +70,15 @@ impl TargetCircuit<Bn256> for TestCircuit<Fr> {
         (Self::Circuit::default(), vec![])
     }
 
-    fn load_instances(_buf: &[u8]) -> Vec<Vec<Vec<Fr>>> {
-        vec![vec![]]
+    fn load_instances(_buf: &[u8]) -> Result<Vec<Vec<Vec<Fr>>>, AggregatorError> {
+        Ok(vec![vec![]])
     }
 }
 
 type ZkEvm = TestCircuit<Fr>;
-zkaggregate! {1, vec![], ZkEvm}
+zkaggregate! {vec![], ZkEvm}
 
-pub fn main() {
-    let builder = zkcli::builder(25);
+pub fn main() -> Result<(), zkcli::AggregatorError> {
+    let builder = zkcli::builder(25)?;
     builder.run()
 }
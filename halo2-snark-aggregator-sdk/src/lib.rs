@@ -1,93 +1,245 @@
+pub mod manifest;
+
 #[macro_export]
 macro_rules! zkaggregate {
-    ( $n:expr, $coherent:expr, $( $x:ident ),+ ) => {
+    ( $coherent:expr, $( $x:ident ),+ ) => {
         mod zkcli {
             $(
                 use super::$x;
             )*
-            use clap::Parser;
+            use clap::{Parser, Subcommand};
             use halo2_proofs::arithmetic::{CurveAffine};
             use halo2_proofs::plonk::{Circuit, VerifyingKey};
             use halo2_proofs::poly::commitment::Params;
+            pub use halo2_snark_aggregator_circuit::error::AggregatorError;
             use halo2_snark_aggregator_circuit::fs::*;
+            use halo2_snark_aggregator_circuit::srs::load_srs;
             use halo2_snark_aggregator_circuit::sample_circuit::{
                 sample_circuit_random_run, sample_circuit_setup, TargetCircuit,
             };
             use halo2_snark_aggregator_circuit::verify_circuit::{
-                CreateProof, Halo2VerifierCircuit, MultiCircuitsCreateProof,
+                CommitmentRef, CreateProof, Halo2VerifierCircuit, MultiCircuitsCreateProof,
                 MultiCircuitsSetup, Setup, SingleProofWitness, VerifyCheck, SingleProofPair,
+                TranscriptHash, HALO2_TRANSCRIPT_SPEC,
             };
-            use halo2_snark_aggregator_solidity::{SolidityGenerate, MultiCircuitSolidityGenerate};
+            use halo2_snark_aggregator_solidity::MultiCircuitSolidityGenerate;
+            use $crate::manifest::{BatchCircuit, Manifest, TranscriptKind};
             use log::info;
             use halo2curves::bn256::{Bn256, Fr, G1Affine};
             use std::io::{Cursor, Read, Write};
             use std::marker::PhantomData;
-            use std::path::PathBuf;
+            use std::path::{Path, PathBuf};
             use std::rc::Rc;
             use paste::paste;
 
 
             #[derive(Parser)]
             struct Cli {
-                // TODO: replace it with subcommand
-                #[clap(short, long)]
-                command: String,
                 #[clap(short, long, parse(from_os_str))]
                 folder_path: std::path::PathBuf,
+                /// Json manifest of the run, it overrides the configuration built in the cli
                 #[clap(short, long, parse(from_os_str))]
-                template_path: Option<std::path::PathBuf>,
+                manifest: Option<std::path::PathBuf>,
+                /// Size of the rayon thread pool [default: RAYON_NUM_THREADS or the number of cpus]
+                #[clap(long)]
+                threads: Option<usize>,
+                #[clap(subcommand)]
+                command: Command,
+            }
+
+            #[derive(Subcommand)]
+            enum Command {
+                /// Import a ptau file or halo2 params of a larger k as the SRS of the run
+                ImportSrs {
+                    #[clap(short, long, parse(from_os_str))]
+                    srs_path: std::path::PathBuf,
+                    /// k of the verify circuit
+                    #[clap(short, long)]
+                    k: Option<u32>,
+                },
+                /// Generate params and vkey of the target circuits
+                SampleSetup,
+                /// Create proofs of the target circuits with random inputs
+                SampleRun {
+                    /// Proofs per circuit
+                    #[clap(short, long)]
+                    nproofs: Option<usize>,
+                },
+                /// Generate params and vkey of the verify circuit
+                VerifySetup {
+                    /// k of the verify circuit
+                    #[clap(short, long)]
+                    k: Option<u32>,
+                },
+                /// Prove the verify circuit over the proofs of the folder
+                VerifyRun {
+                    #[clap(long, arg_enum)]
+                    transcript: Option<TranscriptKind>,
+                },
+                /// Check the proof of the verify circuit
+                VerifyCheck {
+                    #[clap(long, arg_enum)]
+                    transcript: Option<TranscriptKind>,
+                },
+                /// Generate the solidity verifier of the verify circuit
+                VerifySolidity {
+                    #[clap(short, long, parse(from_os_str))]
+                    template_path: std::path::PathBuf,
+                    /// Solidity file to write [default: verifier.sol in the folder]
+                    #[clap(short, long, parse(from_os_str))]
+                    output: Option<std::path::PathBuf>,
+                    #[clap(long, arg_enum)]
+                    transcript: Option<TranscriptKind>,
+                },
             }
 
             paste! {
                 pub struct CliBuilder {
                     args: Cli,
                     folder: PathBuf,
-                    template_folder: Option<PathBuf>,
+                    manifest: Manifest,
                     verify_circuit_k: u32,
+                    hash_instances: bool,
+                    transcript_hash: TranscriptHash,
+                    threads: Option<usize>,
                 }
             }
 
-            fn env_init() {
-                env_logger::init();
-                rayon::ThreadPoolBuilder::new()
-                    .num_threads(24)
-                    .build_global()
-                    .unwrap();
-            }
-
             paste! {
-                pub fn builder(verify_circuit_k: u32) -> CliBuilder {
-                    env_init();
-
+                pub fn builder(verify_circuit_k: u32) -> Result<CliBuilder, AggregatorError> {
                     let args = Cli::parse();
+                    let manifest = match &args.manifest {
+                        Some(path) => Manifest::load(path)?,
+                        None => Manifest::default(),
+                    };
+                    manifest.check_circuits(&[$(<$x as TargetCircuit<Bn256>>::NAME),*])?;
+
+                    // the cli may be embedded in a binary which already set a logger
+                    let _ = env_logger::try_init();
+
                     let folder = args.folder_path.clone();
-                    let template_folder = args.template_path.clone();
 
-                    CliBuilder {
+                    Ok(CliBuilder {
                         args,
                         folder,
-                        template_folder,
+                        manifest,
                         verify_circuit_k,
-                    }
+                        hash_instances: false,
+                        transcript_hash: TranscriptHash::default(),
+                        threads: None,
+                    })
                 }
             }
 
             impl CliBuilder {
+                // Expose a single digest of the aggregated instances.
+                pub fn hash_instances(mut self, hash_instances: bool) -> Self {
+                    self.hash_instances = hash_instances;
+                    self
+                }
+
+                // Digest of the transcript of the verify circuit proof, keccak256 is
+                // cheaper to check on chain.
+                pub fn transcript_hash(mut self, transcript_hash: TranscriptHash) -> Self {
+                    self.transcript_hash = transcript_hash;
+                    self
+                }
+
+                // The options of the command line come first, then the manifest, then
+                // the configuration of the builder.
+                fn get_verify_circuit_k(&self, k: Option<u32>) -> u32 {
+                    k.or(self.manifest.verify_circuit_k)
+                        .unwrap_or(self.verify_circuit_k)
+                }
+
+                fn get_threads(&self) -> Option<usize> {
+                    self.args.threads.or(self.manifest.threads).or(self.threads)
+                }
+
+                fn get_hash_instances(&self) -> bool {
+                    self.manifest.hash_instances.unwrap_or(self.hash_instances)
+                }
+
+                fn get_transcript_hash(&self, transcript: Option<TranscriptKind>) -> TranscriptHash {
+                    transcript
+                        .or(self.manifest.transcript)
+                        .map_or(self.transcript_hash, TranscriptHash::from)
+                }
+
+                fn get_coherent(&self, batch: &[BatchCircuit<'_>]) -> Result<Vec<[CommitmentRef; 2]>, AggregatorError> {
+                    Ok(self.manifest.coherent(batch)?.unwrap_or_else(|| $coherent))
+                }
+
+                // Circuits left out by the manifest or without proofs in the folder are
+                // not aggregated.
+                fn in_batch<SingleCircuit: TargetCircuit<Bn256>>(&self) -> bool {
+                    self.manifest.enabled(SingleCircuit::NAME)
+                        && count_target_circuit_proofs::<SingleCircuit>(&mut self.folder.clone()) > 0
+                }
+
+                // Size of the thread pool of the run. Without it, the commands run on
+                // the current rayon pool, the global one unless the caller installed
+                // another.
+                pub fn threads(mut self, threads: usize) -> Self {
+                    self.threads = Some(threads);
+                    self
+                }
+
+                fn circuit_public_input_size<SingleCircuit: TargetCircuit<Bn256>>(&self) -> usize {
+                    if !self.manifest.enabled(SingleCircuit::NAME) {
+                        return 0;
+                    }
+
+                    let nproofs = count_target_circuit_proofs::<SingleCircuit>(&mut self.folder.clone());
+                    if nproofs > 0 && SingleCircuit::PADDING {
+                        // padded slots come with a selector bit each
+                        SingleCircuit::N_PROOFS * (SingleCircuit::PUBLIC_INPUT_SIZE + 1)
+                    } else {
+                        nproofs * SingleCircuit::PUBLIC_INPUT_SIZE
+                    }
+                }
+
                 fn compute_verify_public_input_size(&self) -> usize {
+                    if self.get_hash_instances() {
+                        return 4 + 1;
+                    }
+
                     4
                     $(
-                        + <$x as TargetCircuit< Bn256>>::N_PROOFS * <$x as TargetCircuit< Bn256>>::PUBLIC_INPUT_SIZE
+                        + self.circuit_public_input_size::<$x>()
                     )*
                 }
 
-                fn dispatch_sample_setup(&self) {
+                // Imports the SRS once, at the largest k of the run, every setup then
+                // downsizes it.
+                fn dispatch_import_srs(&self, srs_path: &Path, k: Option<u32>) -> Result<(), AggregatorError> {
+                    let k = [self.get_verify_circuit_k(k) $(, <$x as TargetCircuit<Bn256>>::TARGET_CIRCUIT_K)*]
+                        .into_iter()
+                        .max()
+                        .unwrap();
+
+                    let params = load_srs(srs_path, k)?;
+                    write_srs_params(&mut self.folder.clone(), &params)
+                }
+
+                fn dispatch_sample_setup(&self) -> Result<(), AggregatorError> {
                     $(
-                        sample_circuit_setup::< Bn256, $x>(self.folder.clone());
+                        if self.manifest.enabled(<$x as TargetCircuit<Bn256>>::NAME) {
+                            sample_circuit_setup::< Bn256, $x>(self.folder.clone())?;
+                        }
                     )*
+                    Ok(())
                 }
 
-                fn sample_run_one_circuit<SingleCircuit: TargetCircuit<Bn256>>(&self) {
-                    for i in 0..SingleCircuit::N_PROOFS {
+                fn sample_run_one_circuit<SingleCircuit: TargetCircuit<Bn256>>(&self, nproofs: Option<usize>) -> Result<(), AggregatorError> {
+                    if !self.manifest.enabled(SingleCircuit::NAME) {
+                        return Ok(());
+                    }
+
+                    let nproofs = nproofs
+                        .or(self.manifest.nproofs(SingleCircuit::NAME))
+                        .unwrap_or(SingleCircuit::N_PROOFS);
+                    for i in 0..nproofs {
                         let (circuit, instances) = SingleCircuit::instance_builder();
 
                         sample_circuit_random_run::< Bn256, SingleCircuit>(
@@ -98,111 +250,170 @@ macro_rules! zkaggregate {
                                 .map(|instance| &instance[..])
                                 .collect::<Vec<_>>()[..],
                             i,
-                        );
+                        )?;
                     }
+                    Ok(())
                 }
 
-                fn dispatch_sample_run(&self) {
+                fn dispatch_sample_run(&self, nproofs: Option<usize>) -> Result<(), AggregatorError> {
                     $(
-                        self.sample_run_one_circuit::<$x>();
+                        self.sample_run_one_circuit::<$x>(nproofs)?;
                     )*
+                    Ok(())
                 }
 
-                fn dispatch_verify_setup(&self) {
-                    let setup: [Setup<_, _>; $n] = [
-                        $(
-                            Setup::new::<$x, _>(&self.folder, &<$x as TargetCircuit< Bn256>>::load_instances),
-                        )*
-                    ];
+                fn dispatch_verify_setup(&self, k: Option<u32>) -> Result<(), AggregatorError> {
+                    let mut setup: Vec<Setup<_, _>> = vec![];
+                    $(
+                        if self.in_batch::<$x>() {
+                            setup.push(Setup::new::<$x, _>(&self.folder, &<$x as TargetCircuit< Bn256>>::load_instances)?);
+                        }
+                    )*
 
-                    let request = MultiCircuitsSetup::<_, _, $n> {
+                    let batch = setup
+                        .iter()
+                        .map(|setup| BatchCircuit {
+                            name: &setup.name,
+                            nproofs: setup.nproofs,
+                            cs: setup.target_circuit_vk.cs(),
+                        })
+                        .collect::<Vec<_>>();
+                    let coherent = self.get_coherent(&batch)?;
+
+                    let request = MultiCircuitsSetup::<_, _> {
                         setups: setup,
-                        coherent: $coherent
+                        coherent,
+                        hash_instances: self.get_hash_instances(),
+                        srs: srs_params_path(&self.folder),
                     };
 
-                    let (params, vk) = request.call(self.verify_circuit_k);
+                    let (params, vk) = request.call(self.get_verify_circuit_k(k))?;
 
-                    write_verify_circuit_params(&mut self.folder.clone(), &params);
-                    write_verify_circuit_vk(&mut self.folder.clone(), &vk);
+                    write_verify_circuit_params(&mut self.folder.clone(), &params)?;
+                    write_verify_circuit_vk(&mut self.folder.clone(), &vk)
                 }
 
-                fn dispatch_verify_run(&self) {
-                    let target_circuit_proofs: [CreateProof<_, _>; $n] = [
-                        $(
-                            CreateProof::new::<$x, _>(&self.folder, &<$x as TargetCircuit< Bn256>>::load_instances),
-                        )*
-                    ];
+                fn dispatch_verify_run(&self, transcript: Option<TranscriptKind>) -> Result<(), AggregatorError> {
+                    let mut target_circuit_proofs: Vec<CreateProof<_, _>> = vec![];
+                    $(
+                        if self.in_batch::<$x>() {
+                            target_circuit_proofs.push(CreateProof::new::<$x, _>(&self.folder, &<$x as TargetCircuit< Bn256>>::load_instances)?);
+                        }
+                    )*
+
+                    let batch = target_circuit_proofs
+                        .iter()
+                        .map(|proof| BatchCircuit {
+                            name: &proof.name,
+                            nproofs: proof.nproofs,
+                            cs: proof.target_circuit_vk.cs(),
+                        })
+                        .collect::<Vec<_>>();
+                    let coherent = self.get_coherent(&batch)?;
+
+                    let verify_circuit_params = load_verify_circuit_params(&mut self.folder.clone())?;
+                    let verify_circuit_vk = load_verify_circuit_vk(&mut self.folder.clone())?;
+                    // The pk is kept across runs, it is regenerated when the vkey changes
+                    let verify_circuit_pk = load_verify_circuit_pk(
+                        &mut self.folder.clone(),
+                        &verify_circuit_params,
+                        &verify_circuit_vk,
+                    )?;
+                    let cached_pk = verify_circuit_pk.is_some();
 
-                    let request = MultiCircuitsCreateProof::<_, _, $n> {
+                    let request = MultiCircuitsCreateProof::<_, _> {
                         target_circuit_proofs,
-                        verify_circuit_params: &load_verify_circuit_params(&mut self.folder.clone()),
-                        verify_circuit_vk: load_verify_circuit_vk(&mut self.folder.clone()),
-                        coherent: $coherent
+                        verify_circuit_params: &verify_circuit_params,
+                        verify_circuit_vk,
+                        verify_circuit_pk,
+                        coherent,
+                        hash_instances: self.get_hash_instances(),
+                        transcript_hash: self.get_transcript_hash(transcript),
                     };
 
-                    let (_, final_pair, instance, proof) = request.call();
+                    let (pk, final_pair, instance, proof, timings) = request.call_with_timings()?;
 
-                    write_verify_circuit_instance(&mut self.folder.clone(), &instance);
-                    write_verify_circuit_proof(&mut self.folder.clone(), &proof);
-                    write_verify_circuit_final_pair(&mut self.folder.clone(), &final_pair);
+                    if !cached_pk {
+                        write_verify_circuit_pk(&mut self.folder.clone(), &pk)?;
+                    }
+                    write_verify_circuit_instance(&mut self.folder.clone(), &instance)?;
+                    write_verify_circuit_proof(&mut self.folder.clone(), &proof)?;
+                    write_verify_circuit_final_pair(&mut self.folder.clone(), &final_pair)?;
+                    write_verify_circuit_timings(&mut self.folder.clone(), &timings)
                 }
 
-                fn dispatch_verify_check(&self) {
-                    let request = VerifyCheck::<Bn256>::new(&self.folder, self.compute_verify_public_input_size());
-                    request.call().unwrap();
+                fn dispatch_verify_check(&self, transcript: Option<TranscriptKind>) -> Result<(), AggregatorError> {
+                    let request = VerifyCheck::<Bn256>::new(
+                        &self.folder,
+                        self.compute_verify_public_input_size(),
+                        self.get_transcript_hash(transcript),
+                    )?;
+                    request.call()?;
 
-                    info!("verify check succeed")
+                    info!("verify check succeed");
+                    Ok(())
                 }
 
-                fn dispatch_verify_solidity(&self) {
-                    let target_circuits_params: [SolidityGenerate<_>; $n] = [
-                        $(
-                            SolidityGenerate::new::<$x>(&self.folder),
-                        )*
-                    ];
-
+                fn dispatch_verify_solidity(
+                    &self,
+                    template_path: &Path,
+                    output: Option<&PathBuf>,
+                    transcript: Option<TranscriptKind>,
+                ) -> Result<(), AggregatorError> {
+                    // The contract only verifies the verify circuit proof, the target
+                    // circuits of the manifest are not loaded.
                     let request = MultiCircuitSolidityGenerate::<Bn256> {
-                        //target_circuits_params,
-                        verify_params: &load_verify_circuit_params(&mut self.folder.clone()),
-                        verify_vk: &load_verify_circuit_vk(&mut self.folder.clone()),
+                        verify_params: &load_verify_circuit_params(&mut self.folder.clone())?,
+                        verify_vk: &load_verify_circuit_vk(&mut self.folder.clone())?,
                         verify_circuit_instance: load_verify_circuit_instance(
                             &mut self.folder.clone(),
-                        ),
-                        proof: load_verify_circuit_proof(&mut self.folder.clone()),
+                        )?,
+                        proof: load_verify_circuit_proof(&mut self.folder.clone())?,
                         verify_public_inputs_size: self.compute_verify_public_input_size(),
+                        transcript_hash: self.get_transcript_hash(transcript),
+                        // No spec to load: the verify circuit proof has a sha or keccak
+                        // transcript, the specs of the target circuits are not read here.
+                        poseidon_spec: HALO2_TRANSCRIPT_SPEC,
                     };
 
-                    let sol = request.call(self.template_folder.clone().unwrap());
-
-                    write_verify_circuit_solidity(
-                        &mut self.folder.clone(),
-                        &Vec::<u8>::from(sol.as_bytes()),
-                    );
-                }
-
-                pub fn run(&self) {
-                    if self.args.command == "sample_setup" {
-                        self.dispatch_sample_setup();
-                    }
+                    let sol = request.call(template_path.to_path_buf());
 
-                    if self.args.command == "sample_run" {
-                        self.dispatch_sample_run();
-                    }
-
-                    if self.args.command == "verify_setup" {
-                        self.dispatch_verify_setup();
-                    }
-
-                    if self.args.command == "verify_run" {
-                        self.dispatch_verify_run();
+                    match output {
+                        Some(output) => Ok(std::fs::write(output, sol.as_bytes())?),
+                        None => write_verify_circuit_solidity(
+                            &mut self.folder.clone(),
+                            &Vec::<u8>::from(sol.as_bytes()),
+                        ),
                     }
+                }
 
-                    if self.args.command == "verify_check" {
-                        self.dispatch_verify_check();
+                // The pool is local to the run, the global pool is left to the binary.
+                pub fn run(&self) -> Result<(), AggregatorError> {
+                    match self.get_threads() {
+                        Some(threads) => rayon::ThreadPoolBuilder::new()
+                            .num_threads(threads)
+                            .build()
+                            .map_err(|err| {
+                                AggregatorError::Io(std::io::Error::new(std::io::ErrorKind::Other, err))
+                            })?
+                            .install(|| self.dispatch()),
+                        None => self.dispatch(),
                     }
+                }
 
-                    if self.args.command == "verify_solidity" {
-                        self.dispatch_verify_solidity();
+                fn dispatch(&self) -> Result<(), AggregatorError> {
+                    match &self.args.command {
+                        Command::ImportSrs { srs_path, k } => self.dispatch_import_srs(srs_path, *k),
+                        Command::SampleSetup => self.dispatch_sample_setup(),
+                        Command::SampleRun { nproofs } => self.dispatch_sample_run(*nproofs),
+                        Command::VerifySetup { k } => self.dispatch_verify_setup(*k),
+                        Command::VerifyRun { transcript } => self.dispatch_verify_run(*transcript),
+                        Command::VerifyCheck { transcript } => self.dispatch_verify_check(*transcript),
+                        Command::VerifySolidity {
+                            template_path,
+                            output,
+                            transcript,
+                        } => self.dispatch_verify_solidity(template_path, output.as_ref(), *transcript),
                     }
                 }
             }
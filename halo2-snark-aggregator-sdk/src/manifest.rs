@@ -0,0 +1,284 @@
+use clap::ArgEnum;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_snark_aggregator_circuit::error::AggregatorError;
+use halo2_snark_aggregator_circuit::verify_circuit::{
+    column_count, ColumnKind, CommitmentRef, TranscriptHash,
+};
+use halo2curves::bn256::Fr;
+use serde::Deserialize;
+use std::path::Path;
+
+#[derive(ArgEnum, Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptKind {
+    Sha256,
+    Keccak256,
+}
+
+impl From<TranscriptKind> for TranscriptHash {
+    fn from(kind: TranscriptKind) -> Self {
+        match kind {
+            TranscriptKind::Sha256 => TranscriptHash::Sha256,
+            TranscriptKind::Keccak256 => TranscriptHash::Keccak256,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestColumnKind {
+    Advice,
+    Fixed,
+    Instance,
+}
+
+impl From<ManifestColumnKind> for ColumnKind {
+    fn from(kind: ManifestColumnKind) -> Self {
+        match kind {
+            ManifestColumnKind::Advice => ColumnKind::Advice,
+            ManifestColumnKind::Fixed => ColumnKind::Fixed,
+            ManifestColumnKind::Instance => ColumnKind::Instance,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestCircuit {
+    // `TargetCircuit::NAME` of the circuit
+    pub name: String,
+    // Proofs created by sample-run, defaults to `TargetCircuit::N_PROOFS`
+    pub nproofs: Option<usize>,
+}
+
+// Same as `CommitmentRef`, but the circuit is given by name instead of its index in
+// the batch.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestCommitment {
+    pub circuit: String,
+    pub proof: usize,
+    #[serde(default)]
+    pub phase: usize,
+    pub kind: ManifestColumnKind,
+    pub index: usize,
+}
+
+// A circuit of the batch, as the coherence pairs of the manifest see it.
+pub struct BatchCircuit<'a> {
+    pub name: &'a str,
+    // Proof slots of the circuit in the batch
+    pub nproofs: usize,
+    // Constraint system of the target vkey
+    pub cs: &'a ConstraintSystem<Fr>,
+}
+
+// Configuration of an aggregation run. Every field is optional, the values given
+// to `zkaggregate!` and its builder apply to the missing ones, and the command line
+// options override the manifest.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub verify_circuit_k: Option<u32>,
+    pub threads: Option<usize>,
+    pub hash_instances: Option<bool>,
+    pub transcript: Option<TranscriptKind>,
+    // Circuits of the batch, all the circuits of the cli when empty
+    #[serde(default)]
+    pub circuits: Vec<ManifestCircuit>,
+    pub coherent: Option<Vec<[ManifestCommitment; 2]>>,
+}
+
+impl ManifestCommitment {
+    fn check(&self, circuit: &BatchCircuit<'_>) -> Result<(), AggregatorError> {
+        if self.proof >= circuit.nproofs {
+            return Err(AggregatorError::Manifest(format!(
+                "coherent commitment of proof {} of {} which has {} proofs",
+                self.proof, self.circuit, circuit.nproofs
+            )));
+        }
+
+        match column_count(circuit.cs, self.phase, self.kind.into()) {
+            Some(count) if self.index < count => Ok(()),
+            Some(count) => Err(AggregatorError::Manifest(format!(
+                "coherent commitment of {:?} column {} in phase {} of {} which has {} of them",
+                self.kind, self.index, self.phase, self.circuit, count
+            ))),
+            None => Err(AggregatorError::Manifest(format!(
+                "coherent commitment of {:?} column in phase {} of {} which has no such phase",
+                self.kind, self.phase, self.circuit
+            ))),
+        }
+    }
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self, AggregatorError> {
+        let buf = std::fs::read(path)?;
+        Self::from_slice(&buf)
+    }
+
+    pub fn from_slice(buf: &[u8]) -> Result<Self, AggregatorError> {
+        serde_json::from_slice(buf).map_err(|err| AggregatorError::Manifest(err.to_string()))
+    }
+
+    // `names` are the circuits the cli was built with.
+    pub fn check_circuits(&self, names: &[&str]) -> Result<(), AggregatorError> {
+        for circuit in self.circuits.iter() {
+            if !names.contains(&circuit.name.as_str()) {
+                return Err(AggregatorError::Manifest(format!(
+                    "unknown circuit {}",
+                    circuit.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn enabled(&self, name: &str) -> bool {
+        self.circuits.is_empty() || self.circuits.iter().any(|c| c.name == name)
+    }
+
+    pub fn nproofs(&self, name: &str) -> Option<usize> {
+        self.circuits
+            .iter()
+            .find(|c| c.name == name)
+            .and_then(|c| c.nproofs)
+    }
+
+    // Resolves the coherence pairs against the circuits of the batch, in the order
+    // they are aggregated.
+    pub fn coherent(
+        &self,
+        batch: &[BatchCircuit<'_>],
+    ) -> Result<Option<Vec<[CommitmentRef; 2]>>, AggregatorError> {
+        let resolve = |c: &ManifestCommitment| {
+            let circuit = batch
+                .iter()
+                .position(|circuit| circuit.name == c.circuit)
+                .ok_or_else(|| {
+                    AggregatorError::Manifest(format!(
+                        "coherent commitment of {} which is not in the batch",
+                        c.circuit
+                    ))
+                })?;
+            c.check(&batch[circuit])?;
+            Ok(CommitmentRef {
+                circuit,
+                proof: c.proof,
+                phase: c.phase,
+                kind: c.kind.into(),
+                index: c.index,
+            })
+        };
+
+        self.coherent
+            .as_ref()
+            .map(|pairs| {
+                pairs
+                    .iter()
+                    .map(|[a, b]| Ok([resolve(a)?, resolve(b)?]))
+                    .collect()
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest() {
+        let manifest = Manifest::from_slice(
+            br#"{
+                "verify_circuit_k": 23,
+                "transcript": "keccak256",
+                "circuits": [{ "name": "a", "nproofs": 2 }, { "name": "b" }],
+                "coherent": [[
+                    { "circuit": "b", "proof": 0, "kind": "advice", "index": 1 },
+                    { "circuit": "a", "proof": 1, "kind": "instance", "index": 0 }
+                ]]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.verify_circuit_k, Some(23));
+        assert_eq!(manifest.transcript, Some(TranscriptKind::Keccak256));
+        assert!(manifest.check_circuits(&["a", "b", "c"]).is_ok());
+        assert!(manifest.check_circuits(&["a"]).is_err());
+        assert!(manifest.enabled("b") && !manifest.enabled("c"));
+        assert_eq!(manifest.nproofs("a"), Some(2));
+        assert_eq!(manifest.nproofs("b"), None);
+
+        let mut cs = ConstraintSystem::<Fr>::default();
+        cs.advice_column();
+        cs.advice_column();
+        cs.instance_column();
+        let circuit = |name, nproofs| BatchCircuit {
+            name,
+            nproofs,
+            cs: &cs,
+        };
+
+        let coherent = manifest
+            .coherent(&[circuit("a", 2), circuit("b", 1)])
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            coherent,
+            vec![[
+                CommitmentRef::advice(1, 0, 0, 1),
+                CommitmentRef {
+                    circuit: 0,
+                    proof: 1,
+                    phase: 0,
+                    kind: ColumnKind::Instance,
+                    index: 0,
+                },
+            ]]
+        );
+        assert!(manifest.coherent(&[circuit("a", 2)]).is_err());
+        // proof 1 of a is out of the batch
+        assert!(manifest
+            .coherent(&[circuit("a", 1), circuit("b", 1)])
+            .is_err());
+
+        let coherent = |commitment: &str| {
+            Manifest::from_slice(
+                format!(
+                    r#"{{ "coherent": [[
+                        {},
+                        {{ "circuit": "a", "proof": 0, "kind": "advice", "index": 0 }}
+                    ]] }}"#,
+                    commitment
+                )
+                .as_bytes(),
+            )
+            .unwrap()
+            .coherent(&[circuit("a", 2), circuit("b", 1)])
+        };
+        assert!(
+            coherent(r#"{ "circuit": "b", "proof": 0, "kind": "advice", "index": 1 }"#).is_ok()
+        );
+        assert!(
+            coherent(r#"{ "circuit": "b", "proof": 0, "kind": "advice", "index": 2 }"#).is_err()
+        );
+        assert!(coherent(
+            r#"{ "circuit": "b", "proof": 0, "phase": 1, "kind": "advice", "index": 0 }"#
+        )
+        .is_err());
+        assert!(
+            coherent(r#"{ "circuit": "b", "proof": 0, "kind": "instance", "index": 1 }"#).is_err()
+        );
+        assert!(
+            coherent(r#"{ "circuit": "b", "proof": 0, "kind": "fixed", "index": 0 }"#).is_err()
+        );
+        assert!(
+            coherent(r#"{ "circuit": "b", "proof": 1, "kind": "fixed", "index": 0 }"#).is_err()
+        );
+
+        assert!(Manifest::from_slice(br#"{ "k": 23 }"#).is_err());
+        assert_eq!(Manifest::from_slice(b"{}").unwrap(), Manifest::default());
+    }
+}
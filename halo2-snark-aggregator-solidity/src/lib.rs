@@ -10,16 +10,23 @@ use crate::code_generator::ctx::SolidityCodeGeneratorContext;
 use crate::code_generator::linear_scan::memory_optimize;
 use crate::transcript::codegen::CodegenTranscriptRead;
 use code_generator::ctx::{CodeGeneratorCtx, G2Point, Statement};
+use digest::Digest;
 use halo2_proofs::arithmetic::CurveAffine;
 use halo2_proofs::arithmetic::Field;
 use halo2_proofs::plonk::VerifyingKey;
 use halo2_proofs::poly::kzg::commitment::ParamsKZG;
 use halo2_snark_aggregator_api::arith::{common::ArithCommonChip, ecc::ArithEccChip};
+use halo2_snark_aggregator_api::hash::poseidon::{TranscriptSpec, TRANSCRIPT_RATE, TRANSCRIPT_T};
+use halo2_snark_aggregator_api::systems::halo2::multiopen::GWCScheme;
 use halo2_snark_aggregator_api::systems::halo2::verify::{
     assign_instance_commitment, verify_single_proof_no_eval,
 };
-use halo2_snark_aggregator_circuit::fs::{load_target_circuit_params, load_target_circuit_vk};
+use halo2_snark_aggregator_circuit::error::AggregatorError;
+use halo2_snark_aggregator_circuit::fs::{
+    count_target_circuit_proofs, load_target_circuit_params, load_target_circuit_vk,
+};
 use halo2_snark_aggregator_circuit::sample_circuit::TargetCircuit;
+use halo2_snark_aggregator_circuit::verify_circuit::TranscriptHash;
 use halo2curves::bn256::Bn256;
 use halo2curves::group::{Curve, Group};
 use halo2curves::pairing::{Engine, MillerLoopResult, MultiMillerLoop};
@@ -32,6 +39,7 @@ use tera::{Context, Tera};
 
 fn render_verifier_sol_template<C: CurveAffine>(
     args: CodeGeneratorCtx,
+    transcript_hash: TranscriptHash,
     _template_folder: std::path::PathBuf,
 ) -> String {
     let mut ctx = Context::new();
@@ -122,6 +130,7 @@ fn render_verifier_sol_template<C: CurveAffine>(
     ctx.insert("memory_size", &args.memory_size);
     ctx.insert("instance_size", &args.instance_size);
     ctx.insert("absorbing_length", &args.absorbing_length);
+    ctx.insert("keccak", &(transcript_hash == TranscriptHash::Keccak256));
     Tera::one_off(include_str!("../templates/verifier.sol"), &ctx, false)
         .expect("failed to render template")
 }
@@ -154,24 +163,26 @@ pub struct SolidityGenerate<E: Engine> {
 }
 
 impl SolidityGenerate<Bn256> {
-    pub fn new<SingleCircuit: TargetCircuit<Bn256>>(folder: &PathBuf) -> SolidityGenerate<Bn256> {
+    pub fn new<SingleCircuit: TargetCircuit<Bn256>>(
+        folder: &PathBuf,
+    ) -> Result<SolidityGenerate<Bn256>, AggregatorError> {
         let target_circuit_params =
-            load_target_circuit_params::<Bn256, SingleCircuit>(&mut folder.clone());
+            load_target_circuit_params::<Bn256, SingleCircuit>(&mut folder.clone())?;
         let target_circuit_vk = load_target_circuit_vk::<Bn256, SingleCircuit>(
             &mut folder.clone(),
             &target_circuit_params,
-        );
+        )?;
 
-        SolidityGenerate {
+        Ok(SolidityGenerate {
             target_circuit_params,
             target_circuit_vk,
-            nproofs: SingleCircuit::N_PROOFS,
-        }
+            nproofs: count_target_circuit_proofs::<SingleCircuit>(&mut folder.clone()),
+        })
     }
 }
 
 pub struct MultiCircuitSolidityGenerate<'a, E: MultiMillerLoop> {
-    //pub target_circuits_params: [SolidityGenerate<E>; N],
+    //pub target_circuits_params: Vec<SolidityGenerate<E>>,
     pub verify_params: &'a ParamsKZG<E>,
     pub verify_vk: &'a VerifyingKey<E::G1Affine>,
     // serialized instance
@@ -179,10 +190,23 @@ pub struct MultiCircuitSolidityGenerate<'a, E: MultiMillerLoop> {
     // serialized proof
     pub proof: Vec<u8>,
     pub verify_public_inputs_size: usize,
+    // digest of the transcript of `proof`
+    pub transcript_hash: TranscriptHash,
+    // Round counts of the poseidon sponge of the codegen transcript. `proof` is read with
+    // `ShaRead` and the challenges come from `transcript_hash`, the sponge is only run in
+    // mock hash mode, so any spec gives the same contract.
+    pub poseidon_spec: TranscriptSpec,
 }
 
 impl<'a, E: MultiMillerLoop + Debug> MultiCircuitSolidityGenerate<'a, E> {
     pub fn call(&self, template_folder: std::path::PathBuf) -> String {
+        match self.transcript_hash {
+            TranscriptHash::Sha256 => self.generate::<sha2::Sha256>(template_folder),
+            TranscriptHash::Keccak256 => self.generate::<sha3::Keccak256>(template_folder),
+        }
+    }
+
+    fn generate<D: Digest + Clone>(&self, template_folder: std::path::PathBuf) -> String {
         let target_circuit_s_g2 = get_xy_from_g2point::<E>(self.verify_params.s_g2());
         let target_circuit_n_g2 = get_xy_from_g2point::<E>(-self.verify_params.g2());
 
@@ -198,9 +222,10 @@ impl<'a, E: MultiMillerLoop + Debug> MultiCircuitSolidityGenerate<'a, E> {
             E::G1Affine,
             _,
             PoseidonEncode<_>,
-            9usize,
-            8usize,
-        >::new(&self.proof[..], ctx, schip, 8usize, 63usize)
+            D,
+            TRANSCRIPT_T,
+            TRANSCRIPT_RATE,
+        >::new(&self.proof[..], ctx, schip, &self.poseidon_spec)
         .unwrap();
 
         let verify_circuit_instance1: Vec<Vec<&[E::Scalar]>> = self
@@ -233,6 +258,7 @@ impl<'a, E: MultiMillerLoop + Debug> MultiCircuitSolidityGenerate<'a, E> {
             verify_params,
             &mut transcript,
             "".to_owned(),
+            &GWCScheme,
         )
         .unwrap();
 
@@ -303,7 +329,11 @@ impl<'a, E: MultiMillerLoop + Debug> MultiCircuitSolidityGenerate<'a, E> {
         let sol_ctx: CodeGeneratorCtx = memory_optimize(sol_ctx);
         let sol_ctx: CodeGeneratorCtx = aggregate(sol_ctx);
 
-        let template = render_verifier_sol_template::<E::G1Affine>(sol_ctx, template_folder);
+        let template = render_verifier_sol_template::<E::G1Affine>(
+            sol_ctx,
+            self.transcript_hash,
+            template_folder,
+        );
         info!("generate solidity succeeds");
 
         template
@@ -1,6 +1,7 @@
 use crate::chips::ecc_chip::SolidityEccExpr;
 use crate::chips::scalar_chip::SolidityFieldExpr;
 use crate::code_generator::ctx::SolidityCodeGeneratorContext;
+use digest::Digest;
 use halo2_ecc_circuit_lib::utils::field_to_bn;
 use halo2_proofs::transcript::EncodedChallenge;
 use halo2_proofs::transcript::{Challenge255, Transcript, TranscriptRead};
@@ -9,7 +10,7 @@ use halo2_snark_aggregator_api::arith::common::ArithCommonChip;
 use halo2_snark_aggregator_api::transcript::sha::ShaRead;
 use halo2_snark_aggregator_api::{
     arith::ecc::ArithEccChip,
-    hash::poseidon::PoseidonChip,
+    hash::poseidon::{PoseidonChip, PoseidonSpec},
     transcript::{encode::Encode, read::TranscriptRead as APITranscriptRead},
 };
 use halo2curves::group::Curve;
@@ -27,11 +28,12 @@ pub struct CodegenTranscriptRead<
         AssignedPoint = SolidityEccExpr<C::CurveExt>,
     >,
     E: Encode<A>,
+    D: Digest,
     const T: usize,
     const RATE: usize,
 > {
     hash: PoseidonChip<A::NativeChip, T, RATE>,
-    reader: ShaRead<R, C, Challenge255<C>, sha3::Keccak256>,
+    reader: ShaRead<R, C, Challenge255<C>, D>,
     _phantom: PhantomData<E>,
 }
 
@@ -47,19 +49,19 @@ impl<
             AssignedPoint = SolidityEccExpr<C::CurveExt>,
         >,
         E: Encode<A>,
+        D: Digest,
         const T: usize,
         const RATE: usize,
-    > CodegenTranscriptRead<R, C, A, E, T, RATE>
+    > CodegenTranscriptRead<R, C, A, E, D, T, RATE>
 {
     pub fn new(
         reader: R,
         ctx: &mut A::Context,
         schip: &A::NativeChip,
-        r_f: usize,
-        r_p: usize,
-    ) -> Result<CodegenTranscriptRead<R, C, A, E, T, RATE>, A::Error> {
+        spec: &PoseidonSpec<T, RATE>,
+    ) -> Result<CodegenTranscriptRead<R, C, A, E, D, T, RATE>, A::Error> {
         Ok(CodegenTranscriptRead {
-            hash: PoseidonChip::new(ctx, schip, r_f, r_p)?,
+            hash: PoseidonChip::from_spec(ctx, schip, spec)?,
             reader: ShaRead::init(reader),
             _phantom: PhantomData,
         })
@@ -109,9 +111,10 @@ impl<
             AssignedPoint = SolidityEccExpr<C::CurveExt>,
         >,
         E: Encode<A>,
+        D: Digest,
         const T: usize,
         const RATE: usize,
-    > CodegenTranscriptRead<R, C, A, E, T, RATE>
+    > CodegenTranscriptRead<R, C, A, E, D, T, RATE>
 {
 }
 
@@ -128,9 +131,10 @@ impl<
             AssignedPoint = SolidityEccExpr<C::CurveExt>,
         >,
         E: Encode<A>,
+        D: Digest + Clone,
         const T: usize,
         const RATE: usize,
-    > APITranscriptRead<A> for CodegenTranscriptRead<R, C, A, E, T, RATE>
+    > APITranscriptRead<A> for CodegenTranscriptRead<R, C, A, E, D, T, RATE>
 {
     fn read_point(
         &mut self,